@@ -0,0 +1,211 @@
+//! Solvency check for a settled run: recompute every remaining participant's share with the
+//! program's own payout math, bonuses included, and compare what is owed against the vault.
+//! Exits non-zero when the vault cannot cover every withdrawal, so it can gate alerts or crons.
+
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anchor_client::solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anchor_client::solana_sdk::signature::Keypair;
+use anchor_client::{Client, Cluster, Program};
+use anchor_spl::token::TokenAccount;
+use anyhow::{anyhow, bail, Result};
+use clap::Parser;
+use instinct_trading::{
+    BonusMode, Platform, Run, RunStatus, Stake, UserParticipation, FEATURE_BONUSES,
+};
+use serde_json::json;
+
+/// Offset of `run_id` in a UserParticipation: discriminator, then `user`
+const PARTICIPATION_RUN_ID_OFFSET: usize = 8 + 32;
+
+#[derive(Parser)]
+#[command(name = "instinct-audit", about = "Check that a settled run's vault covers every withdrawal")]
+struct Cli {
+    /// Cluster name (localnet, devnet, mainnet) or RPC URL
+    #[arg(long, short = 'u', default_value = "localnet")]
+    url: String,
+
+    /// Platform instance id
+    #[arg(long, default_value_t = 1)]
+    platform_id: u64,
+
+    /// Print the report as JSON
+    #[arg(long)]
+    json: bool,
+
+    run_id: u64,
+}
+
+struct Payout {
+    user: Pubkey,
+    deposit: u64,
+    correct_votes: u8,
+    total_votes: u8,
+    base_share: u64,
+    entitled: u64,
+}
+
+fn pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &instinct_trading::ID).0
+}
+
+fn run_pda(platform: &Pubkey, run_id: u64, prefix: &[u8]) -> Pubkey {
+    pda(&[prefix, platform.as_ref(), &run_id.to_le_bytes()])
+}
+
+/// Participations of the run that have not withdrawn; withdraw_and_close removes the rest
+fn open_participations(
+    program: &Program<Arc<Keypair>>,
+    platform: &Pubkey,
+    run_id: u64,
+) -> Result<Vec<UserParticipation>> {
+    let filter = RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+        PARTICIPATION_RUN_ID_OFFSET,
+        &run_id.to_le_bytes(),
+    ));
+    Ok(program
+        .accounts::<UserParticipation>(vec![filter])?
+        .into_iter()
+        // Run ids repeat across platforms; only this platform's PDAs belong to the run
+        .filter(|(address, participation)| {
+            *address
+                == pda(&[b"participation", platform.as_ref(), &run_id.to_le_bytes(), participation.user.as_ref()])
+        })
+        .map(|(_, participation)| participation)
+        .filter(|participation| !participation.withdrawn)
+        .collect())
+}
+
+/// The participant's stake, if any, which withdraw reads for the vote-escrow boost
+fn stake_of(program: &Program<Arc<Keypair>>, platform: &Pubkey, user: &Pubkey) -> Option<Stake> {
+    program.account::<Stake>(pda(&[b"stake", platform.as_ref(), user.as_ref()])).ok()
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    // Read-only: the keypair only satisfies the client and never signs
+    let client = Client::new(Cluster::from_str(&cli.url)?, Arc::new(Keypair::new()));
+    let program = client.program(instinct_trading::ID)?;
+    let platform = pda(&[b"platform", &cli.platform_id.to_le_bytes()]);
+    let platform_state: Platform = program.account(platform)?;
+    let run: Run = program.account(run_pda(&platform, cli.run_id, b"run"))?;
+    if !matches!(run.status, RunStatus::Settled | RunStatus::Disputed) {
+        bail!("run #{} is not settled", cli.run_id);
+    }
+    let vault = program.account::<TokenAccount>(run_pda(&platform, cli.run_id, b"vault"))?.amount;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+    let bonuses_enabled = platform_state.is_feature_enabled(FEATURE_BONUSES);
+
+    // Probe each participant as a non-last withdrawer against an unbounded vault, which yields
+    // the uncapped entitlement instead of the last withdrawer's sweep of the remainder
+    let mut probe = run.clone();
+    probe.withdrawn_count = 0;
+    probe.participant_count = u16::MAX;
+    probe.vesting_outstanding = 0;
+
+    let participations = open_participations(&program, &platform, cli.run_id)?;
+    let mut payouts = Vec::with_capacity(participations.len());
+    for participation in &participations {
+        let boost = match run.bonus_mode {
+            BonusMode::Additive => {
+                let stake = stake_of(&program, &platform, &participation.user);
+                Stake::vote_boost_bps(stake.as_ref(), platform_state.ve_boost_weight, now)
+            }
+            BonusMode::ZeroSum => 0,
+        };
+        let entitled = probe
+            .settled_share(participation, u64::MAX, bonuses_enabled, boost)
+            .map_err(|err| anyhow!("share of {}: {}", participation.user, err))?;
+        let base_share = run
+            .base_share(participation.deposit_amount, participation.fee_exempt)
+            .map_err(|err| anyhow!("base share of {}: {}", participation.user, err))?;
+        payouts.push(Payout {
+            user: participation.user,
+            deposit: participation.deposit_amount,
+            correct_votes: participation.correct_votes,
+            total_votes: participation.total_votes,
+            base_share,
+            entitled,
+        });
+    }
+    payouts.sort_by(|a, b| b.entitled.cmp(&a.entitled));
+
+    let owed: u64 = payouts.iter().map(|payout| payout.entitled).sum();
+    let required = owed + run.vesting_outstanding;
+    let remaining = run.participant_count.saturating_sub(run.withdrawn_count);
+    // Everything paid plus the vault must still add up to the settled balance
+    let accounting_holds = run.check_solvency(vault).is_ok();
+    let solvent = vault >= required && accounting_holds;
+
+    if cli.json {
+        let participants: Vec<_> = payouts
+            .iter()
+            .map(|payout| {
+                json!({
+                    "user": payout.user.to_string(),
+                    "deposit": payout.deposit,
+                    "correct_votes": payout.correct_votes,
+                    "total_votes": payout.total_votes,
+                    "base_share": payout.base_share,
+                    "bonus": payout.entitled.saturating_sub(payout.base_share),
+                    "entitled": payout.entitled,
+                })
+            })
+            .collect();
+        let report = json!({
+            "run_id": cli.run_id,
+            "final_balance": run.final_balance,
+            "total_paid_out": run.total_paid_out,
+            "vault": vault,
+            "owed": owed,
+            "vesting_outstanding": run.vesting_outstanding,
+            "required": required,
+            "surplus": vault as i128 - required as i128,
+            "accounting_holds": accounting_holds,
+            "solvent": solvent,
+            "participants": participants,
+        });
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        println!("Run #{} ({} of {} participants left to withdraw)", cli.run_id, remaining, run.participant_count);
+        for payout in &payouts {
+            println!(
+                "  {}  deposit {}  votes {}/{}  base {}  bonus {}  owed {}",
+                payout.user,
+                run.ui(payout.deposit),
+                payout.correct_votes,
+                payout.total_votes,
+                run.ui(payout.base_share),
+                run.ui(payout.entitled.saturating_sub(payout.base_share)),
+                run.ui(payout.entitled),
+            );
+        }
+        println!("  owed to participants: {}", run.ui(owed));
+        println!("  vesting outstanding:  {}", run.ui(run.vesting_outstanding));
+        println!("  vault balance:        {}", run.ui(vault));
+        if vault >= required {
+            println!("  surplus:              {} (swept by the last withdrawer)", run.ui(vault - required));
+        } else {
+            println!("  shortfall:            {}", run.ui(required - vault));
+        }
+        if !accounting_holds {
+            println!(
+                "  paid out {} + vault {} does not match the settled balance {}",
+                run.ui(run.total_paid_out),
+                run.ui(vault),
+                run.ui(run.final_balance)
+            );
+        }
+    }
+
+    if payouts.len() != remaining as usize {
+        eprintln!("warning: found {} open participations for {} remaining participants", payouts.len(), remaining);
+    }
+    if !solvent {
+        bail!("run #{} is insolvent", cli.run_id);
+    }
+    Ok(())
+}
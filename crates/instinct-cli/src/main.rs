@@ -0,0 +1,474 @@
+//! Operator CLI for the Instinct Trading program: set up a platform, drive a run through its
+//! lifecycle and print decoded account state, signing with a local keypair.
+
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anchor_client::solana_sdk::commitment_config::CommitmentConfig;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anchor_client::solana_sdk::signature::{read_keypair_file, Keypair};
+use anchor_client::solana_sdk::system_program;
+use anchor_client::{Client, Cluster, Program};
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::{self, TokenAccount};
+use anyhow::{anyhow, bail, Context, Result};
+use clap::{Args, Parser, Subcommand};
+use instinct_trading::{
+    accounts, instruction, BonusMode, CreateRunParams, ParticipantShare, Platform, RiskParams, Run,
+    RunStatus, ShareTable,
+};
+
+#[derive(Parser)]
+#[command(name = "instinct-cli", about = "Operate Instinct Trading platforms and runs")]
+struct Cli {
+    /// Cluster name (localnet, devnet, mainnet) or RPC URL
+    #[arg(long, short = 'u', default_value = "localnet")]
+    url: String,
+
+    /// Keypair that signs and pays for every transaction
+    #[arg(long, short = 'k', default_value = "~/.config/solana/id.json")]
+    keypair: String,
+
+    /// Platform instance id
+    #[arg(long, default_value_t = 1)]
+    platform_id: u64,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create the platform with its fee vault and audit log; the keypair becomes its authority
+    InitPlatform {
+        /// Mint of the platform fee vault, normally USDC
+        #[arg(long, value_parser = parse_pubkey)]
+        usdc_mint: Pubkey,
+
+        /// Performance fee in basis points (1500 = 15%)
+        #[arg(long)]
+        fee_bps: u16,
+    },
+    /// Create the platform's next run
+    CreateRun(CreateRunArgs),
+    /// Start a run that has met its quorum
+    StartRun { run_id: u64 },
+    /// Settle a run; the share table is created and grown first if needed
+    SettleRun {
+        run_id: u64,
+
+        /// Run vault balance being settled, in base units of the run's mint
+        #[arg(long)]
+        final_balance: u64,
+
+        /// JSON array of {"user": "<pubkey>", "share_amount": <u64>}, one per participant
+        #[arg(long)]
+        shares: PathBuf,
+    },
+    /// Pause the platform, or a single run with --run
+    Pause {
+        #[arg(long)]
+        run: Option<u64>,
+    },
+    /// Print decoded account state
+    Inspect {
+        #[command(subcommand)]
+        target: InspectTarget,
+    },
+}
+
+#[derive(Subcommand)]
+enum InspectTarget {
+    Platform,
+    Run { run_id: u64 },
+}
+
+/// Run limits in base units of the run's mint; fields not listed here start disabled
+#[derive(Args)]
+struct CreateRunArgs {
+    #[arg(long)]
+    min_deposit: u64,
+
+    #[arg(long)]
+    max_deposit: u64,
+
+    #[arg(long)]
+    max_participants: u16,
+
+    /// Seconds the run may trade once started
+    #[arg(long)]
+    max_duration: i64,
+
+    /// Seconds from now that deposits stay open
+    #[arg(long, default_value_t = 24 * 60 * 60)]
+    deposit_window: i64,
+
+    #[arg(long, default_value_t = 1)]
+    min_participants: u16,
+
+    /// Cap on total deposits (0 = no cap)
+    #[arg(long, default_value_t = 0)]
+    max_run_tvl: u64,
+
+    /// Start on the deposit that fills the run
+    #[arg(long)]
+    auto_start: bool,
+
+    #[arg(long, default_value_t = 12)]
+    max_vote_rounds: u8,
+
+    /// Bonus on the profit share per correct vote (100 = 1%)
+    #[arg(long, default_value_t = 100)]
+    bonus_bps_per_correct_vote: u16,
+
+    /// Deposit mint (default = the platform fee vault's mint)
+    #[arg(long, value_parser = parse_pubkey)]
+    mint: Option<Pubkey>,
+
+    /// Largest notional of a single position
+    #[arg(long)]
+    max_position_size: u64,
+
+    /// Largest position notional over run capital (10000 = 1x)
+    #[arg(long, default_value_t = 10_000)]
+    max_leverage_bps: u32,
+
+    /// Bit i set = market index i may be traded
+    #[arg(long, default_value_t = 1)]
+    allowed_markets: u64,
+
+    #[arg(long, default_value_t = 20)]
+    max_daily_trades: u16,
+}
+
+fn parse_pubkey(value: &str) -> std::result::Result<Pubkey, String> {
+    Pubkey::from_str(value).map_err(|err| err.to_string())
+}
+
+fn pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &instinct_trading::ID).0
+}
+
+fn run_pda(platform: &Pubkey, run_id: u64, prefix: &[u8]) -> Pubkey {
+    pda(&[prefix, platform.as_ref(), &run_id.to_le_bytes()])
+}
+
+fn event_authority() -> Pubkey {
+    pda(&[b"__event_authority"])
+}
+
+fn expand_home(path: &str) -> String {
+    match (path.strip_prefix("~/"), std::env::var("HOME")) {
+        (Some(rest), Ok(home)) => format!("{}/{}", home, rest),
+        _ => path.to_string(),
+    }
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let cluster = Cluster::from_str(&cli.url)?;
+    let keypair_path = expand_home(&cli.keypair);
+    let payer = read_keypair_file(&keypair_path)
+        .map_err(|err| anyhow!("reading keypair {}: {}", keypair_path, err))?;
+    let client = Client::new_with_options(cluster, Arc::new(payer), CommitmentConfig::confirmed());
+    let program = client.program(instinct_trading::ID)?;
+    let platform = pda(&[b"platform", &cli.platform_id.to_le_bytes()]);
+
+    match cli.command {
+        Command::InitPlatform { usdc_mint, fee_bps } => {
+            init_platform(&program, cli.platform_id, platform, usdc_mint, fee_bps)
+        }
+        Command::CreateRun(args) => create_run(&program, platform, args),
+        Command::StartRun { run_id } => start_run(&program, platform, run_id),
+        Command::SettleRun { run_id, final_balance, shares } => {
+            settle_run(&program, platform, run_id, final_balance, &shares)
+        }
+        Command::Pause { run } => pause(&program, platform, run),
+        Command::Inspect { target: InspectTarget::Platform } => inspect_platform(&program, platform),
+        Command::Inspect { target: InspectTarget::Run { run_id } } => inspect_run(&program, platform, run_id),
+    }
+}
+
+fn init_platform(
+    program: &Program<Arc<Keypair>>,
+    platform_id: u64,
+    platform: Pubkey,
+    usdc_mint: Pubkey,
+    platform_fee_bps: u16,
+) -> Result<()> {
+    let signature = program
+        .request()
+        .accounts(accounts::InitializePlatform {
+            platform,
+            platform_fee_vault: pda(&[b"platform_fee_vault", platform.as_ref()]),
+            usdc_mint,
+            audit_log: pda(&[b"audit_log", platform.as_ref()]),
+            authority: program.payer(),
+            token_program: token::ID,
+            system_program: system_program::ID,
+        })
+        .args(instruction::InitializePlatform { platform_id, platform_fee_bps })
+        .send()?;
+    println!("Platform #{} initialized at {}: {}", platform_id, platform, signature);
+    Ok(())
+}
+
+fn create_run(program: &Program<Arc<Keypair>>, platform: Pubkey, args: CreateRunArgs) -> Result<()> {
+    let state: Platform = program.account(platform)?;
+    let run_id = state.total_runs + 1;
+    let mint = match args.mint {
+        Some(mint) => mint,
+        None => program.account::<TokenAccount>(state.platform_fee_vault)?.mint,
+    };
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+
+    let params = CreateRunParams {
+        min_deposit: args.min_deposit,
+        max_deposit: args.max_deposit,
+        max_participants: args.max_participants,
+        max_duration: args.max_duration,
+        max_run_tvl: args.max_run_tvl,
+        min_participants: args.min_participants,
+        min_total_deposit: 0,
+        deposit_deadline: now + args.deposit_window,
+        auto_start: args.auto_start,
+        early_access_until: 0,
+        requires_kyc: false,
+        is_private: false,
+        profit_vesting_secs: 0,
+        management_fee_bps_per_year: 0,
+        series_id: 0,
+        claim_window_secs: 0,
+        bonus_mode: BonusMode::Additive,
+        bonus_bps_per_correct_vote: args.bonus_bps_per_correct_vote,
+        max_vote_rounds: args.max_vote_rounds,
+        min_vote_deposit: 0,
+        max_rebalance_bps_per_round: 0,
+        liquidity_buffer_bps: 0,
+        max_drawdown_bps: 0,
+        stop_loss_nav: 0,
+    };
+    let risk = RiskParams {
+        max_position_size: args.max_position_size,
+        max_leverage_bps: args.max_leverage_bps,
+        allowed_markets: args.allowed_markets,
+        max_daily_trades: args.max_daily_trades,
+    };
+
+    let signature = program
+        .request()
+        .accounts(accounts::CreateRun {
+            platform,
+            run: run_pda(&platform, run_id, b"run"),
+            run_vault: run_pda(&platform, run_id, b"vault"),
+            risk_config: run_pda(&platform, run_id, b"risk_config"),
+            mint,
+            tier: None,
+            authority: program.payer(),
+            token_program: token::ID,
+            system_program: system_program::ID,
+        })
+        .args(instruction::CreateRun { params, risk })
+        .send()?;
+    println!("Run #{} created: {}", run_id, signature);
+    Ok(())
+}
+
+fn start_run(program: &Program<Arc<Keypair>>, platform: Pubkey, run_id: u64) -> Result<()> {
+    let signature = program
+        .request()
+        .accounts(accounts::StartRun {
+            platform,
+            run: run_pda(&platform, run_id, b"run"),
+            run_vault: run_pda(&platform, run_id, b"vault"),
+            automation: None,
+            authority: program.payer(),
+        })
+        .args(instruction::StartRun { run_id })
+        .send()?;
+    println!("Run #{} started: {}", run_id, signature);
+    println!("Participants' vote weights can be recorded with snapshot_vote_weight");
+    Ok(())
+}
+
+fn read_shares(path: &PathBuf) -> Result<Vec<ParticipantShare>> {
+    let data = std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+    let entries: Vec<serde_json::Value> = serde_json::from_str(&data)?;
+    entries
+        .iter()
+        .map(|entry| {
+            let user = entry["user"].as_str().ok_or_else(|| anyhow!("share entry without user: {}", entry))?;
+            let share_amount = entry["share_amount"]
+                .as_u64()
+                .ok_or_else(|| anyhow!("share entry without share_amount: {}", entry))?;
+            Ok(ParticipantShare { user: Pubkey::from_str(user)?, share_amount })
+        })
+        .collect()
+}
+
+/// Create the run's share table if missing and grow it until it holds every participant
+fn prepare_share_table(program: &Program<Arc<Keypair>>, platform: Pubkey, run: Pubkey, share_table: Pubkey, run_id: u64, participants: u16) -> Result<()> {
+    if program.rpc().get_account(&share_table).is_err() {
+        program
+            .request()
+            .accounts(accounts::InitShareTable {
+                platform,
+                run,
+                share_table,
+                authority: program.payer(),
+                system_program: system_program::ID,
+            })
+            .args(instruction::InitShareTable { run_id })
+            .send()?;
+    }
+    while ShareTable::capacity_for(program.rpc().get_account(&share_table)?.data.len()) < participants as u32 {
+        program
+            .request()
+            .accounts(accounts::AllocateShareTable {
+                platform,
+                run,
+                share_table,
+                authority: program.payer(),
+                system_program: system_program::ID,
+            })
+            .args(instruction::AllocateShareTable { run_id })
+            .send()?;
+    }
+    Ok(())
+}
+
+fn settle_run(
+    program: &Program<Arc<Keypair>>,
+    platform: Pubkey,
+    run_id: u64,
+    final_balance: u64,
+    shares: &PathBuf,
+) -> Result<()> {
+    let participant_shares = read_shares(shares)?;
+    let run = run_pda(&platform, run_id, b"run");
+    let platform_state: Platform = program.account(platform)?;
+    let run_state: Run = program.account(run)?;
+    if participant_shares.len() != run_state.participant_count as usize {
+        bail!(
+            "{} shares given for {} participants",
+            participant_shares.len(),
+            run_state.participant_count
+        );
+    }
+
+    let share_table = run_pda(&platform, run_id, b"share_table");
+    prepare_share_table(program, platform, run, share_table, run_id, run_state.participant_count)?;
+
+    let has_manager = run_state.manager != Pubkey::default();
+    let has_committee = platform_state.settlement_committee != Pubkey::default();
+    let signature = program
+        .request()
+        .accounts(accounts::SettleRun {
+            platform,
+            run,
+            run_vault: run_pda(&platform, run_id, b"vault"),
+            platform_fee_vault: pda(&[b"platform_fee_vault", platform.as_ref()]),
+            revenue_vault: (platform_state.revenue_vault != Pubkey::default())
+                .then(|| pda(&[b"revenue_vault", platform.as_ref()])),
+            manager_profile: has_manager.then(|| pda(&[b"manager", platform.as_ref(), run_state.manager.as_ref()])),
+            manager_fee_vault: has_manager
+                .then(|| pda(&[b"manager_fee_vault", platform.as_ref(), run_state.manager.as_ref()])),
+            bond_vault: run_pda(&platform, run_id, b"bond_vault"),
+            settler_token_account: get_associated_token_address(&program.payer(), &run_state.mint),
+            usdc_mint: run_state.mint,
+            share_table,
+            authority: program.payer(),
+            attestor: None,
+            settlement_committee: has_committee.then_some(platform_state.settlement_committee),
+            settlement_approval: has_committee.then(|| run_pda(&platform, run_id, b"settlement_approval")),
+            instructions: None,
+            memo_program: None,
+            token_program: token::ID,
+            system_program: system_program::ID,
+            event_authority: event_authority(),
+            program: instinct_trading::ID,
+        })
+        .args(instruction::SettleRun { run_id, final_balance, participant_shares, report: None })
+        .send()?;
+    println!("Run #{} settled: {}", run_id, signature);
+    Ok(())
+}
+
+fn pause(program: &Program<Arc<Keypair>>, platform: Pubkey, run_id: Option<u64>) -> Result<()> {
+    let audit_log = pda(&[b"audit_log", platform.as_ref()]);
+    let request = program.request();
+    let signature = match run_id {
+        Some(run_id) => request
+            .accounts(accounts::PauseRun {
+                platform,
+                audit_log,
+                run: run_pda(&platform, run_id, b"run"),
+                pauser: program.payer(),
+            })
+            .args(instruction::PauseRun { run_id })
+            .send()?,
+        None => request
+            .accounts(accounts::PausePlatform { platform, audit_log, pauser: program.payer() })
+            .args(instruction::PausePlatform {})
+            .send()?,
+    };
+    match run_id {
+        Some(run_id) => println!("Run #{} paused: {}", run_id, signature),
+        None => println!("Platform paused: {}", signature),
+    }
+    Ok(())
+}
+
+fn status_name(status: RunStatus) -> &'static str {
+    match status {
+        RunStatus::Waiting => "Waiting",
+        RunStatus::Active => "Active",
+        RunStatus::Settled => "Settled",
+        RunStatus::Cancelled => "Cancelled",
+        RunStatus::Expired => "Expired",
+        RunStatus::SettlementPending => "SettlementPending",
+        RunStatus::Disputed => "Disputed",
+        RunStatus::Refunding => "Refunding",
+    }
+}
+
+fn inspect_platform(program: &Program<Arc<Keypair>>, platform: Pubkey) -> Result<()> {
+    let state: Platform = program.account(platform)?;
+    println!("Platform #{} ({})", state.platform_id, platform);
+    println!("  authority:        {}", state.authority);
+    println!("  version:          {}", state.version);
+    println!("  paused:           {}", state.is_paused);
+    println!("  fee:              {} bps", state.platform_fee_bps);
+    println!("  runs created:     {}", state.total_runs);
+    println!("  active runs:      {} (cap {})", state.active_runs, state.max_active_runs);
+    println!("  value locked:     {}", state.total_value_locked);
+    println!("  exposure:         {} (cap {})", state.total_exposure, state.max_total_exposure);
+    println!("  fees collected:   {}", state.total_fees_collected);
+    println!("  features:         {:#x}", state.features);
+    Ok(())
+}
+
+fn inspect_run(program: &Program<Arc<Keypair>>, platform: Pubkey, run_id: u64) -> Result<()> {
+    let run = run_pda(&platform, run_id, b"run");
+    let state: Run = program.account(run)?;
+    println!("Run #{} ({})", state.run_id, run);
+    println!("  status:           {}", status_name(state.status));
+    println!("  version:          {}", state.version);
+    println!("  settler:          {}", state.settler);
+    println!("  manager:          {}", state.manager);
+    println!("  mint:             {}", state.mint);
+    println!("  paused:           {}", state.is_paused);
+    println!("  participants:     {}/{} (min {})", state.participant_count, state.max_participants, state.min_participants);
+    println!("  deposits:         {} ({} to {} each)", state.ui(state.total_deposited), state.ui(state.min_deposit), state.ui(state.max_deposit));
+    println!("  deposit deadline: {}", state.deposit_deadline);
+    println!("  started at:       {}", state.started_at);
+    println!("  ended at:         {}", state.ended_at);
+    println!("  final balance:    {}", state.ui(state.final_balance));
+    println!("  withdrawn:        {}/{}", state.withdrawn_count, state.participant_count);
+    if state.in_escrow {
+        println!("  escrowed until:   {}", state.escrow_until);
+    }
+    Ok(())
+}
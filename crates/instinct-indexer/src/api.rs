@@ -0,0 +1,212 @@
+//! Read-only JSON API over the indexed view, for the frontend.
+//!
+//! - `GET /runs?status=Active` - runs, newest first
+//! - `GET /runs/:run_id` - a run with its participations
+//! - `GET /runs/:run_id/events` - decoded events of a run, oldest first
+//! - `GET /users/:user` - a wallet's participations across runs
+//! - `GET /leaderboard?run_id=&limit=` - participants ranked by profit over settled runs,
+//!   or within a single run
+//! - `GET /squads/leaderboard?run_id=&sort=&limit=` - withdrawn squads of settled runs ranked by
+//!   return or vote accuracy
+//! - `GET /guilds/leaderboard?sort=&limit=` - squad leaders ranked across all their squads
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use rusqlite::params;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::db::{self, Db};
+
+const DEFAULT_LEADERBOARD_SIZE: u32 = 100;
+
+pub fn router(db: Db) -> Router {
+    Router::new()
+        .route("/runs", get(list_runs))
+        .route("/runs/:run_id", get(get_run))
+        .route("/runs/:run_id/events", get(run_events))
+        .route("/users/:user", get(user_participations))
+        .route("/leaderboard", get(leaderboard))
+        .route("/squads/leaderboard", get(squad_leaderboard))
+        .route("/guilds/leaderboard", get(guild_leaderboard))
+        .with_state(db)
+}
+
+struct ApiError(anyhow::Error);
+
+impl<E: Into<anyhow::Error>> From<E> for ApiError {
+    fn from(err: E) -> Self {
+        ApiError(err.into())
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": self.0.to_string() }))).into_response()
+    }
+}
+
+type ApiResult = Result<Response, ApiError>;
+
+#[derive(Deserialize)]
+struct RunFilter {
+    status: Option<String>,
+}
+
+async fn list_runs(State(db): State<Db>, Query(filter): Query<RunFilter>) -> ApiResult {
+    let conn = db.lock().unwrap();
+    let runs = db::query_json(
+        &conn,
+        "SELECT * FROM runs WHERE ?1 IS NULL OR status = ?1 ORDER BY run_id DESC",
+        params![filter.status],
+    )?;
+    Ok(Json(runs).into_response())
+}
+
+async fn get_run(State(db): State<Db>, Path(run_id): Path<u64>) -> ApiResult {
+    let conn = db.lock().unwrap();
+    let Some(mut run) = db::query_json(&conn, "SELECT * FROM runs WHERE run_id = ?1", params![run_id as i64])?
+        .pop()
+    else {
+        return Ok((StatusCode::NOT_FOUND, Json(json!({ "error": "run not found" }))).into_response());
+    };
+    let participations = db::query_json(
+        &conn,
+        "SELECT * FROM participations WHERE run_id = ?1 ORDER BY deposit_amount DESC",
+        params![run_id as i64],
+    )?;
+    run["participations"] = Value::from(participations);
+    Ok(Json(run).into_response())
+}
+
+async fn run_events(State(db): State<Db>, Path(run_id): Path<u64>) -> ApiResult {
+    let conn = db.lock().unwrap();
+    let events = db::query_json(
+        &conn,
+        "SELECT signature, slot, name, data FROM events WHERE run_id = ?1 ORDER BY slot, signature, idx",
+        params![run_id as i64],
+    )?;
+    Ok(Json(events).into_response())
+}
+
+async fn user_participations(State(db): State<Db>, Path(user): Path<String>) -> ApiResult {
+    let conn = db.lock().unwrap();
+    let participations = db::query_json(
+        &conn,
+        "SELECT p.*, r.status AS run_status FROM participations p
+         LEFT JOIN runs r ON r.run_id = p.run_id
+         WHERE p.user = ?1 ORDER BY p.run_id DESC",
+        params![user],
+    )?;
+    Ok(Json(participations).into_response())
+}
+
+#[derive(Deserialize)]
+struct LeaderboardQuery {
+    run_id: Option<u64>,
+    limit: Option<u32>,
+}
+
+/// Profit is final share minus deposit, so only settled runs rank; vote accuracy breaks ties.
+/// Squads rank on their own boards rather than as participants
+async fn leaderboard(State(db): State<Db>, Query(query): Query<LeaderboardQuery>) -> ApiResult {
+    let conn = db.lock().unwrap();
+    let rows = db::query_json(
+        &conn,
+        "SELECT p.user,
+                COUNT(*) AS runs,
+                SUM(p.deposit_amount) AS deposited,
+                SUM(p.final_share) AS returned,
+                SUM(p.final_share - p.deposit_amount) AS profit,
+                SUM(p.correct_votes) AS correct_votes,
+                SUM(p.total_votes) AS total_votes
+         FROM participations p JOIN runs r ON r.run_id = p.run_id
+         WHERE r.status = 'Settled' AND (?1 IS NULL OR p.run_id = ?1)
+           AND p.user NOT IN (SELECT address FROM squads)
+         GROUP BY p.user
+         ORDER BY profit DESC, correct_votes DESC
+         LIMIT ?2",
+        params![query.run_id.map(|id| id as i64), query.limit.unwrap_or(DEFAULT_LEADERBOARD_SIZE)],
+    )?;
+    Ok(Json(rows).into_response())
+}
+
+#[derive(Deserialize)]
+struct SquadLeaderboardQuery {
+    run_id: Option<u64>,
+    sort: Option<String>,
+    limit: Option<u32>,
+}
+
+/// Ranking for squad and guild boards: `return` (default) or `accuracy`, the other breaking ties
+fn squad_order(sort: Option<&str>) -> &'static str {
+    match sort {
+        Some("accuracy") => "accuracy_bps DESC, return_bps DESC",
+        _ => "return_bps DESC, accuracy_bps DESC",
+    }
+}
+
+/// A squad's return is only known once its share is withdrawn, so unwithdrawn squads do not rank
+async fn squad_leaderboard(State(db): State<Db>, Query(query): Query<SquadLeaderboardQuery>) -> ApiResult {
+    let conn = db.lock().unwrap();
+    let rows = db::query_json(
+        &conn,
+        &format!(
+            "SELECT s.address AS squad, s.leader, s.run_id, s.member_count,
+                    p.deposit_amount AS deposited,
+                    p.final_share AS returned,
+                    p.final_share - p.deposit_amount AS profit,
+                    (p.final_share - p.deposit_amount) * 10000 / p.deposit_amount AS return_bps,
+                    p.correct_votes, p.total_votes,
+                    COALESCE(p.correct_votes * 10000 / NULLIF(p.total_votes, 0), 0) AS accuracy_bps
+             FROM squads s
+             JOIN participations p ON p.user = s.address AND p.run_id = s.run_id
+             JOIN runs r ON r.run_id = s.run_id
+             WHERE r.status = 'Settled' AND p.withdrawn = 1 AND (?1 IS NULL OR s.run_id = ?1)
+             ORDER BY {}
+             LIMIT ?2",
+            squad_order(query.sort.as_deref())
+        ),
+        params![query.run_id.map(|id| id as i64), query.limit.unwrap_or(DEFAULT_LEADERBOARD_SIZE)],
+    )?;
+    Ok(Json(rows).into_response())
+}
+
+#[derive(Deserialize)]
+struct GuildLeaderboardQuery {
+    sort: Option<String>,
+    limit: Option<u32>,
+}
+
+/// Guilds are a leader's squads across runs, ranked on their combined return and accuracy
+async fn guild_leaderboard(State(db): State<Db>, Query(query): Query<GuildLeaderboardQuery>) -> ApiResult {
+    let conn = db.lock().unwrap();
+    let rows = db::query_json(
+        &conn,
+        &format!(
+            "SELECT s.leader,
+                    COUNT(*) AS squads,
+                    SUM(s.member_count) AS members,
+                    SUM(p.deposit_amount) AS deposited,
+                    SUM(p.final_share) AS returned,
+                    SUM(p.final_share - p.deposit_amount) AS profit,
+                    SUM(p.final_share - p.deposit_amount) * 10000 / SUM(p.deposit_amount) AS return_bps,
+                    SUM(p.correct_votes) AS correct_votes,
+                    SUM(p.total_votes) AS total_votes,
+                    COALESCE(SUM(p.correct_votes) * 10000 / NULLIF(SUM(p.total_votes), 0), 0) AS accuracy_bps
+             FROM squads s
+             JOIN participations p ON p.user = s.address AND p.run_id = s.run_id
+             JOIN runs r ON r.run_id = s.run_id
+             WHERE r.status = 'Settled' AND p.withdrawn = 1
+             GROUP BY s.leader
+             ORDER BY {}
+             LIMIT ?1",
+            squad_order(query.sort.as_deref())
+        ),
+        params![query.limit.unwrap_or(DEFAULT_LEADERBOARD_SIZE)],
+    )?;
+    Ok(Json(rows).into_response())
+}
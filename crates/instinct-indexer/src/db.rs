@@ -0,0 +1,237 @@
+//! SQLite view of a platform: one row per run, participation and squad account, plus every
+//! decoded program event. Account rows only move forward in slot order, so replays and out-of-order
+//! notifications never roll state back.
+
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use instinct_trading::{Run, RunStatus, Squad, UserParticipation};
+use rusqlite::types::ValueRef;
+use rusqlite::{params, Connection, OptionalExtension, Params};
+use serde_json::{Map, Value};
+use solana_sdk::pubkey::Pubkey;
+
+pub type Db = Arc<Mutex<Connection>>;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS runs (
+    address TEXT PRIMARY KEY,
+    run_id INTEGER NOT NULL UNIQUE,
+    status TEXT NOT NULL,
+    mint TEXT NOT NULL,
+    manager TEXT NOT NULL,
+    total_deposited INTEGER NOT NULL,
+    final_balance INTEGER NOT NULL,
+    total_withdrawn INTEGER NOT NULL,
+    participant_count INTEGER NOT NULL,
+    max_participants INTEGER NOT NULL,
+    min_deposit INTEGER NOT NULL,
+    max_deposit INTEGER NOT NULL,
+    created_at INTEGER NOT NULL,
+    started_at INTEGER NOT NULL,
+    ended_at INTEGER NOT NULL,
+    is_paused INTEGER NOT NULL,
+    slot INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS participations (
+    address TEXT PRIMARY KEY,
+    run_id INTEGER NOT NULL,
+    user TEXT NOT NULL,
+    deposit_amount INTEGER NOT NULL,
+    final_share INTEGER NOT NULL,
+    withdrawn INTEGER NOT NULL,
+    correct_votes INTEGER NOT NULL,
+    total_votes INTEGER NOT NULL,
+    closed INTEGER NOT NULL DEFAULT 0,
+    slot INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS participations_run ON participations (run_id);
+CREATE INDEX IF NOT EXISTS participations_user ON participations (user);
+CREATE TABLE IF NOT EXISTS squads (
+    address TEXT PRIMARY KEY,
+    run_id INTEGER NOT NULL,
+    leader TEXT NOT NULL,
+    total_contributed INTEGER NOT NULL,
+    member_count INTEGER NOT NULL,
+    committed INTEGER NOT NULL,
+    slot INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS squads_leader ON squads (leader);
+CREATE TABLE IF NOT EXISTS events (
+    signature TEXT NOT NULL,
+    idx INTEGER NOT NULL,
+    slot INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    run_id INTEGER NOT NULL,
+    data TEXT NOT NULL,
+    PRIMARY KEY (signature, idx)
+);
+CREATE INDEX IF NOT EXISTS events_run ON events (run_id, slot);
+";
+
+pub fn open(path: &str) -> Result<Db> {
+    let conn = Connection::open(path)?;
+    conn.execute_batch(SCHEMA)?;
+    Ok(Arc::new(Mutex::new(conn)))
+}
+
+pub fn status_name(status: RunStatus) -> &'static str {
+    match status {
+        RunStatus::Waiting => "Waiting",
+        RunStatus::Active => "Active",
+        RunStatus::Settled => "Settled",
+        RunStatus::Cancelled => "Cancelled",
+        RunStatus::Expired => "Expired",
+        RunStatus::SettlementPending => "SettlementPending",
+        RunStatus::Disputed => "Disputed",
+        RunStatus::Refunding => "Refunding",
+    }
+}
+
+pub fn upsert_run(conn: &Connection, address: &Pubkey, run: &Run, slot: u64) -> Result<()> {
+    conn.execute(
+        "INSERT INTO runs VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)
+         ON CONFLICT (address) DO UPDATE SET
+            status = excluded.status, manager = excluded.manager,
+            total_deposited = excluded.total_deposited, final_balance = excluded.final_balance,
+            total_withdrawn = excluded.total_withdrawn, participant_count = excluded.participant_count,
+            max_participants = excluded.max_participants, min_deposit = excluded.min_deposit,
+            max_deposit = excluded.max_deposit, started_at = excluded.started_at,
+            ended_at = excluded.ended_at, is_paused = excluded.is_paused, slot = excluded.slot
+         WHERE excluded.slot >= runs.slot",
+        params![
+            address.to_string(),
+            run.run_id as i64,
+            status_name(run.status),
+            run.mint.to_string(),
+            run.manager.to_string(),
+            run.total_deposited as i64,
+            run.final_balance as i64,
+            run.total_withdrawn as i64,
+            run.participant_count,
+            run.max_participants,
+            run.min_deposit as i64,
+            run.max_deposit as i64,
+            run.created_at,
+            run.started_at,
+            run.ended_at,
+            run.is_paused,
+            slot as i64,
+        ],
+    )?;
+    Ok(())
+}
+
+pub fn upsert_participation(
+    conn: &Connection,
+    address: &Pubkey,
+    participation: &UserParticipation,
+    slot: u64,
+) -> Result<()> {
+    conn.execute(
+        "INSERT INTO participations VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, 0, ?9)
+         ON CONFLICT (address) DO UPDATE SET
+            deposit_amount = excluded.deposit_amount, final_share = excluded.final_share,
+            withdrawn = excluded.withdrawn, correct_votes = excluded.correct_votes,
+            total_votes = excluded.total_votes, closed = 0, slot = excluded.slot
+         WHERE excluded.slot >= participations.slot",
+        params![
+            address.to_string(),
+            participation.run_id as i64,
+            participation.user.to_string(),
+            participation.deposit_amount as i64,
+            participation.final_share as i64,
+            participation.withdrawn,
+            participation.correct_votes,
+            participation.total_votes,
+            slot as i64,
+        ],
+    )?;
+    Ok(())
+}
+
+pub fn upsert_squad(conn: &Connection, address: &Pubkey, squad: &Squad, slot: u64) -> Result<()> {
+    conn.execute(
+        "INSERT INTO squads VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+         ON CONFLICT (address) DO UPDATE SET
+            total_contributed = excluded.total_contributed, member_count = excluded.member_count,
+            committed = excluded.committed, slot = excluded.slot
+         WHERE excluded.slot >= squads.slot",
+        params![
+            address.to_string(),
+            squad.run_id as i64,
+            squad.leader.to_string(),
+            squad.total_contributed as i64,
+            squad.member_count,
+            squad.committed,
+            slot as i64,
+        ],
+    )?;
+    Ok(())
+}
+
+/// A participation account was closed; its row keeps the last known amounts
+pub fn close_participation(conn: &Connection, address: &Pubkey, slot: u64) -> Result<()> {
+    conn.execute(
+        "UPDATE participations SET closed = 1, slot = ?2 WHERE address = ?1 AND slot <= ?2",
+        params![address.to_string(), slot as i64],
+    )?;
+    Ok(())
+}
+
+/// Record a withdraw_and_close payout, which the account itself no longer shows
+pub fn record_closing_withdrawal(conn: &Connection, run_id: u64, user: &Pubkey, share: u64) -> Result<()> {
+    conn.execute(
+        "UPDATE participations SET withdrawn = 1, final_share = ?3, closed = 1 WHERE run_id = ?1 AND user = ?2",
+        params![run_id as i64, user.to_string(), share as i64],
+    )?;
+    Ok(())
+}
+
+pub fn insert_event(
+    conn: &Connection,
+    signature: &str,
+    idx: usize,
+    slot: u64,
+    name: &str,
+    run_id: u64,
+    data: &Value,
+) -> Result<bool> {
+    let inserted = conn.execute(
+        "INSERT OR IGNORE INTO events VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![signature, idx as i64, slot as i64, name, run_id as i64, data.to_string()],
+    )?;
+    Ok(inserted > 0)
+}
+
+/// Newest transaction whose events have been stored, where a signature backfill resumes
+pub fn last_signature(conn: &Connection) -> Result<Option<String>> {
+    Ok(conn
+        .query_row("SELECT signature FROM events ORDER BY slot DESC, idx DESC LIMIT 1", [], |row| row.get(0))
+        .optional()?)
+}
+
+/// Run a query and return its rows as JSON objects keyed by column name
+pub fn query_json(conn: &Connection, sql: &str, params: impl Params) -> Result<Vec<Value>> {
+    let mut statement = conn.prepare(sql)?;
+    let columns: Vec<String> = statement.column_names().into_iter().map(String::from).collect();
+    let rows = statement.query_map(params, |row| {
+        let mut object = Map::new();
+        for (index, column) in columns.iter().enumerate() {
+            let value = match row.get_ref(index)? {
+                ValueRef::Null => Value::Null,
+                ValueRef::Integer(value) => Value::from(value),
+                ValueRef::Real(value) => Value::from(value),
+                ValueRef::Text(text) => match column.as_str() {
+                    // Event payloads are stored as JSON text
+                    "data" => serde_json::from_slice(text).unwrap_or(Value::Null),
+                    _ => Value::from(String::from_utf8_lossy(text).into_owned()),
+                },
+                ValueRef::Blob(_) => Value::Null,
+            };
+            object.insert(column.clone(), value);
+        }
+        Ok(Value::Object(object))
+    })?;
+    Ok(rows.collect::<rusqlite::Result<_>>()?)
+}
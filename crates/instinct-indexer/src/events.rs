@@ -0,0 +1,135 @@
+//! Decoding of the program's `emit_cpi!` events, which arrive as self-invocations whose data
+//! is the event instruction tag, the event discriminator and the Borsh-encoded event.
+
+use anchor_lang::event::EVENT_IX_TAG_LE;
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use instinct_trading::{
+    Rebalanced, RoundDecisionFinalized, RunSettled, StrategySettled, WithdrawnAndClosed,
+};
+use serde_json::{json, Value};
+use solana_sdk::pubkey::Pubkey;
+use solana_transaction_status::option_serializer::OptionSerializer;
+use solana_transaction_status::{EncodedConfirmedTransactionWithStatusMeta, UiInstruction};
+
+pub struct ProgramEvent {
+    pub name: &'static str,
+    pub run_id: u64,
+    pub data: Value,
+    /// Participant paid out and closed by withdraw_and_close, with the share paid
+    pub closed_withdrawal: Option<(Pubkey, u64)>,
+}
+
+fn parse<T: AnchorDeserialize>(mut payload: &[u8]) -> Option<T> {
+    T::deserialize(&mut payload).ok()
+}
+
+pub fn decode(data: &[u8]) -> Option<ProgramEvent> {
+    let data = data.strip_prefix(EVENT_IX_TAG_LE)?;
+    if data.len() < 8 {
+        return None;
+    }
+    let (discriminator, payload) = data.split_at(8);
+
+    let event = if discriminator == RunSettled::DISCRIMINATOR {
+        let event: RunSettled = parse(payload)?;
+        ProgramEvent {
+            name: "RunSettled",
+            run_id: event.run_id,
+            data: json!({
+                "total_deposited": event.total_deposited,
+                "final_balance": event.final_balance,
+                "performance_fee": event.performance_fee,
+                "management_fee": event.management_fee,
+                "manager_fee": event.manager_fee,
+                "available": event.available,
+                "ended_at": event.ended_at,
+            }),
+            closed_withdrawal: None,
+        }
+    } else if discriminator == RoundDecisionFinalized::DISCRIMINATOR {
+        let event: RoundDecisionFinalized = parse(payload)?;
+        ProgramEvent {
+            name: "RoundDecisionFinalized",
+            run_id: event.run_id,
+            data: json!({
+                "round": event.round,
+                "decision": event.decision,
+                "decision_weight": event.decision_weight,
+                "total_weight": event.total_weight,
+            }),
+            closed_withdrawal: None,
+        }
+    } else if discriminator == StrategySettled::DISCRIMINATOR {
+        let event: StrategySettled = parse(payload)?;
+        ProgramEvent {
+            name: "StrategySettled",
+            run_id: event.run_id,
+            data: json!({
+                "index": event.index,
+                "agent": event.agent.to_string(),
+                "capital_in": event.capital_in,
+                "capital_out": event.capital_out,
+                "pnl": event.pnl,
+            }),
+            closed_withdrawal: None,
+        }
+    } else if discriminator == Rebalanced::DISCRIMINATOR {
+        let event: Rebalanced = parse(payload)?;
+        ProgramEvent {
+            name: "Rebalanced",
+            run_id: event.run_id,
+            data: json!({
+                "from_subvault": event.from_subvault,
+                "to_subvault": event.to_subvault,
+                "amount": event.amount,
+                "moved_this_round": event.moved_this_round,
+            }),
+            closed_withdrawal: None,
+        }
+    } else if discriminator == WithdrawnAndClosed::DISCRIMINATOR {
+        let event: WithdrawnAndClosed = parse(payload)?;
+        ProgramEvent {
+            name: "WithdrawnAndClosed",
+            run_id: event.run_id,
+            data: json!({
+                "user": event.user.to_string(),
+                "share": event.share,
+                "rent_refunded": event.rent_refunded,
+            }),
+            closed_withdrawal: Some((event.user, event.share)),
+        }
+    } else {
+        return None;
+    };
+    Some(event)
+}
+
+/// Events emitted by the program in a confirmed transaction, in execution order
+pub fn from_transaction(transaction: &EncodedConfirmedTransactionWithStatusMeta) -> Vec<ProgramEvent> {
+    let Some(decoded) = transaction.transaction.transaction.decode() else {
+        return Vec::new();
+    };
+    let Some(meta) = &transaction.transaction.meta else {
+        return Vec::new();
+    };
+    if meta.err.is_some() {
+        return Vec::new();
+    }
+    let OptionSerializer::Some(inner_instructions) = &meta.inner_instructions else {
+        return Vec::new();
+    };
+    // Invoked programs are always static keys, never loaded from a lookup table
+    let keys = decoded.message.static_account_keys();
+
+    inner_instructions
+        .iter()
+        .flat_map(|inner| &inner.instructions)
+        .filter_map(|instruction| match instruction {
+            UiInstruction::Compiled(compiled) => Some(compiled),
+            UiInstruction::Parsed(_) => None,
+        })
+        .filter(|compiled| keys.get(compiled.program_id_index as usize) == Some(&instinct_trading::ID))
+        .filter_map(|compiled| bs58::decode(&compiled.data).into_vec().ok())
+        .filter_map(|data| decode(&data))
+        .collect()
+}
@@ -0,0 +1,269 @@
+//! Indexer for the Instinct Trading program. It keeps a SQLite view of one platform's runs,
+//! participations and squads from a program account subscription, stores the program's events from a log
+//! subscription, and serves both to the frontend as JSON.
+//!
+//! Account state is backfilled with getProgramAccounts on every (re)connect, and events missed
+//! while disconnected are recovered by walking the program's signatures back to the last one
+//! stored, so restarts leave no gaps.
+
+mod api;
+mod db;
+mod events;
+
+use std::net::SocketAddr;
+use std::str::FromStr;
+use std::thread;
+use std::time::Duration;
+
+use anchor_lang::{AccountDeserialize, Discriminator};
+use anyhow::Result;
+use clap::Parser;
+use instinct_trading::{Run, Squad, UserParticipation};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::pubsub_client::PubsubClient;
+use solana_client::rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient};
+use solana_client::rpc_config::{
+    RpcAccountInfoConfig, RpcProgramAccountsConfig, RpcTransactionConfig, RpcTransactionLogsConfig,
+    RpcTransactionLogsFilter,
+};
+use solana_sdk::account::Account;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_transaction_status::UiTransactionEncoding;
+
+use crate::db::Db;
+
+/// Wait before resubscribing after a websocket drops
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+#[derive(Parser, Clone)]
+#[command(name = "instinct-indexer", about = "Index Instinct Trading runs and serve them as JSON")]
+struct Cli {
+    /// JSON-RPC endpoint
+    #[arg(long, default_value = "http://127.0.0.1:8899")]
+    rpc_url: String,
+
+    /// Websocket endpoint (default = the RPC URL on the next port, as solana-test-validator serves it)
+    #[arg(long)]
+    ws_url: Option<String>,
+
+    /// Platform instance to index
+    #[arg(long, default_value_t = 1)]
+    platform_id: u64,
+
+    /// SQLite database file, created if missing
+    #[arg(long, default_value = "instinct-indexer.sqlite")]
+    db: String,
+
+    /// Address the JSON API listens on
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    listen: SocketAddr,
+}
+
+impl Cli {
+    fn ws_url(&self) -> String {
+        if let Some(url) = &self.ws_url {
+            return url.clone();
+        }
+        let url = self.rpc_url.replacen("https://", "wss://", 1).replacen("http://", "ws://", 1);
+        match url.rsplit_once(':').and_then(|(host, port)| Some((host, port.parse::<u16>().ok()?))) {
+            Some((host, port)) => format!("{}:{}", host, port + 1),
+            None => url,
+        }
+    }
+}
+
+fn pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &instinct_trading::ID).0
+}
+
+/// Store a program account if it is a run, participation or squad of the indexed platform
+fn index_account(db: &Db, platform: &Pubkey, address: &Pubkey, account: &Account, slot: u64) -> Result<()> {
+    let conn = db.lock().unwrap();
+    let data = account.data.as_slice();
+    if account.lamports == 0 || data.len() < 8 {
+        // Of the accounts in the view, only participations are ever closed
+        return db::close_participation(&conn, address, slot);
+    }
+    if data.starts_with(Run::DISCRIMINATOR) {
+        let Ok(run) = Run::try_deserialize(&mut &data[..]) else {
+            eprintln!("skipping run {}: layout not yet migrated", address);
+            return Ok(());
+        };
+        if run.platform == *platform {
+            db::upsert_run(&conn, address, &run, slot)?;
+        }
+    } else if data.starts_with(UserParticipation::DISCRIMINATOR) {
+        let Ok(participation) = UserParticipation::try_deserialize(&mut &data[..]) else {
+            eprintln!("skipping participation {}: layout not yet migrated", address);
+            return Ok(());
+        };
+        // Participations do not store their platform; their address does
+        let expected = pda(&[
+            b"participation",
+            platform.as_ref(),
+            &participation.run_id.to_le_bytes(),
+            participation.user.as_ref(),
+        ]);
+        if expected == *address {
+            db::upsert_participation(&conn, address, &participation, slot)?;
+        }
+    } else if data.starts_with(Squad::DISCRIMINATOR) {
+        let Ok(squad) = Squad::try_deserialize(&mut &data[..]) else {
+            return Ok(());
+        };
+        let expected = pda(&[b"squad", platform.as_ref(), &squad.run_id.to_le_bytes(), squad.leader.as_ref()]);
+        if expected == *address {
+            db::upsert_squad(&conn, address, &squad, slot)?;
+        }
+    }
+    Ok(())
+}
+
+fn account_config() -> RpcAccountInfoConfig {
+    RpcAccountInfoConfig {
+        encoding: Some(UiAccountEncoding::Base64),
+        commitment: Some(CommitmentConfig::confirmed()),
+        ..Default::default()
+    }
+}
+
+fn backfill_accounts(rpc: &RpcClient, db: &Db, platform: &Pubkey) -> Result<()> {
+    let slot = rpc.get_slot_with_commitment(CommitmentConfig::confirmed())?;
+    let accounts = rpc.get_program_accounts_with_config(
+        &instinct_trading::ID,
+        RpcProgramAccountsConfig { account_config: account_config(), ..Default::default() },
+    )?;
+    for (address, account) in &accounts {
+        index_account(db, platform, address, account, slot)?;
+    }
+    println!("backfilled {} program accounts at slot {}", accounts.len(), slot);
+    Ok(())
+}
+
+/// Decode and store the events of one transaction
+fn index_transaction(rpc: &RpcClient, db: &Db, signature: &Signature) -> Result<()> {
+    let transaction = rpc.get_transaction_with_config(
+        signature,
+        RpcTransactionConfig {
+            encoding: Some(UiTransactionEncoding::Base64),
+            commitment: Some(CommitmentConfig::confirmed()),
+            max_supported_transaction_version: Some(0),
+        },
+    )?;
+    let conn = db.lock().unwrap();
+    for (idx, event) in events::from_transaction(&transaction).into_iter().enumerate() {
+        let inserted = db::insert_event(
+            &conn,
+            &signature.to_string(),
+            idx,
+            transaction.slot,
+            event.name,
+            event.run_id,
+            &event.data,
+        )?;
+        if let (true, Some((user, share))) = (inserted, event.closed_withdrawal) {
+            db::record_closing_withdrawal(&conn, event.run_id, &user, share)?;
+        }
+    }
+    Ok(())
+}
+
+/// Index every program transaction since the last one stored, oldest first
+fn backfill_events(rpc: &RpcClient, db: &Db) -> Result<()> {
+    let until = db::last_signature(&db.lock().unwrap())?
+        .map(|signature| Signature::from_str(&signature))
+        .transpose()?;
+    let mut before = None;
+    let mut missed = Vec::new();
+    loop {
+        let page = rpc.get_signatures_for_address_with_config(
+            &instinct_trading::ID,
+            GetConfirmedSignaturesForAddress2Config {
+                before,
+                until,
+                limit: None,
+                commitment: Some(CommitmentConfig::confirmed()),
+            },
+        )?;
+        let Some(oldest) = page.last() else {
+            break;
+        };
+        before = Some(Signature::from_str(&oldest.signature)?);
+        missed.extend(page.into_iter().filter(|status| status.err.is_none()));
+    }
+    for status in missed.iter().rev() {
+        index_transaction(rpc, db, &Signature::from_str(&status.signature)?)?;
+    }
+    println!("backfilled events from {} transactions", missed.len());
+    Ok(())
+}
+
+/// Follow account changes until the subscription drops
+fn follow_accounts(cli: &Cli, db: &Db, platform: &Pubkey) -> Result<()> {
+    let (_subscription, receiver) = PubsubClient::program_subscribe(
+        &cli.ws_url(),
+        &instinct_trading::ID,
+        Some(RpcProgramAccountsConfig { account_config: account_config(), ..Default::default() }),
+    )?;
+    // Subscribe first so nothing changes unseen between the backfill and the first notification
+    backfill_accounts(&RpcClient::new_with_commitment(cli.rpc_url.clone(), CommitmentConfig::confirmed()), db, platform)?;
+    for update in receiver {
+        let address = Pubkey::from_str(&update.value.pubkey)?;
+        if let Some(account) = update.value.account.decode::<Account>() {
+            index_account(db, platform, &address, &account, update.context.slot)?;
+        }
+    }
+    Ok(())
+}
+
+/// Follow program transactions until the subscription drops
+fn follow_events(cli: &Cli, db: &Db) -> Result<()> {
+    let rpc = RpcClient::new_with_commitment(cli.rpc_url.clone(), CommitmentConfig::confirmed());
+    let (_subscription, receiver) = PubsubClient::logs_subscribe(
+        &cli.ws_url(),
+        RpcTransactionLogsFilter::Mentions(vec![instinct_trading::ID.to_string()]),
+        RpcTransactionLogsConfig { commitment: Some(CommitmentConfig::confirmed()) },
+    )?;
+    backfill_events(&rpc, db)?;
+    for notification in receiver {
+        if notification.value.err.is_some() {
+            continue;
+        }
+        let signature = Signature::from_str(&notification.value.signature)?;
+        if let Err(err) = index_transaction(&rpc, db, &signature) {
+            eprintln!("indexing {}: {}", signature, err);
+        }
+    }
+    Ok(())
+}
+
+/// Run a subscription loop on its own thread, reconnecting whenever it ends
+fn spawn_follower(name: &'static str, follow: impl Fn() -> Result<()> + Send + 'static) {
+    thread::spawn(move || loop {
+        match follow() {
+            Ok(()) => eprintln!("{} subscription closed, reconnecting", name),
+            Err(err) => eprintln!("{} subscription failed: {}, reconnecting", name, err),
+        }
+        thread::sleep(RECONNECT_DELAY);
+    });
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let db = db::open(&cli.db)?;
+    let platform = pda(&[b"platform", &cli.platform_id.to_le_bytes()]);
+    println!("indexing platform #{} ({}) into {}", cli.platform_id, platform, cli.db);
+
+    let (account_cli, account_db) = (cli.clone(), db.clone());
+    spawn_follower("account", move || follow_accounts(&account_cli, &account_db, &platform));
+    let (event_cli, event_db) = (cli.clone(), db.clone());
+    spawn_follower("event", move || follow_events(&event_cli, &event_db));
+
+    let listener = tokio::net::TcpListener::bind(cli.listen).await?;
+    println!("serving JSON API on http://{}", cli.listen);
+    axum::serve(listener, api::router(db)).await?;
+    Ok(())
+}
@@ -0,0 +1,317 @@
+//! Integration test harness for Instinct Trading on solana-program-test.
+//!
+//! `TestPlatform::start` boots a bank with the program loaded from `instinct_trading.so`
+//! (run `anchor build` first, or point `BPF_OUT_DIR` at the build output), mints a 6-decimal
+//! test USDC and initializes platform #1 with the bank's payer as authority.
+//!
+//! ```no_run
+//! # async fn lifecycle() -> Result<(), solana_program_test::BanksClientError> {
+//! use instinct_test_utils::TestPlatform;
+//!
+//! let mut platform = TestPlatform::start().await;
+//! let run_id = platform.create_run(TestPlatform::run_params(), TestPlatform::risk_params()).await?;
+//! let user = platform.create_funded_user(100_000_000).await;
+//! platform.deposit(&user, run_id, 50_000_000).await?;
+//! platform.start_run(run_id).await?;
+//! platform.advance_clock(24 * 60 * 60).await;
+//! # Ok(())
+//! # }
+//! ```
+
+use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::{get_associated_token_address, spl_associated_token_account};
+use anchor_spl::token::spl_token;
+use anchor_spl::token::spl_token::solana_program::program_pack::Pack;
+use instinct_trading::{accounts, instruction, BonusMode, CreateRunParams, Platform, RiskParams, Run};
+use solana_program_test::{BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::clock::Clock;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::Transaction;
+use solana_system_interface::instruction as system_instruction;
+
+/// Platform instance the harness initializes
+pub const PLATFORM_ID: u64 = 1;
+
+/// Performance fee of the harness platform (15%)
+pub const PLATFORM_FEE_BPS: u16 = 1500;
+
+/// Lamports each funded user receives for rent and fees
+pub const USER_LAMPORTS: u64 = 10_000_000_000;
+
+pub fn pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &instinct_trading::ID).0
+}
+
+/// PDA of a per-run account, e.g. `run_pda(&platform, 1, b"vault")`
+pub fn run_pda(platform: &Pubkey, run_id: u64, prefix: &[u8]) -> Pubkey {
+    pda(&[prefix, platform.as_ref(), &run_id.to_le_bytes()])
+}
+
+/// Instruction for the program from its generated accounts and args structs
+pub fn program_instruction(accounts: impl ToAccountMetas, args: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: instinct_trading::ID,
+        accounts: accounts.to_account_metas(None),
+        data: args.data(),
+    }
+}
+
+pub struct TestPlatform {
+    pub context: ProgramTestContext,
+    pub usdc_mint: Pubkey,
+    pub platform: Pubkey,
+}
+
+impl TestPlatform {
+    /// Boot the bank, create test USDC and initialize the platform
+    pub async fn start() -> Self {
+        let program_test = ProgramTest::new("instinct_trading", instinct_trading::ID, None);
+        let context = program_test.start_with_context().await;
+        let usdc_mint = Keypair::new();
+        let mut harness = Self {
+            context,
+            usdc_mint: usdc_mint.pubkey(),
+            platform: pda(&[b"platform", &PLATFORM_ID.to_le_bytes()]),
+        };
+        harness.create_usdc_mint(&usdc_mint).await;
+        harness.initialize_platform().await;
+        harness
+    }
+
+    /// Platform authority, mint authority of test USDC and fee payer of every transaction
+    pub fn authority(&self) -> Keypair {
+        self.context.payer.insecure_clone()
+    }
+
+    /// Sign with the authority plus `signers` and process `instructions` in one transaction
+    pub async fn process(&mut self, instructions: &[Instruction], signers: &[&Keypair]) -> Result<(), BanksClientError> {
+        let authority = self.authority();
+        let mut all_signers = vec![&authority];
+        all_signers.extend_from_slice(signers);
+        let blockhash = self.context.banks_client.get_latest_blockhash().await?;
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&authority.pubkey()),
+            &all_signers,
+            blockhash,
+        );
+        self.context.banks_client.process_transaction(transaction).await
+    }
+
+    /// Fetch and decode a program account
+    pub async fn account<T: AccountDeserialize>(&mut self, address: Pubkey) -> T {
+        let account = self.context.banks_client
+            .get_account(address)
+            .await
+            .unwrap()
+            .unwrap_or_else(|| panic!("account {} not found", address));
+        T::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    pub async fn platform_state(&mut self) -> Platform {
+        self.account(self.platform).await
+    }
+
+    pub async fn run_state(&mut self, run_id: u64) -> Run {
+        self.account(run_pda(&self.platform, run_id, b"run")).await
+    }
+
+    /// Token balance of an SPL token account
+    pub async fn token_balance(&mut self, address: Pubkey) -> u64 {
+        let account = self.context.banks_client.get_account(address).await.unwrap().unwrap();
+        spl_token::state::Account::unpack(&account.data).unwrap().amount
+    }
+
+    /// Move the bank clock forward; the slot stays put, so only time-based checks are affected
+    pub async fn advance_clock(&mut self, secs: i64) {
+        let mut clock: Clock = self.context.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp += secs;
+        self.context.set_sysvar(&clock);
+    }
+
+    async fn create_usdc_mint(&mut self, mint: &Keypair) {
+        let authority = self.authority().pubkey();
+        let rent = self.context.banks_client.get_rent().await.unwrap();
+        let instructions = [
+            system_instruction::create_account(
+                &authority,
+                &mint.pubkey(),
+                rent.minimum_balance(spl_token::state::Mint::LEN),
+                spl_token::state::Mint::LEN as u64,
+                &spl_token::ID,
+            ),
+            spl_token::instruction::initialize_mint2(
+                &spl_token::ID,
+                &mint.pubkey(),
+                &authority,
+                None,
+                instinct_trading::USDC_DECIMALS,
+            )
+            .unwrap(),
+        ];
+        self.process(&instructions, &[mint]).await.unwrap();
+    }
+
+    async fn initialize_platform(&mut self) {
+        let platform = self.platform;
+        let instruction = program_instruction(
+            accounts::InitializePlatform {
+                platform,
+                platform_fee_vault: pda(&[b"platform_fee_vault", platform.as_ref()]),
+                usdc_mint: self.usdc_mint,
+                audit_log: pda(&[b"audit_log", platform.as_ref()]),
+                authority: self.authority().pubkey(),
+                token_program: spl_token::ID,
+                system_program: solana_system_interface::program::ID,
+            },
+            instruction::InitializePlatform { platform_id: PLATFORM_ID, platform_fee_bps: PLATFORM_FEE_BPS },
+        );
+        self.process(&[instruction], &[]).await.unwrap();
+    }
+
+    /// Mint test USDC into `owner`'s associated token account, creating it if needed
+    pub async fn mint_usdc(&mut self, owner: &Pubkey, amount: u64) -> Pubkey {
+        let authority = self.authority().pubkey();
+        let token_account = get_associated_token_address(owner, &self.usdc_mint);
+        let instructions = [
+            spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+                &authority,
+                owner,
+                &self.usdc_mint,
+                &spl_token::ID,
+            ),
+            spl_token::instruction::mint_to(&spl_token::ID, &self.usdc_mint, &token_account, &authority, &[], amount)
+                .unwrap(),
+        ];
+        self.process(&instructions, &[]).await.unwrap();
+        token_account
+    }
+
+    /// New wallet holding USER_LAMPORTS and `usdc` test USDC in its associated token account
+    pub async fn create_funded_user(&mut self, usdc: u64) -> Keypair {
+        let user = Keypair::new();
+        let transfer = system_instruction::transfer(&self.authority().pubkey(), &user.pubkey(), USER_LAMPORTS);
+        self.process(&[transfer], &[]).await.unwrap();
+        self.mint_usdc(&user.pubkey(), usdc).await;
+        user
+    }
+
+    /// Run accepting 10-1,000 USDC from up to 10 participants with no deposit deadline, trading
+    /// for a day; every optional feature is off
+    pub fn run_params() -> CreateRunParams {
+        CreateRunParams {
+            min_deposit: 10_000_000,
+            max_deposit: 1_000_000_000,
+            max_participants: 10,
+            max_duration: 24 * 60 * 60,
+            max_run_tvl: 0,
+            min_participants: 1,
+            min_total_deposit: 0,
+            deposit_deadline: i64::MAX / 2,
+            auto_start: false,
+            early_access_until: 0,
+            requires_kyc: false,
+            is_private: false,
+            profit_vesting_secs: 0,
+            management_fee_bps_per_year: 0,
+            series_id: 0,
+            claim_window_secs: 0,
+            bonus_mode: BonusMode::Additive,
+            bonus_bps_per_correct_vote: 100,
+            max_vote_rounds: 12,
+            min_vote_deposit: 0,
+            max_rebalance_bps_per_round: 0,
+            liquidity_buffer_bps: 0,
+            max_drawdown_bps: 0,
+            stop_loss_nav: 0,
+        }
+    }
+
+    /// 1,000 USDC positions at up to 3x on market 0, 20 trades a day
+    pub fn risk_params() -> RiskParams {
+        RiskParams {
+            max_position_size: 1_000_000_000,
+            max_leverage_bps: 30_000,
+            allowed_markets: 1,
+            max_daily_trades: 20,
+        }
+    }
+
+    /// Create the platform's next run with the authority as operator; returns its id
+    pub async fn create_run(&mut self, params: CreateRunParams, risk: RiskParams) -> Result<u64, BanksClientError> {
+        let platform = self.platform;
+        let run_id = self.platform_state().await.total_runs + 1;
+        let instruction = program_instruction(
+            accounts::CreateRun {
+                platform,
+                run: run_pda(&platform, run_id, b"run"),
+                run_vault: run_pda(&platform, run_id, b"vault"),
+                risk_config: run_pda(&platform, run_id, b"risk_config"),
+                mint: self.usdc_mint,
+                tier: None,
+                authority: self.authority().pubkey(),
+                token_program: spl_token::ID,
+                system_program: solana_system_interface::program::ID,
+            },
+            instruction::CreateRun { params, risk },
+        );
+        self.process(&[instruction], &[]).await?;
+        Ok(run_id)
+    }
+
+    /// Deposit from `user`'s associated token account into a public run
+    pub async fn deposit(&mut self, user: &Keypair, run_id: u64, amount: u64) -> Result<(), BanksClientError> {
+        let platform = self.platform;
+        let page_index = self.run_state(run_id).await.participant_page_index();
+        let instruction = program_instruction(
+            accounts::Deposit {
+                platform,
+                run: run_pda(&platform, run_id, b"run"),
+                user_participation: pda(&[
+                    b"participation",
+                    platform.as_ref(),
+                    &run_id.to_le_bytes(),
+                    user.pubkey().as_ref(),
+                ]),
+                participant_page: pda(&[
+                    b"participant_page",
+                    platform.as_ref(),
+                    &run_id.to_le_bytes(),
+                    &page_index.to_le_bytes(),
+                ]),
+                run_vault: run_pda(&platform, run_id, b"vault"),
+                user_token_account: get_associated_token_address(&user.pubkey(), &self.usdc_mint),
+                usdc_mint: self.usdc_mint,
+                stake: None,
+                kyc_attestation: None,
+                invite: None,
+                blacklist_entry: pda(&[b"blacklist", platform.as_ref(), user.pubkey().as_ref()]),
+                user: user.pubkey(),
+                rent_treasury: None,
+                memo_program: None,
+                token_program: spl_token::ID,
+                system_program: solana_system_interface::program::ID,
+            },
+            instruction::Deposit { run_id, amount, invite_code: None },
+        );
+        self.process(&[instruction], &[user]).await
+    }
+
+    /// Start a run as its authority, without snapshotting vote weights
+    pub async fn start_run(&mut self, run_id: u64) -> Result<(), BanksClientError> {
+        let platform = self.platform;
+        let instruction = program_instruction(
+            accounts::StartRun {
+                platform,
+                run: run_pda(&platform, run_id, b"run"),
+                run_vault: run_pda(&platform, run_id, b"vault"),
+                automation: None,
+                authority: self.authority().pubkey(),
+            },
+            instruction::StartRun { run_id },
+        );
+        self.process(&[instruction], &[]).await
+    }
+}
@@ -0,0 +1,37 @@
+//! Run lifecycle against the built program. Build it with `anchor build` and run these with
+//! `SBF_OUT_DIR=../target/deploy cargo test -p instinct-test-utils` from `crates/`.
+
+use instinct_test_utils::{run_pda, TestPlatform};
+use instinct_trading::RunStatus;
+
+#[tokio::test]
+async fn deposit_then_start() {
+    let mut platform = TestPlatform::start().await;
+    let run_id = platform.create_run(TestPlatform::run_params(), TestPlatform::risk_params()).await.unwrap();
+    let user = platform.create_funded_user(100_000_000).await;
+
+    platform.deposit(&user, run_id, 50_000_000).await.unwrap();
+    platform.start_run(run_id).await.unwrap();
+
+    let run = platform.run_state(run_id).await;
+    assert!(run.status == RunStatus::Active);
+    assert_eq!(run.participant_count, 1);
+    assert_eq!(run.total_deposited, 50_000_000);
+    let vault = run_pda(&platform.platform, run_id, b"vault");
+    assert_eq!(platform.token_balance(vault).await, 50_000_000);
+}
+
+#[tokio::test]
+async fn deposit_below_minimum_is_rejected() {
+    let mut platform = TestPlatform::start().await;
+    let run_id = platform.create_run(TestPlatform::run_params(), TestPlatform::risk_params()).await.unwrap();
+    let user = platform.create_funded_user(100_000_000).await;
+
+    // run_params accepts 10-1,000 USDC
+    assert!(platform.deposit(&user, run_id, 5_000_000).await.is_err());
+
+    let run = platform.run_state(run_id).await;
+    assert!(run.status == RunStatus::Waiting);
+    assert_eq!(run.participant_count, 0);
+    assert_eq!(run.total_deposited, 0);
+}
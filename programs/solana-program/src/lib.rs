@@ -1,8 +1,126 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_lang::solana_program::secp256k1_program;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use anchor_lang::system_program;
+use core::fmt;
+use anchor_spl::associated_token::{get_associated_token_address, AssociatedToken};
+use anchor_spl::memo::{self, BuildMemo, Memo};
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 
 declare_id!("6EYowRZgeA51JkwPJ5R1wnhxTYHYumnGYrNZwcGegCnc");
 
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Time past a run's max duration before anyone may force it into refunds
+pub const SETTLEMENT_GRACE_PERIOD_SECS: i64 = 3 * 24 * 60 * 60;
+
+/// Year length used to accrue annualized management fees
+pub const SECONDS_PER_YEAR: i64 = 365 * 24 * 60 * 60;
+
+/// Fixed-point scale of the platform's revenue-per-staked-token accumulator
+pub const REVENUE_PRECISION: u128 = 1_000_000_000_000;
+
+/// Minimum time between revenue distributions to stakers
+pub const REVENUE_EPOCH_SECS: i64 = 24 * 60 * 60;
+
+/// Minimum delay between queueing a scheduled admin action and executing it
+pub const MIN_ACTION_DELAY_SECS: i64 = 2 * 24 * 60 * 60;
+
+/// Admin actions kept in a platform's audit log before the oldest are overwritten
+pub const AUDIT_LOG_CAPACITY: usize = 64;
+
+/// Longest vote-escrow lock; a lock this long gives ve_weight equal to the locked amount
+pub const MAX_LOCK_SECS: i64 = 4 * SECONDS_PER_YEAR;
+
+/// Vote-accuracy bonus boost at full ve_weight (5000 = bonus × 1.5)
+pub const MAX_VE_VOTE_BOOST_BPS: u64 = 5000;
+
+/// Starting value of a manager's compounded equity index (1.0)
+pub const EQUITY_INDEX_BASE: u64 = 1_000_000;
+
+/// Decimals run limits are quoted in when a run is created (USDC)
+pub const USDC_DECIMALS: u8 = 6;
+
+/// Pyth pull-oracle receiver program that owns PriceUpdateV2 accounts
+pub const PYTH_RECEIVER_PROGRAM_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+
+/// Address lookup table program, called with hand-encoded instructions
+pub const ADDRESS_LOOKUP_TABLE_PROGRAM_ID: Pubkey = pubkey!("AddressLookupTab1e1111111111111111111111111");
+
+/// Oldest stablecoin price accepted for deposit conversion
+pub const MAX_PRICE_AGE_SECS: i64 = 60;
+
+/// Largest deviation from $1 at which a stablecoin deposit is still accepted
+pub const MAX_DEPEG_BPS: u64 = 200;
+
+/// Widest price confidence interval accepted, relative to the price
+pub const MAX_PRICE_CONF_BPS: u64 = 100;
+
+/// Prefix of the message an EVM wallet signs to link itself to a Solana wallet
+pub const EVM_LINK_DOMAIN: &[u8] = b"instinct-evm-link";
+
+/// Stake-based fee discount tiers configurable per platform
+pub const STAKE_TIER_COUNT: usize = 3;
+
+/// Total time a run's deposit deadline may be pushed back after creation
+pub const MAX_DEADLINE_EXTENSION_SECS: i64 = 14 * 24 * 60 * 60;
+
+/// Vote-accuracy bonus per correct vote of runs from before the rate was configurable (100 = 1%)
+pub const DEFAULT_BONUS_BPS_PER_CORRECT_VOTE: u16 = 100;
+
+/// Options a vote round can offer
+pub const MAX_VOTE_OPTIONS: usize = 4;
+
+/// Longest details URI a trade proposal can carry
+pub const MAX_DETAILS_URI_LEN: usize = 200;
+
+/// Smallest bond an AI agent can register with (1,000 USDC)
+pub const MIN_AGENT_BOND: u64 = 1_000_000_000;
+
+/// Wait between an agent starting to unbond and withdrawing its bond, so faults can still be slashed
+pub const AGENT_UNBONDING_SECS: i64 = 7 * 24 * 60 * 60;
+
+/// Rebalances kept in a run's PositionLedger before the oldest are overwritten
+pub const POSITION_LEDGER_CAPACITY: usize = 64;
+
+/// Oldest risk-oracle settlement report accepted, in slots (~1 minute)
+pub const MAX_REPORT_AGE_SLOTS: u64 = 150;
+
+/// Length of the window RiskConfig counts trades over
+pub const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Most test USDC the devnet faucet mints per call (10,000 USDC)
+pub const FAUCET_MAX_AMOUNT: u64 = 10_000_000_000;
+
+/// Most approvers a settlement committee may list
+pub const MAX_SETTLEMENT_APPROVERS: usize = 10;
+
+/// Participants listed per ParticipantPage
+pub const PARTICIPANT_PAGE_SIZE: usize = 64;
+
+/// Oldest NAV mark an open-ended vault accepts deposits and redemptions against while trading
+pub const MAX_NAV_AGE_SECS: i64 = 15 * 60;
+
+/// Current layout versions; bump when fields are appended and `migrate_account` must upgrade
+pub const PLATFORM_VERSION: u8 = 17;
+pub const RUN_VERSION: u8 = 32;
+pub const PARTICIPATION_VERSION: u8 = 10;
+
+/// Optional subsystems toggled per platform through `set_feature_flag`
+pub const FEATURE_VOTING: u64 = 1 << 0;
+pub const FEATURE_BONUSES: u64 = 1 << 1;
+pub const FEATURE_ROLLOVER: u64 = 1 << 2;
+pub const FEATURE_TRADING_CPI: u64 = 1 << 3;
+pub const ALL_FEATURES: u64 = FEATURE_VOTING | FEATURE_BONUSES | FEATURE_ROLLOVER | FEATURE_TRADING_CPI;
+/// Subsystems that are live on a freshly initialized platform; the rest ship dark
+pub const DEFAULT_FEATURES: u64 = FEATURE_VOTING | FEATURE_BONUSES;
+
 #[program]
 pub mod instinct_trading {
     use super::*;
@@ -10,6 +128,7 @@ pub mod instinct_trading {
     /// Initialize the platform (one-time setup)
     pub fn initialize_platform(
         ctx: Context<InitializePlatform>,
+        platform_id: u64,              // Distinguishes independent platform instances
         platform_fee_bps: u16, // Fee in basis points (e.g., 1500 = 15%)
     ) -> Result<()> {
         require!(platform_fee_bps <= 10000, ErrorCode::InvalidFee);
@@ -22,36 +141,134 @@ pub mod instinct_trading {
         platform.bump = ctx.bumps.platform;
         platform.total_fees_collected = 0;
         platform.platform_fee_vault = ctx.accounts.platform_fee_vault.key();
+        platform.settlement_bond = 0;
+        platform.dispute_window_secs = 0;
+        platform.max_platform_tvl = 0;
+        platform.total_value_locked = 0;
+        platform.stake_mint = Pubkey::default();
+        platform.min_priority_stake = 0;
+        platform.total_staked = 0;
+        platform.compliance_authority = Pubkey::default();
+        platform.version = PLATFORM_VERSION;
+        platform.platform_id = platform_id;
+        platform.features = DEFAULT_FEATURES;
+        platform.exit_penalty_bps = 0;
+        platform.exit_penalty_to_treasury = false;
+        platform.revenue_vault = Pubkey::default();
+        platform.revenue_share_bps = 0;
+        platform.revenue_per_share = 0;
+        platform.pending_revenue = 0;
+        platform.revenue_epoch = 0;
+        platform.last_revenue_distribution = 0;
+        platform.stake_tiers = Default::default();
+        platform.ve_boost_weight = 0;
+        platform.governance_program = Pubkey::default();
+        platform.guardian = Pubkey::default();
+        platform.bridge_authority = Pubkey::default();
+        platform.total_deposited_lifetime = 0;
+        platform.total_withdrawn_lifetime = 0;
+        platform.active_runs = 0;
+        platform.settlement_escrow_secs = 0;
+        platform.attestor = Pubkey::default();
+        platform.risk_oracle = Pubkey::default();
+        platform.max_active_runs = 0;
+        platform.max_total_exposure = 0;
+        platform.total_exposure = 0;
+        platform.settlement_committee = Pubkey::default();
+        platform.authority_expires_at = 0;
+        platform.authority_rotation_secs = 0;
+        platform.pending_authority = Pubkey::default();
+        ctx.accounts.audit_log.load_init()?.bump = ctx.bumps.audit_log;
 
-        msg!("Platform initialized with {}% fee", platform_fee_bps as f64 / 100.0);
+        msg!("Platform #{} initialized with {}% fee", platform_id, platform_fee_bps as f64 / 100.0);
         Ok(())
     }
 
-    /// Create vault for a run (must be called before users can deposit)
-    pub fn create_run_vault(
-        _ctx: Context<CreateRunVault>,
-        run_id: u64,
+    /// Create or update a run tier preset (e.g. bronze/silver/gold); runs already created
+    /// from the tier keep the values they were created with (admin only)
+    pub fn set_run_tier(
+        ctx: Context<SetRunTier>,
+        tier_id: u8,
+        params: RunTierParams,
     ) -> Result<()> {
-        msg!("Vault created for run #{}", run_id);
+        params.validate()?;
+        let tier = &mut ctx.accounts.tier;
+        tier.platform = ctx.accounts.platform.key();
+        tier.tier_id = tier_id;
+        tier.name = params.name;
+        tier.min_deposit = params.min_deposit;
+        tier.max_deposit = params.max_deposit;
+        tier.min_participants = params.min_participants;
+        tier.max_participants = params.max_participants;
+        tier.max_run_tvl = params.max_run_tvl;
+        tier.min_total_deposit = params.min_total_deposit;
+        tier.max_duration = params.max_duration;
+        tier.management_fee_bps_per_year = params.management_fee_bps_per_year;
+        tier.bonus_bps_per_correct_vote = params.bonus_bps_per_correct_vote;
+        tier.max_bonus_bps = params.max_bonus_bps;
+        tier.is_active = params.is_active;
+        tier.bump = ctx.bumps.tier;
+
+        msg!("Run tier {} set: {}-{} USDC, {}-{} participants, {} bps bonus per correct vote",
+            tier_id, params.min_deposit, params.max_deposit, params.min_participants,
+            params.max_participants, params.bonus_bps_per_correct_vote);
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::SetRunTier, ctx.accounts.authority.key(), &(tier_id, params))?;
         Ok(())
     }
 
-    /// Create a new trading run
+    /// Create a new trading run, its vault and its risk limits under the next id from the
+    /// platform's run counter. With a tier preset, the tier's deposit range, participant caps,
+    /// duration, fees and bonus schedule replace those fields of `params`
     pub fn create_run(
         ctx: Context<CreateRun>,
-        run_id: u64,
-        min_deposit: u64,
-        max_deposit: u64,
-        max_participants: u16,
+        params: CreateRunParams,
+        risk: RiskParams,
     ) -> Result<()> {
         require!(!ctx.accounts.platform.is_paused, ErrorCode::PlatformPaused);
-        require!(min_deposit > 0, ErrorCode::InvalidDepositAmount);
-        require!(max_deposit >= min_deposit, ErrorCode::InvalidDepositAmount);
-        require!(max_participants > 0, ErrorCode::InvalidParticipantLimit);
+        let run_id = ctx.accounts.platform.next_run_id()?;
+
+        let now = Clock::get()?.unix_timestamp;
+        let mut params = params;
+        let (tier, max_bonus_bps) = match &ctx.accounts.tier {
+            Some(tier) => {
+                require!(tier.is_active, ErrorCode::RunTierInactive);
+                tier.apply(&mut params);
+                (tier.key(), tier.max_bonus_bps)
+            }
+            None => (Pubkey::default(), 0),
+        };
+        params.validate(now)?;
+        risk.validate()?;
+        let CreateRunParams {
+            min_deposit,
+            max_deposit,
+            max_participants,
+            max_duration,
+            max_run_tvl,
+            min_participants,
+            min_total_deposit,
+            deposit_deadline,
+            auto_start,
+            early_access_until,
+            requires_kyc,
+            is_private,
+            profit_vesting_secs,
+            management_fee_bps_per_year,
+            series_id,
+            claim_window_secs,
+            bonus_mode,
+            bonus_bps_per_correct_vote,
+            max_vote_rounds,
+            min_vote_deposit,
+            max_rebalance_bps_per_round,
+            liquidity_buffer_bps,
+            max_drawdown_bps,
+            stop_loss_nav,
+        } = params;
 
         let run = &mut ctx.accounts.run;
         run.run_id = run_id;
-        run.authority = ctx.accounts.platform.authority;
+        run.settler = Pubkey::default();
         run.status = RunStatus::Waiting;
         run.total_deposited = 0;
         run.final_balance = 0;
@@ -62,16 +279,85 @@ pub mod instinct_trading {
         run.min_deposit = min_deposit;
         run.max_deposit = max_deposit;
         run.max_participants = max_participants;
-        run.created_at = Clock::get()?.unix_timestamp;
+        run.created_at = now;
         run.started_at = 0;
         run.ended_at = 0;
         run.bump = ctx.bumps.run;
+        run.settlement_bond = 0;
+        run.shares_hash = [0u8; 32];
+        run.dispute_deadline = 0;
+        run.bond_resolved = false;
+        run.total_paid_out = 0;
+        run.exit_penalties_collected = 0;
+        run.max_duration = max_duration;
+        run.is_paused = false;
+        run.max_run_tvl = max_run_tvl;
+        run.min_participants = min_participants;
+        run.min_total_deposit = min_total_deposit;
+        run.deposit_deadline = deposit_deadline;
+        run.auto_start = auto_start;
+        run.waitlist_head = 0;
+        run.waitlist_tail = 0;
+        run.early_access_until = early_access_until;
+        run.requires_kyc = requires_kyc;
+        run.is_private = is_private;
+        run.version = RUN_VERSION;
+        run.platform = ctx.accounts.platform.key();
+        run.deadline_extension = 0;
+        run.profit_vesting_secs = profit_vesting_secs;
+        run.vesting_outstanding = 0;
+        run.management_fee_bps_per_year = management_fee_bps_per_year;
+        run.management_fee_amount = 0;
+        run.exempt_deposits = 0;
+        run.manager = Pubkey::default();
+        run.manager_fee_bps = 0;
+        run.manager_fee_amount = 0;
+        run.series_id = series_id;
+        run.mint_decimals = USDC_DECIMALS;
+        run.claim_window_secs = claim_window_secs;
+        run.tier = tier;
+        run.bonus_bps_per_correct_vote = bonus_bps_per_correct_vote;
+        run.max_bonus_bps = max_bonus_bps;
+        run.bonus_mode = bonus_mode;
+        run.vote_bonus_weight = 0;
+        run.vote_penalty_weight = 0;
+        run.max_vote_rounds = max_vote_rounds;
+        run.min_vote_deposit = min_vote_deposit;
+        run.voting_open_until = 0;
+        run.agent = Pubkey::default();
+        run.agent_at_fault = false;
+        run.strategy_count = 0;
+        run.max_rebalance_bps_per_round = max_rebalance_bps_per_round;
+        run.liquidity_buffer_bps = liquidity_buffer_bps;
+        run.escrow_until = 0;
+        run.in_escrow = false;
+        run.max_drawdown_bps = max_drawdown_bps;
+        run.breaker_tripped = false;
+        run.stop_loss_nav = stop_loss_nav;
+        run.exposure = 0;
+        run.shares_recorded = 0;
+        run.lookup_table = Pubkey::default();
+        run.unconverted_deposits = 0;
+        // Limits above are quoted in USDC units; adopt the vault mint's decimals
+        run.set_mint_decimals(ctx.accounts.mint.decimals)?;
+        run.mint = ctx.accounts.mint.key();
+
+        let risk_config = &mut ctx.accounts.risk_config;
+        risk_config.run_id = run_id;
+        risk_config.max_position_size = rescale_amount(risk.max_position_size, USDC_DECIMALS, run.mint_decimals)?;
+        risk_config.max_leverage_bps = risk.max_leverage_bps;
+        risk_config.allowed_markets = risk.allowed_markets;
+        risk_config.max_daily_trades = risk.max_daily_trades;
+        risk_config.trade_day = 0;
+        risk_config.trades_today = 0;
+        risk_config.bump = ctx.bumps.risk_config;
 
         let platform = &mut ctx.accounts.platform;
-        platform.total_runs += 1;
+        platform.total_runs = run_id;
+        platform.begin_run()?;
 
-        msg!("Run #{} created - Min: {} Max: {} Participants: {}", 
-            run_id, min_deposit, max_deposit, max_participants);
+        msg!("Run #{} created - Min: {} Max: {} Participants: {} ({} decimals)", 
+            run_id, min_deposit, max_deposit, max_participants, run.mint_decimals);
         Ok(())
     }
 
@@ -80,16 +366,48 @@ pub mod instinct_trading {
         ctx: Context<Deposit>,
         run_id: u64,
         amount: u64,
+        invite_code: Option<Vec<u8>>, // Pre-image of an invite hash, required for private runs
     ) -> Result<()> {
+        ctx.accounts.run.check_vault(ctx.accounts.run.key(), &ctx.accounts.run_vault)?;
         let run = &mut ctx.accounts.run;
+        let now = Clock::get()?.unix_timestamp;
         
         // Validations
         require!(!ctx.accounts.platform.is_paused, ErrorCode::PlatformPaused);
-        require!(run.status == RunStatus::Waiting, ErrorCode::RunNotInWaitingPhase);
-        require!(amount >= run.min_deposit, ErrorCode::DepositTooLow);
-        require!(amount <= run.max_deposit, ErrorCode::DepositTooHigh);
-        require!(run.participant_count < run.max_participants, ErrorCode::RunFull);
+        BlacklistEntry::check_not_listed(&ctx.accounts.blacklist_entry)?;
+        run.check_deposit(amount, now)?;
+        // Queued users get freed spots before newcomers
+        require!(run.waitlist_head == run.waitlist_tail, ErrorCode::WaitlistNotEmpty);
+
+        // Only stakers locked through the priority window may deposit during it
+        if now < run.early_access_until {
+            let staked = Stake::priority_amount_of(&ctx.accounts.stake, run.early_access_until);
+            require!(
+                staked > 0 && staked >= ctx.accounts.platform.min_priority_stake,
+                ErrorCode::EarlyAccessOnly
+            );
+        }
+
+        if run.requires_kyc {
+            KycAttestation::check(
+                ctx.accounts.kyc_attestation.as_deref(),
+                ctx.accounts.platform.compliance_authority,
+                now,
+            )?;
+        }
+
+        if run.is_private {
+            let invite = ctx.accounts.invite.as_mut().ok_or(ErrorCode::InviteRequired)?;
+            let code = invite_code.as_deref().ok_or(ErrorCode::InviteRequired)?;
+            invite.redeem(run_id, ctx.accounts.user.key(), code)?;
+        }
+
+        ctx.accounts.platform.record_deposit(amount)?;
 
+        write_memo(
+            &ctx.accounts.memo_program,
+            &format!("instinct:deposit run_id={} participation={}", run_id, ctx.accounts.user_participation.key()),
+        )?;
         // Transfer USDC from user to run vault
         let cpi_accounts = Transfer {
             from: ctx.accounts.user_token_account.to_account_info(),
@@ -101,439 +419,11140 @@ pub mod instinct_trading {
         token::transfer(cpi_ctx, amount)?;
 
         // Update user participation record
-        let participation = &mut ctx.accounts.user_participation;
-        participation.user = ctx.accounts.user.key();
-        participation.run_id = run_id;
-        participation.deposit_amount = amount;
-        participation.final_share = 0;
-        participation.withdrawn = false;
-        participation.correct_votes = 0;
-        participation.total_votes = 0;
-        participation.bump = ctx.bumps.user_participation;
+        ctx.accounts.user_participation.record_deposit(
+            ctx.accounts.user.key(),
+            run_id,
+            amount,
+            ctx.bumps.user_participation,
+        );
+        if let Some(treasury) = ctx.accounts.rent_treasury.as_mut() {
+            RentTreasury::sponsor(
+                treasury,
+                &ctx.accounts.user.to_account_info(),
+                &mut ctx.accounts.user_participation,
+            )?;
+        }
 
         // Update run totals
-        run.total_deposited += amount;
-        run.participant_count += 1;
+        ctx.accounts.platform.admit_participant(
+            run,
+            &ctx.accounts.participant_page,
+            ctx.accounts.user_participation.user,
+            ctx.bumps.participant_page,
+            amount,
+            now,
+        )?;
 
         msg!("User {} deposited {} USDC to run #{}", 
-            ctx.accounts.user.key(), amount, run_id);
+            ctx.accounts.user.key(), run.ui(amount), run_id);
         Ok(())
     }
 
-    /// Start a run (called by backend authority)
-    pub fn start_run(
-        ctx: Context<ManageRun>,
+    /// Deposit for a user who approved the signing relayer as delegate on their USDC account,
+    /// without the user signing this transaction
+    pub fn deposit_with_delegate(
+        ctx: Context<DepositWithDelegate>,
         run_id: u64,
+        amount: u64,
     ) -> Result<()> {
         let run = &mut ctx.accounts.run;
-        
-        require!(run.status == RunStatus::Waiting, ErrorCode::InvalidRunStatus);
-        require!(run.participant_count > 0, ErrorCode::NoParticipants);
-
-        run.status = RunStatus::Active;
-        run.started_at = Clock::get()?.unix_timestamp;
+        let now = Clock::get()?.unix_timestamp;
 
-        msg!("Run #{} started with {} participants and {} USDC", 
-            run_id, run.participant_count, run.total_deposited);
-        Ok(())
-    }
+        run.check_delegated_deposit(
+            &ctx.accounts.platform,
+            &ctx.accounts.blacklist_entry,
+            &ctx.accounts.stake,
+            ctx.accounts.kyc_attestation.as_deref(),
+            amount,
+            now,
+        )?;
 
-    /// Settle a run with final P/L (called by backend authority after trading ends)
-    pub fn settle_run(
-        ctx: Context<SettleRun>,
-        run_id: u64,
-        final_balance: u64,
-        participant_shares: Vec<ParticipantShare>,
-    ) -> Result<()> {
-        // Verify current vault balance matches reported final_balance
-        let vault_balance = ctx.accounts.run_vault.amount;
-        require!(vault_balance == final_balance, ErrorCode::VaultBalanceMismatch);
-        
-        // Read values we need from run before any mutable borrows
-        let run_status = ctx.accounts.run.status.clone();
-        let participant_count = ctx.accounts.run.participant_count;
-        let total_deposited = ctx.accounts.run.total_deposited;
-        let run_bump = ctx.accounts.run.bump;
-        let run_id_bytes = run_id.to_le_bytes();
-        
-        require!(run_status == RunStatus::Active, ErrorCode::InvalidRunStatus);
-        require!(participant_shares.len() == participant_count as usize, ErrorCode::InvalidSharesCount);
+        ctx.accounts.platform.record_deposit(amount)?;
 
-        // Calculate platform fee ONLY on profit (not on principal)
-        let profit = if final_balance > total_deposited {
-            final_balance
-                .checked_sub(total_deposited)
-                .ok_or(ErrorCode::ArithmeticOverflow)?
-        } else {
-            0
+        write_memo(
+            &ctx.accounts.memo_program,
+            &format!("instinct:deposit run_id={} participation={}", run_id, ctx.accounts.user_participation.key()),
+        )?;
+        // The token program enforces the delegate and the approved amount
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.user_token_account.to_account_info(),
+            to: ctx.accounts.run_vault.to_account_info(),
+            authority: ctx.accounts.relayer.to_account_info(),
         };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
 
-        let platform_fee = (profit as u128)
-            .checked_mul(ctx.accounts.platform.platform_fee_bps as u128)
-            .ok_or(ErrorCode::ArithmeticOverflow)?
-            .checked_div(10000)
-            .ok_or(ErrorCode::ArithmeticOverflow)?
-            as u64;
-
-        // Transfer platform fee to platform vault (if there is profit)
-        if platform_fee > 0 {
-            let run_seeds = &[
-                b"run".as_ref(),
-                run_id_bytes.as_ref(),
-                &[run_bump],
-            ];
-            let signer = &[&run_seeds[..]];
-
-            let cpi_accounts = Transfer {
-                from: ctx.accounts.run_vault.to_account_info(),
-                to: ctx.accounts.platform_fee_vault.to_account_info(),
-                authority: ctx.accounts.run.to_account_info(),
-            };
-            let cpi_program = ctx.accounts.token_program.to_account_info();
-            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-            token::transfer(cpi_ctx, platform_fee)?;
-        }
-
-        // Now update run state (mutable borrow)
-        let run = &mut ctx.accounts.run;
-        run.status = RunStatus::Settled;
-        run.final_balance = final_balance
-            .checked_sub(platform_fee)
-            .ok_or(ErrorCode::ArithmeticOverflow)?;
-        run.platform_fee_amount = platform_fee;
-        run.ended_at = Clock::get()?.unix_timestamp;
-
-        // Update platform totals
-        let platform = &mut ctx.accounts.platform;
-        platform.total_fees_collected = platform.total_fees_collected
-            .checked_add(platform_fee)
-            .ok_or(ErrorCode::ArithmeticOverflow)?;
-
-        msg!("Run #{} settled - Initial: {} Final: {} Fee: {} Available: {}", 
-            run_id, 
-            total_deposited, 
-            final_balance,
-            platform_fee,
-            run.final_balance
+        ctx.accounts.user_participation.record_deposit(
+            ctx.accounts.user.key(),
+            run_id,
+            amount,
+            ctx.bumps.user_participation,
         );
-        
+        ctx.accounts.platform.admit_participant(
+            run,
+            &ctx.accounts.participant_page,
+            ctx.accounts.user_participation.user,
+            ctx.bumps.participant_page,
+            amount,
+            now,
+        )?;
+
+        msg!("Relayer {} deposited {} USDC to run #{} for {}",
+            ctx.accounts.relayer.key(), run.ui(amount), run_id, ctx.accounts.user.key());
         Ok(())
     }
 
-    /// Withdraw user's share after run settlement
-    pub fn withdraw(
-        ctx: Context<Withdraw>,
+    /// Deposit from a wallet that signed a `DepositIntent` off-chain; the relayer pays all fees.
+    /// The signature is checked by an ed25519 program instruction placed right before this one,
+    /// and funds move under the user's approval to the platform's intent delegate PDA
+    pub fn deposit_by_intent(
+        ctx: Context<DepositByIntent>,
         run_id: u64,
+        intent: DepositIntent,
     ) -> Result<()> {
-        // Read values we need before any mutable borrows
-        let run_status = ctx.accounts.run.status.clone();
-        let withdrawn_count = ctx.accounts.run.withdrawn_count;
-        let participant_count = ctx.accounts.run.participant_count;
-        let final_balance = ctx.accounts.run.final_balance;
-        let total_deposited = ctx.accounts.run.total_deposited;
-        let run_bump = ctx.accounts.run.bump;
-        let run_id_from_account = ctx.accounts.run.run_id;
-        
-        require!(run_status == RunStatus::Settled, ErrorCode::RunNotSettled);
-        require!(!ctx.accounts.user_participation.withdrawn, ErrorCode::AlreadyWithdrawn);
-
-        let user_share: u64;
-        let deposit_amount = ctx.accounts.user_participation.deposit_amount;
-        let correct_votes = ctx.accounts.user_participation.correct_votes;
-
-        // Check if this is the last withdrawal - fixes rounding dust issue
-        let is_last_user = withdrawn_count + 1 == participant_count;
-
-        if is_last_user {
-            // Last user gets all remaining balance to eliminate rounding dust
-            user_share = ctx.accounts.run_vault.amount;
-            
-            msg!(
-                "Last withdrawal - user {} gets remaining vault balance: {}",
-                ctx.accounts.user.key(),
-                user_share
-            );
-        } else {
-            // Calculate proportional share for non-last users
-            let base_share_numerator = (deposit_amount as u128)
-                .checked_mul(final_balance as u128)
-                .ok_or(ErrorCode::ArithmeticOverflow)?;
-            
-            let base_share = base_share_numerator
-                .checked_div(total_deposited as u128)
-                .ok_or(ErrorCode::ArithmeticOverflow)? as u64;
+        let now = Clock::get()?.unix_timestamp;
+        let platform_key = ctx.accounts.platform.key();
+        let amount = intent.amount;
 
-            // Calculate bonus ONLY if there was profit (FIX #3)
-            if final_balance > total_deposited {
-                // Calculate this user's share of the profit
-                let profit_ratio = final_balance
-                    .checked_sub(total_deposited)
-                    .ok_or(ErrorCode::ArithmeticOverflow)?;
-                
-                let user_profit_share = (deposit_amount as u128)
-                    .checked_mul(profit_ratio as u128)
-                    .ok_or(ErrorCode::ArithmeticOverflow)?
-                    .checked_div(total_deposited as u128)
-                    .ok_or(ErrorCode::ArithmeticOverflow)? as u64;
-
-                // Apply bonus to profit share only (1% per correct vote)
-                let correct_vote_bonus_bps = (correct_votes as u64)
-                    .checked_mul(100)
-                    .ok_or(ErrorCode::ArithmeticOverflow)?; // 1% per vote in bps
-                
-                let bonus = (user_profit_share as u128)
-                    .checked_mul(correct_vote_bonus_bps as u128)
-                    .ok_or(ErrorCode::ArithmeticOverflow)?
-                    .checked_div(10000)
-                    .ok_or(ErrorCode::ArithmeticOverflow)? as u64;
+        require_keys_eq!(intent.platform, platform_key, ErrorCode::InvalidIntentSignature);
+        require_keys_eq!(intent.user, ctx.accounts.user.key(), ErrorCode::InvalidIntentSignature);
+        require!(intent.run_id == run_id, ErrorCode::InvalidIntentSignature);
+        require!(now <= intent.expiry, ErrorCode::IntentExpired);
+        require!(intent.nonce == ctx.accounts.intent_nonce.next_nonce, ErrorCode::InvalidIntentNonce);
+        intent.verify_signature(&ctx.accounts.instructions)?;
 
-                user_share = base_share
-                    .checked_add(bonus)
-                    .ok_or(ErrorCode::ArithmeticOverflow)?;
-            } else {
-                // No bonus on losses
-                user_share = base_share;
-            }
+        let run = &mut ctx.accounts.run;
+        run.check_delegated_deposit(
+            &ctx.accounts.platform,
+            &ctx.accounts.blacklist_entry,
+            &ctx.accounts.stake,
+            ctx.accounts.kyc_attestation.as_deref(),
+            amount,
+            now,
+        )?;
 
-            // Ensure we don't exceed vault balance
-            require!(
-                user_share <= ctx.accounts.run_vault.amount,
-                ErrorCode::InsufficientVaultFunds
-            );
-        }
+        ctx.accounts.platform.record_deposit(amount)?;
 
-        // Transfer USDC from vault to user
-        let run_id_bytes = run_id_from_account.to_le_bytes();
-        let run_seeds = &[
-            b"run".as_ref(),
-            run_id_bytes.as_ref(),
-            &[run_bump],
+        let delegate_seeds = &[
+            b"intent_delegate".as_ref(),
+            platform_key.as_ref(),
+            &[ctx.bumps.intent_delegate],
         ];
-        let signer = &[&run_seeds[..]];
+        let signer = &[&delegate_seeds[..]];
 
+        write_memo(
+            &ctx.accounts.memo_program,
+            &format!("instinct:deposit run_id={} participation={}", run_id, ctx.accounts.user_participation.key()),
+        )?;
         let cpi_accounts = Transfer {
-            from: ctx.accounts.run_vault.to_account_info(),
-            to: ctx.accounts.user_token_account.to_account_info(),
-            authority: ctx.accounts.run.to_account_info(),
+            from: ctx.accounts.user_token_account.to_account_info(),
+            to: ctx.accounts.run_vault.to_account_info(),
+            authority: ctx.accounts.intent_delegate.to_account_info(),
         };
         let cpi_program = ctx.accounts.token_program.to_account_info();
         let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-        token::transfer(cpi_ctx, user_share)?;
+        token::transfer(cpi_ctx, amount)?;
 
-        // Update participation record
-        let participation = &mut ctx.accounts.user_participation;
-        participation.final_share = user_share;
-        participation.withdrawn = true;
+        ctx.accounts.user_participation.record_deposit(
+            intent.user,
+            run_id,
+            amount,
+            ctx.bumps.user_participation,
+        );
+        ctx.accounts.platform.admit_participant(
+            run,
+            &ctx.accounts.participant_page,
+            ctx.accounts.user_participation.user,
+            ctx.bumps.participant_page,
+            amount,
+            now,
+        )?;
 
-        // Update run withdrawal tracking (FIX #2)
-        let run = &mut ctx.accounts.run;
-        run.total_withdrawn = run.total_withdrawn
-            .checked_add(user_share)
-            .ok_or(ErrorCode::ArithmeticOverflow)?;
-        run.withdrawn_count = run.withdrawn_count
+        let intent_nonce = &mut ctx.accounts.intent_nonce;
+        intent_nonce.user = intent.user;
+        intent_nonce.next_nonce = intent_nonce.next_nonce
             .checked_add(1)
             .ok_or(ErrorCode::ArithmeticOverflow)?;
+        intent_nonce.bump = ctx.bumps.intent_nonce;
 
-        msg!(
-            "User {} withdrew {} USDC from run #{} ({}/{})",
-            ctx.accounts.user.key(),
-            user_share,
-            run_id,
-            run.withdrawn_count,
-            run.participant_count
-        );
+        msg!("Intent #{} from {} deposited {} USDC to run #{}", intent.nonce, intent.user, run.ui(amount), run_id);
         Ok(())
     }
 
-    /// Update user's vote statistics (called by backend after each voting round)
-    pub fn update_vote_stats(
-        ctx: Context<UpdateVoteStats>,
-        _run_id: u64,
-        _user_pubkey: Pubkey,
-        correct_votes: u8,
-        total_votes: u8,
+    /// Create the audit log for a platform initialized before audit logging existed (admin only)
+    pub fn initialize_audit_log(ctx: Context<InitializeAuditLog>) -> Result<()> {
+        ctx.accounts.audit_log.load_init()?.bump = ctx.bumps.audit_log;
+        msg!("Audit log {} created", ctx.accounts.audit_log.key());
+        Ok(())
+    }
+
+    /// Create the lamport treasury that reimburses participation rent (admin only)
+    pub fn initialize_rent_treasury(ctx: Context<InitializeRentTreasury>) -> Result<()> {
+        let treasury = &mut ctx.accounts.rent_treasury;
+        treasury.bump = ctx.bumps.rent_treasury;
+        treasury.total_sponsored = 0;
+        treasury.total_recaptured = 0;
+        msg!("Rent treasury {} created", treasury.key());
+        Ok(())
+    }
+
+    /// Add lamports to the rent treasury (anyone)
+    pub fn fund_rent_treasury(ctx: Context<FundRentTreasury>, lamports: u64) -> Result<()> {
+        let cpi_accounts = system_program::Transfer {
+            from: ctx.accounts.funder.to_account_info(),
+            to: ctx.accounts.rent_treasury.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+        system_program::transfer(cpi_ctx, lamports)?;
+        msg!("Rent treasury funded with {} lamports by {}", lamports, ctx.accounts.funder.key());
+        Ok(())
+    }
+
+    /// Take spare lamports back out of the rent treasury (admin only)
+    pub fn withdraw_rent_treasury(ctx: Context<WithdrawRentTreasury>, lamports: u64) -> Result<()> {
+        let treasury_info = ctx.accounts.rent_treasury.to_account_info();
+        let spare = treasury_info
+            .lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(RentTreasury::LEN));
+        require!(lamports <= spare, ErrorCode::RentTreasuryEmpty);
+        treasury_info.sub_lamports(lamports)?;
+        ctx.accounts.authority.add_lamports(lamports)?;
+        msg!("{} lamports withdrawn from the rent treasury", lamports);
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::WithdrawRentTreasury, ctx.accounts.authority.key(), &lamports)?;
+        Ok(())
+    }
+
+    /// Create the lamport vault that pays a bounty to callers of permissionless cranks (admin only)
+    pub fn initialize_keeper_incentive(
+        ctx: Context<InitializeKeeperIncentive>,
+        bounty_lamports: u64,
     ) -> Result<()> {
-        let participation = &mut ctx.accounts.user_participation;
-        
-        require!(ctx.accounts.run.status == RunStatus::Active, ErrorCode::InvalidRunStatus);
-        
-        participation.correct_votes = correct_votes;
-        participation.total_votes = total_votes;
+        let incentive = &mut ctx.accounts.keeper_incentive;
+        incentive.bump = ctx.bumps.keeper_incentive;
+        incentive.bounty_lamports = bounty_lamports;
+        incentive.total_paid = 0;
+        msg!("Keeper incentive {} created paying {} lamports per crank", incentive.key(), bounty_lamports);
+        Ok(())
+    }
 
+    /// Set the lamports paid per crank; 0 turns bounties off (admin only)
+    pub fn set_keeper_bounty(ctx: Context<ManageKeeperIncentive>, bounty_lamports: u64) -> Result<()> {
+        ctx.accounts.keeper_incentive.bounty_lamports = bounty_lamports;
+        msg!("Keeper bounty set to {} lamports", bounty_lamports);
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::SetKeeperBounty, ctx.accounts.authority.key(), &bounty_lamports)?;
         Ok(())
     }
 
-    /// Emergency pause (admin only)
-    pub fn pause_platform(ctx: Context<AdminAction>) -> Result<()> {
-        ctx.accounts.platform.is_paused = true;
-        msg!("Platform paused by authority");
+    /// Add lamports to the keeper incentive vault (anyone)
+    pub fn fund_keeper_incentive(ctx: Context<FundKeeperIncentive>, lamports: u64) -> Result<()> {
+        let cpi_accounts = system_program::Transfer {
+            from: ctx.accounts.funder.to_account_info(),
+            to: ctx.accounts.keeper_incentive.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+        system_program::transfer(cpi_ctx, lamports)?;
+        msg!("Keeper incentive funded with {} lamports by {}", lamports, ctx.accounts.funder.key());
         Ok(())
     }
 
-    /// Unpause platform (admin only)
-    pub fn unpause_platform(ctx: Context<AdminAction>) -> Result<()> {
-        ctx.accounts.platform.is_paused = false;
-        msg!("Platform unpaused by authority");
+    /// Take spare lamports back out of the keeper incentive vault (admin only)
+    pub fn withdraw_keeper_incentive(ctx: Context<ManageKeeperIncentive>, lamports: u64) -> Result<()> {
+        let incentive_info = ctx.accounts.keeper_incentive.to_account_info();
+        let spare = incentive_info
+            .lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(KeeperIncentive::LEN));
+        require!(lamports <= spare, ErrorCode::KeeperIncentiveEmpty);
+        incentive_info.sub_lamports(lamports)?;
+        ctx.accounts.authority.add_lamports(lamports)?;
+        msg!("{} lamports withdrawn from the keeper incentive", lamports);
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::WithdrawKeeperIncentive, ctx.accounts.authority.key(), &lamports)?;
         Ok(())
     }
 
-    /// Withdraw collected platform fees (admin only)
-    pub fn withdraw_platform_fees(
-        ctx: Context<WithdrawPlatformFees>,
-        amount: u64,
+    /// Create the inbox that bridged USDC is minted into and set the authority trusted to relay
+    /// attested bridge messages, e.g. a CCTP/Wormhole receiver program's PDA (admin only)
+    pub fn initialize_bridge(
+        ctx: Context<InitializeBridge>,
+        bridge_authority: Pubkey,
     ) -> Result<()> {
-        require!(
-            amount <= ctx.accounts.platform_fee_vault.amount,
-            ErrorCode::InsufficientVaultFunds
-        );
+        ctx.accounts.platform.bridge_authority = bridge_authority;
+        msg!("Bridge inbox {} created, relayed by {}", ctx.accounts.bridge_inbox.key(), bridge_authority);
+        Ok(())
+    }
+
+    /// Change or disable (default key) the bridge authority (admin only)
+    pub fn set_bridge_authority(
+        ctx: Context<AdminAction>,
+        bridge_authority: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.platform.bridge_authority = bridge_authority;
+        msg!("Bridge authority set to {}", bridge_authority);
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::SetBridgeAuthority, ctx.accounts.authority.key(), &bridge_authority)?;
+        Ok(())
+    }
+
+    /// Link an EVM address to the signing Solana wallet, proven by a secp256k1 program
+    /// instruction right before this one over EVM_LINK_DOMAIN || platform || wallet
+    pub fn link_evm_address(
+        ctx: Context<LinkEvmAddress>,
+        evm_address: [u8; 20],
+    ) -> Result<()> {
+        let mut message = EVM_LINK_DOMAIN.to_vec();
+        message.extend_from_slice(ctx.accounts.platform.key().as_ref());
+        message.extend_from_slice(ctx.accounts.wallet.key().as_ref());
+        EvmLink::verify_signature(&ctx.accounts.instructions, &evm_address, &message)?;
+
+        let link = &mut ctx.accounts.evm_link;
+        link.evm_address = evm_address;
+        link.wallet = ctx.accounts.wallet.key();
+        link.bump = ctx.bumps.evm_link;
+
+        msg!("EVM address linked to {}", link.wallet);
+        Ok(())
+    }
+
+    /// Credit a deposit bridged from an EVM chain: USDC already delivered to the bridge inbox is moved
+    /// into the run vault for the Solana wallet linked to the EVM sender. Each (domain, nonce) is consumed once
+    pub fn receive_bridged_deposit(
+        ctx: Context<ReceiveBridgedDeposit>,
+        run_id: u64,
+        message: BridgedDeposit,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let amount = message.amount;
+        let wallet = ctx.accounts.evm_link.wallet;
+
+        require!(message.run_id == run_id, ErrorCode::InvalidBridgeMessage);
+        require!(amount <= ctx.accounts.bridge_inbox.amount, ErrorCode::InsufficientVaultFunds);
+
+        let run = &mut ctx.accounts.run;
+        run.check_delegated_deposit(
+            &ctx.accounts.platform,
+            &ctx.accounts.blacklist_entry,
+            &ctx.accounts.stake,
+            ctx.accounts.kyc_attestation.as_deref(),
+            amount,
+            now,
+        )?;
+
+        ctx.accounts.platform.record_deposit(amount)?;
 
         let platform_bump = ctx.accounts.platform.bump;
+        let platform_id_bytes = ctx.accounts.platform.platform_id.to_le_bytes();
         let platform_seeds = &[
             b"platform".as_ref(),
+            platform_id_bytes.as_ref(),
             &[platform_bump],
         ];
         let signer = &[&platform_seeds[..]];
 
+        write_memo(
+            &ctx.accounts.memo_program,
+            &format!("instinct:deposit run_id={} participation={}", run_id, ctx.accounts.user_participation.key()),
+        )?;
         let cpi_accounts = Transfer {
-            from: ctx.accounts.platform_fee_vault.to_account_info(),
-            to: ctx.accounts.destination_token_account.to_account_info(),
+            from: ctx.accounts.bridge_inbox.to_account_info(),
+            to: ctx.accounts.run_vault.to_account_info(),
             authority: ctx.accounts.platform.to_account_info(),
         };
         let cpi_program = ctx.accounts.token_program.to_account_info();
         let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
         token::transfer(cpi_ctx, amount)?;
 
-        msg!("Platform fees withdrawn: {} USDC", amount);
+        ctx.accounts.user_participation.record_deposit(
+            wallet,
+            run_id,
+            amount,
+            ctx.bumps.user_participation,
+        );
+        ctx.accounts.platform.admit_participant(
+            run,
+            &ctx.accounts.participant_page,
+            ctx.accounts.user_participation.user,
+            ctx.bumps.participant_page,
+            amount,
+            now,
+        )?;
+
+        let processed = &mut ctx.accounts.processed_message;
+        processed.source_domain = message.source_domain;
+        processed.nonce = message.nonce;
+        processed.processed_at = now;
+        processed.bump = ctx.bumps.processed_message;
+
+        msg!("Bridged deposit (domain {}, nonce {}) of {} USDC credited to {} in run #{}",
+            message.source_domain, message.nonce, run.ui(amount), wallet, run_id);
         Ok(())
     }
 
-    /// Emergency withdraw (admin only - for stuck funds)
-    pub fn emergency_withdraw(
-        ctx: Context<EmergencyWithdraw>,
+    /// Accept another stablecoin in a run through its own sub-vault, priced by a Pyth feed (platform authority or manager)
+    pub fn add_accepted_mint(
+        ctx: Context<AddAcceptedMint>,
         run_id: u64,
-        amount: u64,
+        price_feed_id: [u8; 32],
     ) -> Result<()> {
-        require!(ctx.accounts.platform.is_paused, ErrorCode::PlatformNotPaused);
+        require!(ctx.accounts.run.status == RunStatus::Waiting, ErrorCode::RunNotInWaitingPhase);
 
-        let run = &ctx.accounts.run;
-        let run_id_bytes = run.run_id.to_le_bytes();
-        let run_seeds = &[
-            b"run",
-            run_id_bytes.as_ref(),
-            &[run.bump],
-        ];
-        let signer = &[&run_seeds[..]];
-
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.run_vault.to_account_info(),
-            to: ctx.accounts.destination_token_account.to_account_info(),
-            authority: ctx.accounts.run.to_account_info(),
-        };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-        token::transfer(cpi_ctx, amount)?;
+        let accepted = &mut ctx.accounts.accepted_mint;
+        accepted.run_id = run_id;
+        accepted.mint = ctx.accounts.mint.key();
+        accepted.price_feed_id = price_feed_id;
+        accepted.total_deposited = 0;
+        accepted.bump = ctx.bumps.accepted_mint;
+        accepted.decimals = ctx.accounts.mint.decimals;
+        accepted.unconverted_notional = 0;
 
-        msg!("Emergency withdraw: {} USDC from run #{}", amount, run_id);
+        msg!("Run #{} accepts {} deposits", run_id, accepted.mint);
         Ok(())
     }
+
+    /// Deposit an accepted non-USDC stablecoin, credited at its USD notional from a fresh Pyth price
+    pub fn deposit_stablecoin(
+        ctx: Context<DepositStablecoin>,
+        run_id: u64,
+        amount: u64,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let price = PythPrice::load(&ctx.accounts.price_update, &ctx.accounts.accepted_mint.price_feed_id)?;
+        let run = &mut ctx.accounts.run;
+        let notional = rescale_amount(
+            price.usd_notional(amount, now)?,
+            ctx.accounts.accepted_mint.decimals,
+            run.mint_decimals,
+        )?;
+
+        require!(!ctx.accounts.platform.is_paused, ErrorCode::PlatformPaused);
+        BlacklistEntry::check_not_listed(&ctx.accounts.blacklist_entry)?;
+        run.check_deposit(notional, now)?;
+        require!(run.waitlist_head == run.waitlist_tail, ErrorCode::WaitlistNotEmpty);
+        require!(!run.is_private, ErrorCode::InviteRequired);
+        if now < run.early_access_until {
+            let staked = Stake::priority_amount_of(&ctx.accounts.stake, run.early_access_until);
+            require!(
+                staked > 0 && staked >= ctx.accounts.platform.min_priority_stake,
+                ErrorCode::EarlyAccessOnly
+            );
+        }
+        if run.requires_kyc {
+            KycAttestation::check(
+                ctx.accounts.kyc_attestation.as_deref(),
+                ctx.accounts.platform.compliance_authority,
+                now,
+            )?;
+        }
+
+        ctx.accounts.platform.record_deposit(notional)?;
+
+        write_memo(
+            &ctx.accounts.memo_program,
+            &format!("instinct:deposit run_id={} participation={}", run_id, ctx.accounts.user_participation.key()),
+        )?;
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.user_token_account.to_account_info(),
+            to: ctx.accounts.sub_vault.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+
+        let participation = &mut ctx.accounts.user_participation;
+        participation.record_deposit(ctx.accounts.user.key(), run_id, notional, ctx.bumps.user_participation);
+        participation.deposit_mint = ctx.accounts.accepted_mint.mint;
+
+        let accepted = &mut ctx.accounts.accepted_mint;
+        accepted.total_deposited = accepted.total_deposited
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        accepted.unconverted_notional = accepted.unconverted_notional
+            .checked_add(notional)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        run.unconverted_deposits = run.unconverted_deposits
+            .checked_add(notional)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        // Share math only ever sees the USD notional
+        ctx.accounts.platform.admit_participant(
+            run,
+            &ctx.accounts.participant_page,
+            ctx.accounts.user_participation.user,
+            ctx.bumps.participant_page,
+            notional,
+            now,
+        )?;
+
+        msg!("User {} deposited {} of {} (${} notional) to run #{}",
+            ctx.accounts.user.key(), UiAmount { amount, decimals: accepted.decimals }, accepted.mint, run.ui(notional), run_id);
+        Ok(())
+    }
+
+    /// Convert a sub-vault into USDC at its Pyth price: the operator pays the USD notional of the
+    /// whole sub-vault into the run vault and takes the stablecoins in exchange. Runs convert
+    /// every sub-vault before settlement (platform authority or manager)
+    pub fn convert_sub_vault(
+        ctx: Context<ConvertSubVault>,
+        run_id: u64,
+    ) -> Result<()> {
+        let run = &ctx.accounts.run;
+        // Refund mode snapshots the vault, so conversions stop once trading ends
+        require!(
+            run.status == RunStatus::Active || run.status == RunStatus::SettlementPending,
+            ErrorCode::InvalidRunStatus
+        );
+        let amount = ctx.accounts.sub_vault.amount;
+        let now = Clock::get()?.unix_timestamp;
+        let price = PythPrice::load(&ctx.accounts.price_update, &ctx.accounts.accepted_mint.price_feed_id)?;
+        let usdc_amount = rescale_amount(
+            price.usd_notional(amount, now)?,
+            ctx.accounts.accepted_mint.decimals,
+            run.mint_decimals,
+        )?;
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.authority_token_account.to_account_info(),
+            to: ctx.accounts.run_vault.to_account_info(),
+            authority: ctx.accounts.authority.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::transfer(cpi_ctx, usdc_amount)?;
+
+        let platform_key = run.platform;
+        let run_id_bytes = run.run_id.to_le_bytes();
+        let run_seeds = &[
+            b"run".as_ref(),
+            platform_key.as_ref(),
+            run_id_bytes.as_ref(),
+            &[run.bump],
+        ];
+        let signer = &[&run_seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.sub_vault.to_account_info(),
+            to: ctx.accounts.destination_token_account.to_account_info(),
+            authority: ctx.accounts.run.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, amount)?;
+
+        let accepted = &mut ctx.accounts.accepted_mint;
+        let run = &mut ctx.accounts.run;
+        run.unconverted_deposits = run.unconverted_deposits
+            .checked_sub(accepted.unconverted_notional)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        accepted.unconverted_notional = 0;
+
+        msg!("Converted {} of {} from run #{} into {} USDC",
+            UiAmount { amount, decimals: accepted.decimals }, accepted.mint, run_id, run.ui(usdc_amount));
+        Ok(())
+    }
+
+    /// Refund a stablecoin deposit in kind from its sub-vault, for runs that entered refund mode
+    /// before the sub-vault was converted
+    pub fn claim_sub_vault_refund(
+        ctx: Context<ClaimSubVaultRefund>,
+        run_id: u64,
+    ) -> Result<()> {
+        let run = &ctx.accounts.run;
+        let deposit_amount = ctx.accounts.user_participation.deposit_amount;
+
+        require!(run.status.is_refundable(), ErrorCode::RefundsNotEnabled);
+        require!(!ctx.accounts.user_participation.withdrawn, ErrorCode::AlreadyWithdrawn);
+        BlacklistEntry::check_not_listed(&ctx.accounts.blacklist_entry)?;
+        ctx.accounts.user_participation.check_not_frozen()?;
+        let unconverted_notional = ctx.accounts.accepted_mint.unconverted_notional;
+        require!(unconverted_notional > 0, ErrorCode::SubVaultConverted);
+
+        // Pro-rata of what is left, so the last depositor of the mint takes the remainder
+        let refund = (deposit_amount as u128)
+            .checked_mul(ctx.accounts.sub_vault.amount as u128)
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+            .checked_div(unconverted_notional as u128)
+            .ok_or(ErrorCode::ArithmeticOverflow)? as u64;
+
+        let platform_key = run.platform;
+        let run_id_bytes = run.run_id.to_le_bytes();
+        let run_seeds = &[
+            b"run".as_ref(),
+            platform_key.as_ref(),
+            run_id_bytes.as_ref(),
+            &[run.bump],
+        ];
+        let signer = &[&run_seeds[..]];
+
+        write_memo(
+            &ctx.accounts.memo_program,
+            &format!("instinct:refund run_id={} participation={}", run_id, ctx.accounts.user_participation.key()),
+        )?;
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.sub_vault.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: ctx.accounts.run.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, refund)?;
+
+        // Paid from the sub-vault, so the run vault's payout tracking is left alone
+        let participation = &mut ctx.accounts.user_participation;
+        participation.final_share = refund;
+        participation.withdrawn = true;
+        let accepted = &mut ctx.accounts.accepted_mint;
+        accepted.unconverted_notional = unconverted_notional
+            .checked_sub(deposit_amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        let run = &mut ctx.accounts.run;
+        run.withdrawn_count = run.withdrawn_count
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        let platform = &mut ctx.accounts.platform;
+        platform.total_value_locked = platform.total_value_locked.saturating_sub(deposit_amount);
+        platform.record_withdrawal(rescale_amount(refund, accepted.decimals, run.mint_decimals)?)?;
+
+        msg!("User {} refunded {} of {} from run #{} ({}/{})",
+            ctx.accounts.user.key(), UiAmount { amount: refund, decimals: accepted.decimals }, accepted.mint,
+            run_id, run.withdrawn_count, run.participant_count);
+        Ok(())
+    }
+
+    /// Turn a run without deposits into a share-token run: deposits mint an SPL receipt token
+    /// at vault NAV and holders redeem by burning it, instead of using participation accounts.
+    /// An `open_ended` run keeps taking deposits and redemptions at the marked NAV while it trades
+    /// (platform authority or manager)
+    pub fn init_share_mint(
+        ctx: Context<InitShareMint>,
+        run_id: u64,
+        open_ended: bool,
+    ) -> Result<()> {
+        let run = &mut ctx.accounts.run;
+        require!(run.status == RunStatus::Waiting, ErrorCode::RunNotInWaitingPhase);
+        require!(run.participant_count == 0 && run.total_deposited == 0, ErrorCode::RunHasDeposits);
+        require!(!run.is_private, ErrorCode::InviteRequired);
+        run.share_mint = ctx.accounts.share_mint.key();
+        run.open_ended = open_ended;
+        msg!("Run #{} issues share token {} (open-ended: {})", run_id, run.share_mint, open_ended);
+        Ok(())
+    }
+
+    /// Deposit into a share-token run and receive shares at the vault's NAV
+    pub fn deposit_shares(
+        ctx: Context<DepositShares>,
+        run_id: u64,
+        amount: u64,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let run = &ctx.accounts.run;
+        require!(run.is_share_run(), ErrorCode::NotShareTokenRun);
+        run.check_delegated_deposit(
+            &ctx.accounts.platform,
+            &ctx.accounts.blacklist_entry,
+            &ctx.accounts.stake,
+            ctx.accounts.kyc_attestation.as_deref(),
+            amount,
+            now,
+        )?;
+        let total_assets = run.nav_assets(ctx.accounts.run_vault.amount, now)?;
+        let shares = Run::convert_to_shares(amount, total_assets, ctx.accounts.share_mint.supply)?;
+        require!(shares > 0, ErrorCode::InvalidDepositAmount);
+
+        ctx.accounts.platform.record_deposit(amount)?;
+
+        write_memo(
+            &ctx.accounts.memo_program,
+            &format!("instinct:deposit run_id={} share_account={}", run_id, ctx.accounts.user_share_account.key()),
+        )?;
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.user_token_account.to_account_info(),
+            to: ctx.accounts.run_vault.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+
+        let platform_key = run.platform;
+        let run_id_bytes = run.run_id.to_le_bytes();
+        let run_seeds = &[
+            b"run".as_ref(),
+            platform_key.as_ref(),
+            run_id_bytes.as_ref(),
+            &[run.bump],
+        ];
+        let signer = &[&run_seeds[..]];
+        let cpi_accounts = token::MintTo {
+            mint: ctx.accounts.share_mint.to_account_info(),
+            to: ctx.accounts.user_share_account.to_account_info(),
+            authority: ctx.accounts.run.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::mint_to(cpi_ctx, shares)?;
+
+        let run = &mut ctx.accounts.run;
+        run.total_deposited = run.total_deposited
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        if run.uses_marked_nav() {
+            run.marked_nav = run.marked_nav
+                .checked_add(amount)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+        }
+
+        msg!("User {} deposited {} USDC to run #{} for {} shares",
+            ctx.accounts.user.key(), run.ui(amount), run_id, run.ui(shares));
+        Ok(())
+    }
+
+    /// Burn shares of a settled or refunding share-token run for their pro-rata part of the vault.
+    /// Open-ended runs also redeem before settlement, at the marked NAV while trading
+    pub fn redeem_shares(
+        ctx: Context<RedeemShares>,
+        run_id: u64,
+        shares: u64,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let run = &ctx.accounts.run;
+        require!(run.is_share_run(), ErrorCode::NotShareTokenRun);
+        require!(run.status != RunStatus::Disputed, ErrorCode::SettlementDisputed);
+        let open_redemption = run.open_ended
+            && matches!(run.status, RunStatus::Waiting | RunStatus::Active)
+            && !run.is_paused;
+        require!(
+            open_redemption || run.status == RunStatus::Settled || run.status.is_refundable(),
+            ErrorCode::RunNotSettled
+        );
+        require!(shares > 0, ErrorCode::InsufficientShares);
+        BlacklistEntry::check_not_listed(&ctx.accounts.blacklist_entry)?;
+        run.check_claim_open(now)?;
+
+        let supply = ctx.accounts.share_mint.supply;
+        let total_assets = run.nav_assets(ctx.accounts.run_vault.amount, now)?;
+        let payout = Run::convert_to_assets(shares, total_assets, supply)?;
+        require!(payout <= ctx.accounts.run_vault.amount, ErrorCode::InsufficientVaultLiquidity);
+        let principal = run.principal_of(shares, supply)?;
+
+        let cpi_accounts = token::Burn {
+            mint: ctx.accounts.share_mint.to_account_info(),
+            from: ctx.accounts.user_share_account.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::burn(cpi_ctx, shares)?;
+
+        let platform_key = run.platform;
+        let run_id_bytes = run.run_id.to_le_bytes();
+        let run_seeds = &[
+            b"run".as_ref(),
+            platform_key.as_ref(),
+            run_id_bytes.as_ref(),
+            &[run.bump],
+        ];
+        let signer = &[&run_seeds[..]];
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.run_vault.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: ctx.accounts.run.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, payout)?;
+
+        let run = &mut ctx.accounts.run;
+        run.total_withdrawn = run.total_withdrawn
+            .checked_add(payout)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        run.total_paid_out = run.total_paid_out
+            .checked_add(payout)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        if run.open_ended {
+            // Net principal stays the cost basis that settlement fees are charged against
+            run.total_deposited = run.total_deposited.saturating_sub(principal);
+        }
+        if run.uses_marked_nav() {
+            run.marked_nav = run.marked_nav.saturating_sub(payout);
+        }
+
+        let platform = &mut ctx.accounts.platform;
+        platform.total_value_locked = platform.total_value_locked.saturating_sub(principal);
+        platform.record_withdrawal(payout)?;
+
+        msg!("User {} redeemed {} shares of run #{} for {} USDC",
+            ctx.accounts.user.key(), run.ui(shares), run_id, run.ui(payout));
+        Ok(())
+    }
+
+    /// Follow a manager: new runs they operate can be auto-joined with up to `max_per_run` USDC.
+    /// Funds move under an SPL token approval granted to the subscription PDA
+    pub fn subscribe(
+        ctx: Context<Subscribe>,
+        manager: Pubkey,
+        max_per_run: u64,
+    ) -> Result<()> {
+        require!(max_per_run > 0, ErrorCode::InvalidDepositAmount);
+
+        let subscription = &mut ctx.accounts.subscription;
+        subscription.user = ctx.accounts.user.key();
+        subscription.manager = manager;
+        subscription.max_per_run = max_per_run;
+        subscription.active = true;
+        subscription.bump = ctx.bumps.subscription;
+
+        msg!("User {} subscribed to manager {} with up to {} USDC per run",
+            subscription.user, manager, max_per_run);
+        Ok(())
+    }
+
+    /// Change the per-run amount or pause a subscription
+    pub fn update_subscription(
+        ctx: Context<UpdateSubscription>,
+        manager: Pubkey,
+        max_per_run: u64,
+        active: bool,
+    ) -> Result<()> {
+        require!(max_per_run > 0, ErrorCode::InvalidDepositAmount);
+
+        let subscription = &mut ctx.accounts.subscription;
+        subscription.max_per_run = max_per_run;
+        subscription.active = active;
+
+        msg!("Subscription to manager {} updated - {} USDC per run, active: {}",
+            manager, max_per_run, active);
+        Ok(())
+    }
+
+    /// Stop following a manager and reclaim the subscription rent
+    pub fn unsubscribe(_ctx: Context<Unsubscribe>, manager: Pubkey) -> Result<()> {
+        msg!("Unsubscribed from manager {}", manager);
+        Ok(())
+    }
+
+    /// Deposit a subscriber's pre-approved funds into a Waiting run of the manager they follow (anyone)
+    pub fn auto_enroll(
+        ctx: Context<AutoEnroll>,
+        run_id: u64,
+    ) -> Result<()> {
+        let run = &mut ctx.accounts.run;
+        let subscription = &ctx.accounts.subscription;
+        let now = Clock::get()?.unix_timestamp;
+        let amount = subscription.max_per_run.min(run.max_deposit);
+
+        require!(subscription.active, ErrorCode::SubscriptionInactive);
+        require!(
+            run.manager != Pubkey::default() && run.manager == subscription.manager,
+            ErrorCode::SubscriptionMismatch
+        );
+        run.check_delegated_deposit(
+            &ctx.accounts.platform,
+            &ctx.accounts.blacklist_entry,
+            &ctx.accounts.stake,
+            ctx.accounts.kyc_attestation.as_deref(),
+            amount,
+            now,
+        )?;
+
+        ctx.accounts.platform.record_deposit(amount)?;
+
+        let platform_key = ctx.accounts.platform.key();
+        let subscription_seeds = &[
+            b"subscription".as_ref(),
+            platform_key.as_ref(),
+            subscription.user.as_ref(),
+            subscription.manager.as_ref(),
+            &[subscription.bump],
+        ];
+        let signer = &[&subscription_seeds[..]];
+
+        write_memo(
+            &ctx.accounts.memo_program,
+            &format!("instinct:deposit run_id={} participation={}", run_id, ctx.accounts.user_participation.key()),
+        )?;
+        // The subscription PDA moves the funds as the token account's approved delegate
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.user_token_account.to_account_info(),
+            to: ctx.accounts.run_vault.to_account_info(),
+            authority: ctx.accounts.subscription.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, amount)?;
+
+        let user = ctx.accounts.subscription.user;
+        ctx.accounts.user_participation.record_deposit(
+            user,
+            run_id,
+            amount,
+            ctx.bumps.user_participation,
+        );
+
+        ctx.accounts.platform.admit_participant(
+            run,
+            &ctx.accounts.participant_page,
+            ctx.accounts.user_participation.user,
+            ctx.bumps.participant_page,
+            amount,
+            now,
+        )?;
+
+        msg!("Subscriber {} auto-enrolled with {} USDC in run #{}", user, run.ui(amount), run_id);
+        KeeperIncentive::reward(&mut ctx.accounts.keeper_incentive, &ctx.accounts.cranker.to_account_info())?;
+        Ok(())
+    }
+
+    /// Set up recurring deposits into a run series, funded by an SPL token approval to the plan PDA
+    pub fn create_dca_plan(
+        ctx: Context<CreateDcaPlan>,
+        series_id: u64,
+        amount: u64,
+        cadence_secs: i64,
+    ) -> Result<()> {
+        require!(series_id != 0, ErrorCode::InvalidSeries);
+        require!(amount > 0, ErrorCode::InvalidDepositAmount);
+        require!(cadence_secs > 0, ErrorCode::InvalidDuration);
+
+        let plan = &mut ctx.accounts.dca_plan;
+        plan.user = ctx.accounts.user.key();
+        plan.series_id = series_id;
+        plan.amount = amount;
+        plan.cadence_secs = cadence_secs;
+        plan.funding_account = ctx.accounts.funding_account.key();
+        plan.next_execution = Clock::get()?.unix_timestamp;
+        plan.executions = 0;
+        plan.bump = ctx.bumps.dca_plan;
+
+        msg!("DCA plan: {} USDC into series #{} every {}s", amount, series_id, cadence_secs);
+        Ok(())
+    }
+
+    /// Close a DCA plan and reclaim its rent
+    pub fn cancel_dca_plan(_ctx: Context<CancelDcaPlan>, series_id: u64) -> Result<()> {
+        msg!("DCA plan for series #{} cancelled", series_id);
+        Ok(())
+    }
+
+    /// Deposit a DCA plan's amount into the open run of its series once the interval has passed (anyone)
+    pub fn execute_dca(
+        ctx: Context<ExecuteDca>,
+        run_id: u64,
+    ) -> Result<()> {
+        let run = &mut ctx.accounts.run;
+        let plan = &ctx.accounts.dca_plan;
+        let now = Clock::get()?.unix_timestamp;
+        let amount = plan.amount;
+
+        require!(now >= plan.next_execution, ErrorCode::DcaNotDue);
+        require!(run.series_id == plan.series_id, ErrorCode::InvalidSeries);
+        run.check_delegated_deposit(
+            &ctx.accounts.platform,
+            &ctx.accounts.blacklist_entry,
+            &ctx.accounts.stake,
+            ctx.accounts.kyc_attestation.as_deref(),
+            amount,
+            now,
+        )?;
+
+        ctx.accounts.platform.record_deposit(amount)?;
+
+        let platform_key = ctx.accounts.platform.key();
+        let series_id_bytes = plan.series_id.to_le_bytes();
+        let plan_seeds = &[
+            b"dca_plan".as_ref(),
+            platform_key.as_ref(),
+            plan.user.as_ref(),
+            series_id_bytes.as_ref(),
+            &[plan.bump],
+        ];
+        let signer = &[&plan_seeds[..]];
+
+        write_memo(
+            &ctx.accounts.memo_program,
+            &format!("instinct:deposit run_id={} participation={}", run_id, ctx.accounts.user_participation.key()),
+        )?;
+        // The plan PDA moves the funds as the funding account's approved delegate
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.funding_account.to_account_info(),
+            to: ctx.accounts.run_vault.to_account_info(),
+            authority: ctx.accounts.dca_plan.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, amount)?;
+
+        let user = ctx.accounts.dca_plan.user;
+        ctx.accounts.user_participation.record_deposit(
+            user,
+            run_id,
+            amount,
+            ctx.bumps.user_participation,
+        );
+        ctx.accounts.platform.admit_participant(
+            run,
+            &ctx.accounts.participant_page,
+            ctx.accounts.user_participation.user,
+            ctx.bumps.participant_page,
+            amount,
+            now,
+        )?;
+
+        let plan = &mut ctx.accounts.dca_plan;
+        plan.next_execution = now.saturating_add(plan.cadence_secs);
+        plan.executions = plan.executions
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        msg!("DCA #{} for {} deposited {} USDC in run #{}", plan.executions, user, run.ui(amount), run_id);
+        KeeperIncentive::reward(&mut ctx.accounts.keeper_incentive, &ctx.accounts.cranker.to_account_info())?;
+        Ok(())
+    }
+
+    /// Leave a run before it starts and take the deposit back
+    pub fn leave_run(
+        ctx: Context<LeaveRun>,
+        run_id: u64,
+    ) -> Result<()> {
+        let run = &ctx.accounts.run;
+        let amount = ctx.accounts.user_participation.deposit_amount;
+
+        require!(run.status == RunStatus::Waiting, ErrorCode::RunNotInWaitingPhase);
+        ctx.accounts.user_participation.check_not_frozen()?;
+        // Other stablecoins sit in a sub-vault until converted, so the USDC vault cannot refund them early
+        require!(
+            ctx.accounts.user_participation.deposit_mint == Pubkey::default(),
+            ErrorCode::AltDepositLocked
+        );
+
+        let platform_key = run.platform;
+        let run_id_bytes = run.run_id.to_le_bytes();
+        let run_seeds = &[
+            b"run".as_ref(),
+            platform_key.as_ref(),
+            run_id_bytes.as_ref(),
+            &[run.bump],
+        ];
+        let signer = &[&run_seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.run_vault.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: ctx.accounts.run.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, amount)?;
+
+        let run = &mut ctx.accounts.run;
+        // Drop any vote contribution together with the deposit
+        run.record_vote_stats(&ctx.accounts.user_participation, 0, 0)?;
+        run.total_deposited = run.total_deposited
+            .checked_sub(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        run.participant_count = run.participant_count
+            .checked_sub(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        let platform = &mut ctx.accounts.platform;
+        platform.total_value_locked = platform.total_value_locked.saturating_sub(amount);
+        platform.record_withdrawal(amount)?;
+
+        RentTreasury::recapture(&mut ctx.accounts.rent_treasury, &mut ctx.accounts.user_participation)?;
+
+        msg!("User {} left run #{} and reclaimed {} USDC",
+            ctx.accounts.user.key(), run_id, ctx.accounts.run.ui(amount));
+        Ok(())
+    }
+
+    /// Leave an active run early for the current value of the deposit, minus the exit penalty.
+    /// Remaining accounts are (strategy, strategy vault) pairs for every strategy in index order,
+    /// so capital allocated to strategies counts toward the run's value; the run vault must hold
+    /// enough to pay the exit
+    pub fn emergency_exit<'info>(
+        ctx: Context<'_, '_, 'info, 'info, EmergencyExit<'info>>,
+        run_id: u64,
+    ) -> Result<()> {
+        let run = &ctx.accounts.run;
+        let deposit_amount = ctx.accounts.user_participation.deposit_amount;
+
+        require!(run.status == RunStatus::Active, ErrorCode::InvalidRunStatus);
+        require!(!run.is_paused, ErrorCode::RunPaused);
+        require!(run.participant_count > 1, ErrorCode::LastParticipantCannotExit);
+        // NAV only counts the run's USDC, so every deposit has to be in it
+        require!(run.unconverted_deposits == 0, ErrorCode::UnconvertedDeposits);
+        BlacklistEntry::check_not_listed(&ctx.accounts.blacklist_entry)?;
+        ctx.accounts.user_participation.check_not_frozen()?;
+
+        // Mid-run NAV is whatever the run and strategy vaults hold right now
+        let strategies = StrategyVault::load_all(
+            ctx.remaining_accounts,
+            &ctx.accounts.platform.key(),
+            run_id,
+            run.strategy_count as usize,
+        )?;
+        let nav = strategies.iter().try_fold(ctx.accounts.run_vault.amount, |nav, (_, balance)| {
+            nav.checked_add(*balance).ok_or(ErrorCode::ArithmeticOverflow)
+        })?;
+        let nav_share = (deposit_amount as u128)
+            .checked_mul(nav as u128)
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+            .checked_div(run.total_deposited as u128)
+            .ok_or(ErrorCode::ArithmeticOverflow)? as u64;
+        // Stakers get their tier's discount on the penalty
+        let now = Clock::get()?.unix_timestamp;
+        let discount_bps = ctx.accounts.platform
+            .stake_discount_bps(Stake::boosted_amount_of(&ctx.accounts.stake, now));
+        let penalty = (nav_share as u128)
+            .checked_mul(ctx.accounts.platform.exit_penalty_bps as u128)
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+            .checked_mul(10000 - discount_bps as u128)
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+            .checked_div(10000 * 10000)
+            .ok_or(ErrorCode::ArithmeticOverflow)? as u64;
+        let payout = nav_share
+            .checked_sub(penalty)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        let to_treasury = ctx.accounts.platform.exit_penalty_to_treasury && penalty > 0;
+        let leaving_vault = if to_treasury { nav_share } else { payout };
+        require!(leaving_vault <= ctx.accounts.run_vault.amount, ErrorCode::InsufficientVaultFunds);
+
+        let platform_key = run.platform;
+        let run_id_bytes = run.run_id.to_le_bytes();
+        let run_seeds = &[
+            b"run".as_ref(),
+            platform_key.as_ref(),
+            run_id_bytes.as_ref(),
+            &[run.bump],
+        ];
+        let signer = &[&run_seeds[..]];
+
+        write_memo(
+            &ctx.accounts.memo_program,
+            &format!("instinct:exit run_id={} participation={}", run_id, ctx.accounts.user_participation.key()),
+        )?;
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.run_vault.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: ctx.accounts.run.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, payout)?;
+
+        // Otherwise the penalty stays in the vault and accrues to the remaining participants
+        if to_treasury {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.run_vault.to_account_info(),
+                to: ctx.accounts.platform_fee_vault.to_account_info(),
+                authority: ctx.accounts.run.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+            token::transfer(cpi_ctx, penalty)?;
+        }
+
+        let run = &mut ctx.accounts.run;
+        // The departing deposit no longer shares in the zero-sum vote bonus or penalty
+        run.record_vote_stats(&ctx.accounts.user_participation, 0, 0)?;
+        run.total_deposited = run.total_deposited
+            .checked_sub(deposit_amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        run.participant_count = run.participant_count
+            .checked_sub(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        let platform = &mut ctx.accounts.platform;
+        platform.total_value_locked = platform.total_value_locked.saturating_sub(deposit_amount);
+        platform.record_withdrawal(payout)?;
+        if to_treasury {
+            run.exit_penalties_collected = run.exit_penalties_collected
+                .checked_add(penalty)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+            platform.total_fees_collected = platform.total_fees_collected
+                .checked_add(penalty)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+        }
+
+        RentTreasury::recapture(&mut ctx.accounts.rent_treasury, &mut ctx.accounts.user_participation)?;
+
+        msg!("User {} exited run #{} early - paid {} USDC, penalty {}",
+            ctx.accounts.user.key(), run_id, ctx.accounts.run.ui(payout), ctx.accounts.run.ui(penalty));
+        Ok(())
+    }
+
+    /// Create the escrow vault for a run's waitlist
+    pub fn create_waitlist_vault(
+        _ctx: Context<CreateWaitlistVault>,
+        run_id: u64,
+    ) -> Result<()> {
+        msg!("Waitlist vault created for run #{}", run_id);
+        Ok(())
+    }
+
+    /// Queue for a full run, escrowing the deposit until a spot frees up; a wallet holds at most
+    /// one place in a run's queue
+    pub fn join_waitlist(
+        ctx: Context<JoinWaitlist>,
+        run_id: u64,
+        amount: u64,
+    ) -> Result<()> {
+        let run = &ctx.accounts.run;
+
+        require!(!ctx.accounts.platform.is_paused, ErrorCode::PlatformPaused);
+        BlacklistEntry::check_not_listed(&ctx.accounts.blacklist_entry)?;
+        require!(run.status == RunStatus::Waiting, ErrorCode::RunNotInWaitingPhase);
+        require!(run.participant_count >= run.max_participants, ErrorCode::RunNotFull);
+        // Promotion cannot present an invite code, so private runs have no waitlist
+        require!(!run.is_private, ErrorCode::InviteRequired);
+        require!(amount >= run.min_deposit, ErrorCode::DepositTooLow);
+        require!(amount <= run.max_deposit, ErrorCode::DepositTooHigh);
+        if run.requires_kyc {
+            KycAttestation::check(
+                ctx.accounts.kyc_attestation.as_deref(),
+                ctx.accounts.platform.compliance_authority,
+                Clock::get()?.unix_timestamp,
+            )?;
+        }
+        // A queued user must not already hold a participation, or promotion could never succeed
+        require!(
+            ctx.accounts.user_participation.data_is_empty(),
+            ErrorCode::AlreadyParticipating
+        );
+
+        write_memo(
+            &ctx.accounts.memo_program,
+            &format!("instinct:waitlist run_id={} entry={}", run_id, ctx.accounts.waitlist_entry.key()),
+        )?;
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.user_token_account.to_account_info(),
+            to: ctx.accounts.waitlist_vault.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+
+        let position = run.waitlist_tail;
+        let entry = &mut ctx.accounts.waitlist_entry;
+        entry.run_id = run_id;
+        entry.user = ctx.accounts.user.key();
+        entry.amount = amount;
+        entry.position = position;
+        entry.bump = ctx.bumps.waitlist_entry;
+        let marker = &mut ctx.accounts.waitlist_marker;
+        marker.position = position;
+        marker.bump = ctx.bumps.waitlist_marker;
+
+        let run = &mut ctx.accounts.run;
+        run.waitlist_tail = run.waitlist_tail
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        msg!("User {} joined waitlist for run #{} at position {} with {} USDC",
+            ctx.accounts.user.key(), run_id, position, run.ui(amount));
+        Ok(())
+    }
+
+    /// Move the head of the waitlist into a freed spot (anyone can crank)
+    pub fn promote_from_waitlist(
+        ctx: Context<PromoteFromWaitlist>,
+        run_id: u64,
+    ) -> Result<()> {
+        let amount = ctx.accounts.waitlist_entry.amount;
+        let user = ctx.accounts.waitlist_entry.user;
+        let position = ctx.accounts.waitlist_entry.position;
+        let now = Clock::get()?.unix_timestamp;
+
+        BlacklistEntry::check_not_listed(&ctx.accounts.blacklist_entry)?;
+        ctx.accounts.run.check_deposit(amount, now)?;
+        ctx.accounts.platform.record_deposit(amount)?;
+
+        let platform_key = ctx.accounts.run.platform;
+        let run_id_bytes = ctx.accounts.run.run_id.to_le_bytes();
+        let run_seeds = &[
+            b"run".as_ref(),
+            platform_key.as_ref(),
+            run_id_bytes.as_ref(),
+            &[ctx.accounts.run.bump],
+        ];
+        let signer = &[&run_seeds[..]];
+
+        write_memo(
+            &ctx.accounts.memo_program,
+            &format!("instinct:deposit run_id={} participation={}", run_id, ctx.accounts.user_participation.key()),
+        )?;
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.waitlist_vault.to_account_info(),
+            to: ctx.accounts.run_vault.to_account_info(),
+            authority: ctx.accounts.run.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, amount)?;
+
+        ctx.accounts.user_participation.record_deposit(
+            user,
+            run_id,
+            amount,
+            ctx.bumps.user_participation,
+        );
+
+        let run = &mut ctx.accounts.run;
+        run.waitlist_head = run.waitlist_head
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        ctx.accounts.platform.admit_participant(
+            run,
+            &ctx.accounts.participant_page,
+            ctx.accounts.user_participation.user,
+            ctx.bumps.participant_page,
+            amount,
+            now,
+        )?;
+
+        msg!("Waitlist position {} promoted - user {} joined run #{} with {} USDC",
+            position, user, run_id, run.ui(amount));
+        KeeperIncentive::reward(&mut ctx.accounts.keeper_incentive, &ctx.accounts.cranker.to_account_info())?;
+        Ok(())
+    }
+
+    /// Step past a waitlist head that can never be promoted - its wallet is blacklisted or already
+    /// participating, or its deposit is past the deadline, outside the deposit limits or over a TVL
+    /// cap - so the queue keeps moving. The skipped user reclaims the deposit with leave_waitlist
+    /// (anyone can crank)
+    pub fn skip_waitlist_head(
+        ctx: Context<SkipWaitlistHead>,
+        run_id: u64,
+    ) -> Result<()> {
+        let run = &ctx.accounts.run;
+        let entry = &ctx.accounts.waitlist_entry;
+        require!(run.status == RunStatus::Waiting, ErrorCode::RunNotInWaitingPhase);
+        let unpromotable = !ctx.accounts.blacklist_entry.data_is_empty()
+            || !ctx.accounts.user_participation.data_is_empty()
+            || run.rejects_waitlisted(&ctx.accounts.platform, entry.amount, Clock::get()?.unix_timestamp);
+        require!(unpromotable, ErrorCode::WaitlistHeadPromotable);
+
+        let position = entry.position;
+        let user = entry.user;
+        let run = &mut ctx.accounts.run;
+        run.waitlist_head = run.waitlist_head
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        msg!("Skipped waitlist position {} of run #{} - user {} cannot be promoted", position, run_id, user);
+        KeeperIncentive::reward(&mut ctx.accounts.keeper_incentive, &ctx.accounts.cranker.to_account_info())?;
+        Ok(())
+    }
+
+    /// Reclaim an escrowed waitlist deposit once the run has stopped accepting participants, or
+    /// once the entry has been skipped
+    pub fn leave_waitlist(
+        ctx: Context<LeaveWaitlist>,
+        run_id: u64,
+        position: u64,
+    ) -> Result<()> {
+        let run = &ctx.accounts.run;
+
+        // While Waiting the queue order is binding; entries unlock once the run moves on or skips them
+        require!(
+            run.status != RunStatus::Waiting || position < run.waitlist_head,
+            ErrorCode::RunNotInWaitingPhase
+        );
+
+        let amount = ctx.accounts.waitlist_entry.amount;
+        let platform_key = run.platform;
+        let run_id_bytes = run.run_id.to_le_bytes();
+        let run_seeds = &[
+            b"run".as_ref(),
+            platform_key.as_ref(),
+            run_id_bytes.as_ref(),
+            &[run.bump],
+        ];
+        let signer = &[&run_seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.waitlist_vault.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: ctx.accounts.run.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, amount)?;
+
+        msg!("User {} left waitlist position {} of run #{} and reclaimed {} USDC",
+            ctx.accounts.user.key(), position, run_id, run.ui(amount));
+        Ok(())
+    }
+
+    /// Open a squad that pools its members' contributions into a single participation in a
+    /// Waiting run; the caller leads it and commits the pool
+    pub fn create_squad(
+        ctx: Context<CreateSquad>,
+        run_id: u64,
+    ) -> Result<()> {
+        let run = &ctx.accounts.run;
+        require!(run.status == RunStatus::Waiting, ErrorCode::RunNotInWaitingPhase);
+        // The squad commits without a member present, so it cannot redeem an invite
+        require!(!run.is_private, ErrorCode::InviteRequired);
+        require!(!run.is_share_run(), ErrorCode::ShareTokenRun);
+        // Members are paid from the squad vault in one go, which vesting would split
+        require!(run.profit_vesting_secs == 0, ErrorCode::ProfitVestingActive);
+
+        let squad = &mut ctx.accounts.squad;
+        squad.leader = ctx.accounts.leader.key();
+        squad.run_id = run_id;
+        squad.total_contributed = 0;
+        squad.member_count = 0;
+        squad.claimed_count = 0;
+        squad.committed = false;
+        squad.bump = ctx.bumps.squad;
+
+        msg!("Squad {} created for run #{} by {}", squad.key(), run_id, squad.leader);
+        Ok(())
+    }
+
+    /// Add to a squad's pool before it is committed, escrowing the contribution in the squad vault
+    pub fn join_squad(
+        ctx: Context<JoinSquad>,
+        run_id: u64,
+        leader: Pubkey,
+        amount: u64,
+    ) -> Result<()> {
+        let run = &ctx.accounts.run;
+        require!(!ctx.accounts.platform.is_paused, ErrorCode::PlatformPaused);
+        BlacklistEntry::check_not_listed(&ctx.accounts.blacklist_entry)?;
+        require!(run.status == RunStatus::Waiting, ErrorCode::RunNotInWaitingPhase);
+        require!(!ctx.accounts.squad.committed, ErrorCode::SquadCommitted);
+        require!(amount > 0, ErrorCode::DepositTooLow);
+        if run.requires_kyc {
+            KycAttestation::check(
+                ctx.accounts.kyc_attestation.as_deref(),
+                ctx.accounts.platform.compliance_authority,
+                Clock::get()?.unix_timestamp,
+            )?;
+        }
+        // The pool is one deposit, so it must stay within the run's per-deposit limit
+        let total = ctx.accounts.squad.total_contributed
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        require!(total <= run.max_deposit, ErrorCode::DepositTooHigh);
+
+        write_memo(
+            &ctx.accounts.memo_program,
+            &format!("instinct:squad_contribution run_id={} squad={}", run_id, ctx.accounts.squad.key()),
+        )?;
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.member_token_account.to_account_info(),
+            to: ctx.accounts.squad_vault.to_account_info(),
+            authority: ctx.accounts.member.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+
+        let squad = &mut ctx.accounts.squad;
+        let squad_member = &mut ctx.accounts.squad_member;
+        if squad_member.member == Pubkey::default() {
+            squad_member.squad = squad.key();
+            squad_member.member = ctx.accounts.member.key();
+            squad_member.bump = ctx.bumps.squad_member;
+            squad.member_count = squad.member_count
+                .checked_add(1)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+        }
+        squad_member.contribution = squad_member.contribution
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        squad.total_contributed = total;
+
+        msg!("Member {} added {} USDC to squad {} of {} for run #{} ({} pooled)",
+            squad_member.member, run.ui(amount), squad.key(), leader, run_id, run.ui(total));
+        Ok(())
+    }
+
+    /// Take a contribution back out of a squad that has not been committed yet
+    pub fn leave_squad(
+        ctx: Context<LeaveSquad>,
+        run_id: u64,
+        leader: Pubkey,
+    ) -> Result<()> {
+        require!(!ctx.accounts.squad.committed, ErrorCode::SquadCommitted);
+        let amount = ctx.accounts.squad_member.contribution;
+
+        let squad = &ctx.accounts.squad;
+        let platform_key = ctx.accounts.run.platform;
+        let run_id_bytes = run_id.to_le_bytes();
+        let squad_seeds = &[
+            b"squad".as_ref(),
+            platform_key.as_ref(),
+            run_id_bytes.as_ref(),
+            leader.as_ref(),
+            &[squad.bump],
+        ];
+        let signer = &[&squad_seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.squad_vault.to_account_info(),
+            to: ctx.accounts.member_token_account.to_account_info(),
+            authority: ctx.accounts.squad.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, amount)?;
+
+        let squad = &mut ctx.accounts.squad;
+        squad.total_contributed = squad.total_contributed
+            .checked_sub(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        squad.member_count = squad.member_count
+            .checked_sub(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        msg!("Member {} left squad {} and reclaimed {} USDC",
+            ctx.accounts.member.key(), squad.key(), ctx.accounts.run.ui(amount));
+        Ok(())
+    }
+
+    /// Deposit a squad's pool into its run as one participation owned by the squad PDA (squad
+    /// leader). Deposit checks apply to the pooled amount and to the leader
+    pub fn commit_squad(
+        ctx: Context<CommitSquad>,
+        run_id: u64,
+    ) -> Result<()> {
+        ctx.accounts.run.check_vault(ctx.accounts.run.key(), &ctx.accounts.run_vault)?;
+        let amount = ctx.accounts.squad.total_contributed;
+        let now = Clock::get()?.unix_timestamp;
+        require!(!ctx.accounts.squad.committed, ErrorCode::SquadCommitted);
+        require!(amount > 0, ErrorCode::EmptySquad);
+
+        ctx.accounts.run.check_delegated_deposit(
+            &ctx.accounts.platform,
+            &ctx.accounts.blacklist_entry,
+            &ctx.accounts.stake,
+            ctx.accounts.kyc_attestation.as_deref(),
+            amount,
+            now,
+        )?;
+        ctx.accounts.platform.record_deposit(amount)?;
+
+        let squad_key = ctx.accounts.squad.key();
+        let platform_key = ctx.accounts.platform.key();
+        let leader_key = ctx.accounts.leader.key();
+        let run_id_bytes = run_id.to_le_bytes();
+        let squad_seeds = &[
+            b"squad".as_ref(),
+            platform_key.as_ref(),
+            run_id_bytes.as_ref(),
+            leader_key.as_ref(),
+            &[ctx.accounts.squad.bump],
+        ];
+        let signer = &[&squad_seeds[..]];
+
+        write_memo(
+            &ctx.accounts.memo_program,
+            &format!("instinct:deposit run_id={} participation={}", run_id, ctx.accounts.user_participation.key()),
+        )?;
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.squad_vault.to_account_info(),
+            to: ctx.accounts.run_vault.to_account_info(),
+            authority: ctx.accounts.squad.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, amount)?;
+
+        ctx.accounts.user_participation.record_deposit(
+            squad_key,
+            run_id,
+            amount,
+            ctx.bumps.user_participation,
+        );
+        ctx.accounts.squad.committed = true;
+
+        let run = &mut ctx.accounts.run;
+        ctx.accounts.platform.admit_participant(
+            run,
+            &ctx.accounts.participant_page,
+            squad_key,
+            ctx.bumps.participant_page,
+            amount,
+            now,
+        )?;
+
+        msg!("Squad {} joined run #{} with {} USDC from {} members",
+            squad_key, run_id, run.ui(amount), ctx.accounts.squad.member_count);
+        Ok(())
+    }
+
+    /// Pay a committed squad's settled share, or its refund, into the squad vault for its members
+    /// to claim (anyone can crank)
+    pub fn withdraw_squad(
+        ctx: Context<WithdrawSquad>,
+        run_id: u64,
+        leader: Pubkey,
+    ) -> Result<()> {
+        let run = &ctx.accounts.run;
+        let participation = &ctx.accounts.user_participation;
+        require!(!participation.withdrawn, ErrorCode::AlreadyWithdrawn);
+        participation.check_not_frozen()?;
+        run.check_solvency(ctx.accounts.run_vault.amount)?;
+
+        let share = if run.status.is_refundable() {
+            run.refund_share(participation.deposit_amount, ctx.accounts.run_vault.amount)?
+        } else {
+            require!(run.status != RunStatus::Disputed, ErrorCode::SettlementDisputed);
+            require!(run.status == RunStatus::Settled, ErrorCode::RunNotSettled);
+            run.check_claim_open(Clock::get()?.unix_timestamp)?;
+            // A squad has no stake of its own, so no vote-escrow boost applies
+            run.settled_share(
+                participation,
+                ctx.accounts.run_vault.amount,
+                ctx.accounts.platform.is_feature_enabled(FEATURE_BONUSES),
+                0,
+            )?
+        };
+
+        let platform_key = run.platform;
+        let run_id_bytes = run.run_id.to_le_bytes();
+        let run_seeds = &[
+            b"run".as_ref(),
+            platform_key.as_ref(),
+            run_id_bytes.as_ref(),
+            &[run.bump],
+        ];
+        let signer = &[&run_seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.run_vault.to_account_info(),
+            to: ctx.accounts.squad_vault.to_account_info(),
+            authority: ctx.accounts.run.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, share)?;
+
+        let run = &mut ctx.accounts.run;
+        run.record_payout(&mut ctx.accounts.user_participation, share, 0)?;
+
+        // Principal leaves the platform's TVL regardless of the P/L paid out
+        let platform = &mut ctx.accounts.platform;
+        platform.total_value_locked = platform.total_value_locked
+            .saturating_sub(ctx.accounts.user_participation.deposit_amount);
+        platform.record_withdrawal(share)?;
+
+        msg!("Squad {} of {} withdrew {} USDC from run #{} ({}/{})",
+            ctx.accounts.squad.key(), leader, run.ui(share), run_id, run.withdrawn_count, run.participant_count);
+        Ok(())
+    }
+
+    /// Claim a member's cut of what the squad's participation was paid, pro rata to their
+    /// contribution, once the squad has withdrawn
+    pub fn claim_member_share(
+        ctx: Context<ClaimMemberShare>,
+        run_id: u64,
+        leader: Pubkey,
+    ) -> Result<()> {
+        require!(ctx.accounts.user_participation.withdrawn, ErrorCode::SquadNotWithdrawn);
+        BlacklistEntry::check_not_listed(&ctx.accounts.blacklist_entry)?;
+
+        let squad = &ctx.accounts.squad;
+        let amount = squad.member_share(
+            ctx.accounts.squad_member.contribution,
+            ctx.accounts.user_participation.final_share,
+            ctx.accounts.squad_vault.amount,
+        )?;
+
+        let platform_key = ctx.accounts.platform.key();
+        let run_id_bytes = run_id.to_le_bytes();
+        let squad_seeds = &[
+            b"squad".as_ref(),
+            platform_key.as_ref(),
+            run_id_bytes.as_ref(),
+            leader.as_ref(),
+            &[squad.bump],
+        ];
+        let signer = &[&squad_seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.squad_vault.to_account_info(),
+            to: ctx.accounts.member_token_account.to_account_info(),
+            authority: ctx.accounts.squad.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, amount)?;
+
+        let squad = &mut ctx.accounts.squad;
+        squad.claimed_count = squad.claimed_count
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        msg!("Member {} claimed {} USDC from squad {} ({}/{})",
+            ctx.accounts.member.key(), ctx.accounts.run.ui(amount), squad.key(), squad.claimed_count, squad.member_count);
+        Ok(())
+    }
+
+    /// Split a bonus earned by a committed squad, e.g. a leaderboard prize, among its members by
+    /// contribution weight, paid from the caller's token account (anyone). Remaining accounts are
+    /// (squad member, member's associated token account, member's blacklist PDA) triples covering
+    /// every member in ascending wallet order, so it must run before any member claims their share;
+    /// the last member takes the rounding remainder and blacklisted members' cuts stay with the caller
+    pub fn distribute_squad_bonus<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DistributeSquadBonus<'info>>,
+        run_id: u64,
+        leader: Pubkey,
+        amount: u64,
+    ) -> Result<()> {
+        let squad = &ctx.accounts.squad;
+        require!(squad.committed, ErrorCode::SquadNotCommitted);
+        // Claimed members' accounts are closed, so the split would no longer cover them
+        require!(squad.claimed_count == 0, ErrorCode::SquadClaimsStarted);
+        require!(
+            ctx.remaining_accounts.len() == 3 * squad.member_count as usize,
+            ErrorCode::InvalidPayoutAccounts
+        );
+
+        let platform_key = ctx.accounts.platform.key();
+        let squad_key = squad.key();
+        let mut previous_member = Pubkey::default();
+        let mut allotted: u64 = 0;
+        let mut paid_total: u64 = 0;
+        for (index, triple) in ctx.remaining_accounts.chunks(3).enumerate() {
+            let (member_info, token_info, blacklist_info) = (&triple[0], &triple[1], &triple[2]);
+            let squad_member: Account<SquadMember> = Account::try_from(member_info)?;
+            require_keys_eq!(squad_member.squad, squad_key, ErrorCode::InvalidPayoutAccounts);
+            // Strictly ascending wallets rule out passing a member twice
+            require!(squad_member.member > previous_member, ErrorCode::InvalidPayoutAccounts);
+            previous_member = squad_member.member;
+            require_keys_eq!(
+                token_info.key(),
+                get_associated_token_address(&squad_member.member, &ctx.accounts.run.mint),
+                ErrorCode::InvalidPayoutAccounts
+            );
+            let (blacklist_key, _) = Pubkey::find_program_address(
+                &[b"blacklist", platform_key.as_ref(), squad_member.member.as_ref()],
+                &crate::ID,
+            );
+            require_keys_eq!(blacklist_info.key(), blacklist_key, ErrorCode::InvalidPayoutAccounts);
+
+            let cut = if index + 1 == squad.member_count as usize {
+                amount - allotted
+            } else {
+                (squad_member.contribution as u128)
+                    .checked_mul(amount as u128)
+                    .ok_or(ErrorCode::ArithmeticOverflow)?
+                    .checked_div(squad.total_contributed as u128)
+                    .ok_or(ErrorCode::ArithmeticOverflow)? as u64
+            };
+            allotted = allotted.checked_add(cut).ok_or(ErrorCode::ArithmeticOverflow)?;
+            if cut == 0 || BlacklistEntry::check_not_listed(blacklist_info).is_err() {
+                continue;
+            }
+
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.funder_token_account.to_account_info(),
+                to: token_info.clone(),
+                authority: ctx.accounts.funder.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+            token::transfer(cpi_ctx, cut)?;
+            paid_total = paid_total.saturating_add(cut);
+        }
+
+        msg!("Distributed {} bonus to the {} members of squad {} of {} in run #{}",
+            ctx.accounts.run.ui(paid_total), squad.member_count, squad_key, leader, run_id);
+        Ok(())
+    }
+
+    /// Create an invite code for a private run (platform authority or manager). An invite bound to a
+    /// wallet admits only that wallet, so its code cannot be replayed by anyone who saw it
+    pub fn create_invite(
+        ctx: Context<CreateInvite>,
+        run_id: u64,
+        code_hash: [u8; 32],
+        max_uses: u16,
+        wallet: Option<Pubkey>,
+    ) -> Result<()> {
+        require!(max_uses > 0, ErrorCode::InvalidInviteUses);
+
+        let invite = &mut ctx.accounts.invite;
+        invite.run_id = run_id;
+        invite.code_hash = code_hash;
+        invite.remaining_uses = max_uses;
+        invite.bump = ctx.bumps.invite;
+        invite.platform = ctx.accounts.run.platform;
+        invite.wallet = wallet.unwrap_or_default();
+
+        msg!("Invite created for run #{} with {} uses", run_id, max_uses);
+        Ok(())
+    }
+
+    /// Start a run (backend authority or run manager). Participations passed in remaining accounts
+    /// get their vote weight snapshotted; the rest can be snapshotted through snapshot_vote_weight
+    pub fn start_run<'info>(
+        ctx: Context<'_, '_, 'info, 'info, StartRun<'info>>,
+        run_id: u64,
+    ) -> Result<()> {
+        ctx.accounts.run.check_vault(ctx.accounts.run.key(), &ctx.accounts.run_vault)?;
+        let run = &mut ctx.accounts.run;
+        
+        require!(run.status == RunStatus::Waiting, ErrorCode::InvalidRunStatus);
+        require!(!run.is_paused, ErrorCode::RunPaused);
+        require!(run.has_deposits(), ErrorCode::NoParticipants);
+        require!(run.is_quorum_met(), ErrorCode::QuorumNotReached);
+
+        let now = Clock::get()?.unix_timestamp;
+        if !run.is_operator(&ctx.accounts.platform, ctx.accounts.authority.key()) {
+            // Only a registered automation thread gets past the account constraint this way
+            let automation = ctx.accounts.automation.as_ref().ok_or(ErrorCode::NotRunOperator)?;
+            require!(now >= automation.start_at, ErrorCode::AutomationNotDue);
+        }
+        run.start(now)?;
+        ctx.accounts.platform.add_exposure(run)?;
+        let snapshotted = UserParticipation::snapshot_vote_weights(ctx.remaining_accounts, &run.platform, run_id)?;
+
+        msg!("Run #{} started with {} participants and {} USDC ({} vote weights recorded)", 
+            run_id, run.participant_count, run.ui(run.total_deposited), snapshotted);
+        Ok(())
+    }
+
+    /// Let an automation thread (e.g. a Clockwork thread's signer PDA) call `start_run` once
+    /// `start_at` has passed, so the start doesn't wait on the operator's own cron. Overdue runs
+    /// need no registration: `force_refund_mode` is permissionless already (platform authority or manager)
+    pub fn register_run_automation(
+        ctx: Context<RegisterRunAutomation>,
+        run_id: u64,
+        thread: Pubkey,
+        start_at: i64,
+    ) -> Result<()> {
+        require!(ctx.accounts.run.status == RunStatus::Waiting, ErrorCode::InvalidRunStatus);
+        require!(start_at >= Clock::get()?.unix_timestamp, ErrorCode::InvalidAutomationSchedule);
+
+        let automation = &mut ctx.accounts.automation;
+        automation.run_id = run_id;
+        automation.thread = thread;
+        automation.start_at = start_at;
+        automation.bump = ctx.bumps.automation;
+
+        msg!("Run #{} will be started by thread {} at {}", run_id, thread, start_at);
+        Ok(())
+    }
+
+    /// Deregister a run's automation thread and reclaim its rent (platform authority or manager)
+    pub fn cancel_run_automation(ctx: Context<CancelRunAutomation>, run_id: u64) -> Result<()> {
+        msg!("Run #{} automation by thread {} cancelled", run_id, ctx.accounts.automation.thread);
+        Ok(())
+    }
+
+    /// Record the vote weight of a participant that was not snapshotted by start_run (anyone)
+    pub fn snapshot_vote_weight(
+        ctx: Context<SnapshotVoteWeight>,
+        run_id: u64,
+        _user: Pubkey,
+    ) -> Result<()> {
+        require!(ctx.accounts.run.status == RunStatus::Active, ErrorCode::InvalidRunStatus);
+        let participation = &mut ctx.accounts.user_participation;
+        require!(participation.vote_weight == 0, ErrorCode::VoteWeightAlreadyRecorded);
+        participation.vote_weight = participation.deposit_amount;
+        msg!("Vote weight of {} in run #{}: {}", participation.user, run_id, participation.vote_weight);
+        Ok(())
+    }
+
+    /// Mark an open-ended run's total assets, in and outside the vault. Deposits and redemptions
+    /// while it trades are priced off the latest mark, which goes stale after MAX_NAV_AGE_SECS
+    /// (platform authority or manager)
+    pub fn mark_nav(
+        ctx: Context<ManageRun>,
+        run_id: u64,
+        total_assets: u64,
+    ) -> Result<()> {
+        let run = &mut ctx.accounts.run;
+        require!(run.open_ended, ErrorCode::NotOpenEndedRun);
+        require!(run.status == RunStatus::Active, ErrorCode::InvalidRunStatus);
+
+        run.record_mark(total_assets, Clock::get()?.unix_timestamp);
+
+        msg!("Run #{} marked at {} USDC", run_id, run.ui(total_assets));
+        if run.breaker_tripped {
+            msg!("Run #{} paused by its drawdown breaker", run_id);
+        }
+        Ok(())
+    }
+
+    /// Stop an active run whose latest NAV mark is below its stop-loss floor: pull every strategy's
+    /// capital back into the run vault and open pro-rata refunds. Remaining accounts are
+    /// (strategy, strategy vault) pairs for every strategy in index order (anyone)
+    pub fn trigger_stop_loss<'info>(
+        ctx: Context<'_, '_, 'info, 'info, TriggerStopLoss<'info>>,
+        run_id: u64,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let run = &ctx.accounts.run;
+        require!(run.status == RunStatus::Active, ErrorCode::InvalidRunStatus);
+        require!(
+            run.stop_loss_nav > 0
+                && run.nav_marked_at >= run.started_at
+                && run.marked_nav < run.stop_loss_nav,
+            ErrorCode::StopLossNotTriggered
+        );
+
+        let platform_key = ctx.accounts.platform.key();
+        let run_id_bytes = run_id.to_le_bytes();
+        let strategies = StrategyVault::load_all(ctx.remaining_accounts, &platform_key, run_id, run.strategy_count as usize)?;
+        let run_seeds = &[
+            b"run".as_ref(),
+            platform_key.as_ref(),
+            run_id_bytes.as_ref(),
+            &[run.bump],
+        ];
+        let signer = &[&run_seeds[..]];
+        for (index, (mut strategy, balance)) in strategies.into_iter().enumerate() {
+            if balance > 0 {
+                let cpi_accounts = Transfer {
+                    from: ctx.remaining_accounts[index * 2 + 1].clone(),
+                    to: ctx.accounts.run_vault.to_account_info(),
+                    authority: ctx.accounts.run.to_account_info(),
+                };
+                let cpi_program = ctx.accounts.token_program.to_account_info();
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+                token::transfer(cpi_ctx, balance)?;
+            }
+            strategy.record_allocation(balance, 0)?;
+            strategy.exit(&crate::ID)?;
+        }
+
+        ctx.accounts.run_vault.reload()?;
+        let vault_balance = ctx.accounts.run_vault.amount;
+        let run = &mut ctx.accounts.run;
+        run.enter_refund_mode(RunStatus::Refunding, vault_balance, now)?;
+        ctx.accounts.platform.end_run(run);
+
+        msg!(
+            "Run #{} stop-loss triggered at {} (floor {}) - refunding {}",
+            run_id,
+            run.ui(run.marked_nav),
+            run.ui(run.stop_loss_nav),
+            run.ui(vault_balance)
+        );
+        KeeperIncentive::reward(&mut ctx.accounts.keeper_incentive, &ctx.accounts.caller.to_account_info())?;
+        Ok(())
+    }
+
+    /// Mark an active run's NAV against its deposits; a drawdown past `max_drawdown_bps` pauses
+    /// the run until the authority resumes it (platform authority or manager)
+    pub fn mark_to_market(
+        ctx: Context<ManageRun>,
+        run_id: u64,
+        nav: u64,
+    ) -> Result<()> {
+        let run = &mut ctx.accounts.run;
+        require!(run.status == RunStatus::Active, ErrorCode::InvalidRunStatus);
+        let drawdown_bps = run.record_mark(nav, Clock::get()?.unix_timestamp);
+
+        msg!("Run #{} marked to market at {} USDC ({} bps drawdown)", run_id, run.ui(nav), drawdown_bps);
+        if run.breaker_tripped {
+            msg!("Run #{} paused by its drawdown breaker", run_id);
+        }
+        Ok(())
+    }
+
+    /// Create the (empty) share table for a run (platform authority or manager)
+    pub fn init_share_table(
+        ctx: Context<InitShareTable>,
+        run_id: u64,
+    ) -> Result<()> {
+        let share_table = &mut ctx.accounts.share_table.load_init()?;
+        share_table.run_id = run_id;
+        share_table.capacity = 0;
+        share_table.len = 0;
+        share_table.bump = ctx.bumps.share_table;
+
+        msg!("Share table created for run #{}", run_id);
+        Ok(())
+    }
+
+    /// Grow the share table toward one entry per participant; repeat until fully allocated
+    pub fn allocate_share_table(
+        ctx: Context<AllocateShareTable>,
+        run_id: u64,
+    ) -> Result<()> {
+        require!(ctx.accounts.run.status == RunStatus::Active, ErrorCode::InvalidRunStatus);
+
+        let data_len = ctx.accounts.share_table.to_account_info().data_len();
+        let capacity = ShareTable::capacity_for(data_len);
+        ctx.accounts.share_table.load_mut()?.capacity = capacity;
+
+        msg!("Share table for run #{} holds {}/{} entries",
+            run_id, capacity, ctx.accounts.run.participant_count);
+        Ok(())
+    }
+
+    /// Create an address lookup table with the run PDA as its authority, so payout and vote batches
+    /// can address participants by index. `recent_slot` must be a recent slot, as the lookup table
+    /// program requires (platform authority or manager)
+    pub fn create_run_lookup_table(
+        ctx: Context<ManageRunLookupTable>,
+        run_id: u64,
+        recent_slot: u64,
+    ) -> Result<()> {
+        require_keys_eq!(ctx.accounts.run.lookup_table, Pubkey::default(), ErrorCode::LookupTableExists);
+        let (table, bump) = Pubkey::find_program_address(
+            &[ctx.accounts.run.key().as_ref(), &recent_slot.to_le_bytes()],
+            &ADDRESS_LOOKUP_TABLE_PROGRAM_ID,
+        );
+        require_keys_eq!(ctx.accounts.lookup_table.key(), table, ErrorCode::InvalidLookupTable);
+
+        // bincode ProgramInstruction::CreateLookupTable { recent_slot, bump_seed }
+        let mut data = 0u32.to_le_bytes().to_vec();
+        data.extend_from_slice(&recent_slot.to_le_bytes());
+        data.push(bump);
+        ctx.accounts.invoke_with_run_seeds(data)?;
+        ctx.accounts.run.lookup_table = table;
+
+        msg!("Run #{} lookup table {} created", run_id, table);
+        Ok(())
+    }
+
+    /// Append participant ATAs, participation PDAs and other run accounts to the run's lookup
+    /// table; addresses become usable one slot later (platform authority or manager)
+    pub fn extend_run_lookup_table(
+        ctx: Context<ManageRunLookupTable>,
+        run_id: u64,
+        addresses: Vec<Pubkey>,
+    ) -> Result<()> {
+        require!(!addresses.is_empty(), ErrorCode::InvalidLookupTable);
+        require_keys_eq!(ctx.accounts.lookup_table.key(), ctx.accounts.run.lookup_table, ErrorCode::InvalidLookupTable);
+
+        // bincode ProgramInstruction::ExtendLookupTable { new_addresses }
+        let mut data = 2u32.to_le_bytes().to_vec();
+        data.extend_from_slice(&(addresses.len() as u64).to_le_bytes());
+        for address in &addresses {
+            data.extend_from_slice(address.as_ref());
+        }
+        ctx.accounts.invoke_with_run_seeds(data)?;
+
+        msg!("Run #{} lookup table extended with {} addresses", run_id, addresses.len());
+        Ok(())
+    }
+
+    /// Settle a run with final P/L (backend authority or run manager, after trading ends). With a
+    /// risk oracle configured, `report` must match the settlement and be signed by the oracle in an
+    /// ed25519 program instruction placed right before this one. Runs too large for one
+    /// transaction settle in stages instead: begin_settlement, record_shares_batch, complete_settlement
+    pub fn settle_run<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettleRun<'info>>,
+        run_id: u64,
+        final_balance: u64,
+        participant_shares: Vec<ParticipantShare>,
+        report: Option<SettlementReport>,
+    ) -> Result<()> {
+        let run = &ctx.accounts.run;
+        require!(run.status == RunStatus::Active, ErrorCode::InvalidRunStatus);
+        require!(participant_shares.len() == run.participant_count as usize, ErrorCode::InvalidSharesCount);
+        ShareTable::write_entries(&ctx.accounts.share_table, 0, &participant_shares)?;
+        settle_recorded_shares(ctx, run_id, final_balance, report)
+    }
+
+    /// Stop trading and open a staged settlement, for runs whose share table does not fit one
+    /// transaction. Shares are then written with record_shares_batch (platform authority or manager)
+    pub fn begin_settlement(
+        ctx: Context<StageSettlement>,
+        run_id: u64,
+    ) -> Result<()> {
+        let run = &mut ctx.accounts.run;
+        require!(run.status == RunStatus::Active, ErrorCode::InvalidRunStatus);
+        require!(
+            ctx.accounts.share_table.load()?.capacity >= run.participant_count as u32,
+            ErrorCode::ShareTableTooSmall
+        );
+        run.transition_to(RunStatus::SettlementPending)?;
+        run.shares_recorded = 0;
+
+        msg!("Run #{} settlement begun for {} participants", run_id, run.participant_count);
+        Ok(())
+    }
+
+    /// Append the next batch of participant shares to a staged settlement's share table
+    /// (platform authority or manager)
+    pub fn record_shares_batch(
+        ctx: Context<StageSettlement>,
+        run_id: u64,
+        participant_shares: Vec<ParticipantShare>,
+    ) -> Result<()> {
+        let run = &mut ctx.accounts.run;
+        require!(run.status == RunStatus::SettlementPending, ErrorCode::InvalidRunStatus);
+        let recorded = (run.shares_recorded as usize)
+            .checked_add(participant_shares.len())
+            .filter(|&n| n <= run.participant_count as usize)
+            .ok_or(ErrorCode::InvalidSharesCount)?;
+        ShareTable::write_entries(&ctx.accounts.share_table, run.shares_recorded as usize, &participant_shares)?;
+        run.shares_recorded = recorded as u16;
+
+        msg!("Run #{} settlement recorded {}/{} shares", run_id, recorded, run.participant_count);
+        Ok(())
+    }
+
+    /// Finish a staged settlement once every participant's share is recorded; takes the same
+    /// accounts, fees, bond and oracle report as settle_run (platform authority or manager)
+    pub fn complete_settlement<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettleRun<'info>>,
+        run_id: u64,
+        final_balance: u64,
+        report: Option<SettlementReport>,
+    ) -> Result<()> {
+        let run = &ctx.accounts.run;
+        require!(run.status == RunStatus::SettlementPending, ErrorCode::InvalidRunStatus);
+        require!(run.shares_recorded == run.participant_count, ErrorCode::InvalidSharesCount);
+        settle_recorded_shares(ctx, run_id, final_balance, report)
+    }
+
+    /// Withdraw user's share after run settlement
+    pub fn withdraw(
+        ctx: Context<Withdraw>,
+        run_id: u64,
+    ) -> Result<()> {
+        let (_, paid_now, vesting_profit) = ctx.accounts.pay_share()?;
+
+        let run = &ctx.accounts.run;
+        msg!(
+            "User {} withdrew {} USDC from run #{} ({}/{}), {} vesting",
+            ctx.accounts.user.key(),
+            run.ui(paid_now),
+            run_id,
+            run.withdrawn_count,
+            run.participant_count,
+            vesting_profit
+        );
+        Ok(())
+    }
+
+    /// Compute what `withdraw` would pay `user` without moving funds; simulate the transaction
+    /// and read the return data to display exact numbers
+    pub fn preview_withdraw(
+        ctx: Context<PreviewWithdraw>,
+        run_id: u64,
+        user: Pubkey,
+    ) -> Result<WithdrawPreview> {
+        let run = &ctx.accounts.run;
+        let participation = &ctx.accounts.user_participation;
+        require!(run.status != RunStatus::Disputed, ErrorCode::SettlementDisputed);
+        require!(run.status == RunStatus::Settled, ErrorCode::RunNotSettled);
+        require!(!participation.withdrawn, ErrorCode::AlreadyWithdrawn);
+        let now = Clock::get()?.unix_timestamp;
+        run.check_claim_open(now)?;
+
+        let vote_boost_bps = Stake::vote_boost_bps(ctx.accounts.stake.as_deref(), ctx.accounts.platform.ve_boost_weight, now);
+        let share = run.settled_share(
+            participation,
+            ctx.accounts.run_vault.amount,
+            ctx.accounts.platform.is_feature_enabled(FEATURE_BONUSES),
+            vote_boost_bps,
+        )?;
+        let base_share = run.base_share(participation.deposit_amount, participation.fee_exempt)?;
+        let (paid_now, vesting) = run.split_vesting(participation, share);
+
+        msg!("Preview for {} in run #{}: share {} (bonus {}), {} now, {} vesting",
+            user, run_id, run.ui(share), run.ui(share.saturating_sub(base_share)), run.ui(paid_now), run.ui(vesting));
+        Ok(WithdrawPreview {
+            share,
+            base_share,
+            bonus: share.saturating_sub(base_share),
+            paid_now,
+            vesting,
+        })
+    }
+
+    /// Withdraw the full share and close the participation account, refunding its rent to the user
+    pub fn withdraw_and_close(
+        ctx: Context<Withdraw>,
+        run_id: u64,
+    ) -> Result<()> {
+        // Vesting profit is tracked on the participation, so it cannot be closed while any is owed
+        require!(ctx.accounts.run.profit_vesting_secs == 0, ErrorCode::ProfitVestingActive);
+        let (share, _, _) = ctx.accounts.pay_share()?;
+
+        RentTreasury::recapture(&mut ctx.accounts.rent_treasury, &mut ctx.accounts.user_participation)?;
+        let rent_refunded = ctx.accounts.user_participation.to_account_info().lamports();
+        ctx.accounts.user_participation.close(ctx.accounts.user.to_account_info())?;
+
+        emit_cpi!(WithdrawnAndClosed {
+            run_id,
+            user: ctx.accounts.user.key(),
+            share,
+            rent_refunded,
+        });
+        msg!("User {} withdrew {} USDC from run #{} and closed their participation",
+            ctx.accounts.user.key(), ctx.accounts.run.ui(share), run_id);
+        Ok(())
+    }
+
+    /// Release a settled run's funds once its escrow window has passed, opening withdrawals (anyone)
+    pub fn finalize_settlement(
+        ctx: Context<FinalizeSettlement>,
+        run_id: u64,
+    ) -> Result<()> {
+        let run = &mut ctx.accounts.run;
+        require!(run.status == RunStatus::Settled, ErrorCode::RunNotSettled);
+        require!(run.in_escrow, ErrorCode::SettlementNotInEscrow);
+        require!(Clock::get()?.unix_timestamp >= run.escrow_until, ErrorCode::SettlementInEscrow);
+        run.in_escrow = false;
+
+        msg!("Run #{} settlement finalized; withdrawals open", run_id);
+        KeeperIncentive::reward(&mut ctx.accounts.keeper_incentive, &ctx.accounts.caller.to_account_info())?;
+        Ok(())
+    }
+
+    /// Pay settled shares to participants who have not claimed (admin only). Remaining accounts are
+    /// (participation, user's associated token account, user's blacklist PDA, user's stake PDA)
+    /// groups; the PDAs may be uninitialized. Already-withdrawn, frozen and blacklisted
+    /// participations are skipped, and stakers get the same vote-escrow boost as on `withdraw`
+    pub fn push_payouts<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PushPayouts<'info>>,
+        run_id: u64,
+    ) -> Result<()> {
+        let run_status = ctx.accounts.run.status;
+        require!(run_status != RunStatus::Disputed, ErrorCode::SettlementDisputed);
+        require!(run_status == RunStatus::Settled, ErrorCode::RunNotSettled);
+        require!(ctx.remaining_accounts.len().is_multiple_of(4), ErrorCode::InvalidPayoutAccounts);
+        let now = Clock::get()?.unix_timestamp;
+        ctx.accounts.run.check_claim_open(now)?;
+
+        let platform_key = ctx.accounts.platform.key();
+        let bonuses_enabled = ctx.accounts.platform.is_feature_enabled(FEATURE_BONUSES);
+        let run_id_bytes = run_id.to_le_bytes();
+        let run_bump = ctx.accounts.run.bump;
+        let run_seeds = &[
+            b"run".as_ref(),
+            platform_key.as_ref(),
+            run_id_bytes.as_ref(),
+            &[run_bump],
+        ];
+        let signer = &[&run_seeds[..]];
+
+        let ve_boost_weight = ctx.accounts.platform.ve_boost_weight;
+        let mut paid_count: u32 = 0;
+        for group in ctx.remaining_accounts.chunks(4) {
+            let (participation_info, token_info, blacklist_info, stake_info) =
+                (&group[0], &group[1], &group[2], &group[3]);
+            let mut participation: Account<UserParticipation> = Account::try_from(participation_info)?;
+            let expected = Pubkey::create_program_address(
+                &[
+                    b"participation",
+                    platform_key.as_ref(),
+                    run_id_bytes.as_ref(),
+                    participation.user.as_ref(),
+                    &[participation.bump],
+                ],
+                &crate::ID,
+            )
+            .map_err(|_| ErrorCode::InvalidPayoutAccounts)?;
+            require_keys_eq!(participation_info.key(), expected, ErrorCode::InvalidPayoutAccounts);
+            // Frozen participants stay in the vault until unfrozen and withdraw themselves
+            if participation.withdrawn || participation.frozen {
+                continue;
+            }
+            require_keys_eq!(
+                token_info.key(),
+                get_associated_token_address(&participation.user, &ctx.accounts.run.mint),
+                ErrorCode::InvalidPayoutAccounts
+            );
+            let (blacklist_key, _) = Pubkey::find_program_address(
+                &[b"blacklist", platform_key.as_ref(), participation.user.as_ref()],
+                &crate::ID,
+            );
+            require_keys_eq!(blacklist_info.key(), blacklist_key, ErrorCode::InvalidPayoutAccounts);
+            // Blacklisted wallets cannot withdraw themselves either
+            if !blacklist_info.data_is_empty() {
+                continue;
+            }
+            let (stake_key, _) = Pubkey::find_program_address(
+                &[b"stake", platform_key.as_ref(), participation.user.as_ref()],
+                &crate::ID,
+            );
+            require_keys_eq!(stake_info.key(), stake_key, ErrorCode::InvalidPayoutAccounts);
+            let stake = Stake::read_optional(stake_info)?;
+            let vote_boost_bps = Stake::vote_boost_bps(stake.as_ref(), ve_boost_weight, now);
+
+            ctx.accounts.run_vault.reload()?;
+            let run = &ctx.accounts.run;
+            run.check_solvency(ctx.accounts.run_vault.amount)?;
+            let share = run.settled_share(&participation, ctx.accounts.run_vault.amount, bonuses_enabled, vote_boost_bps)?;
+            let (paid_now, vesting_profit) = run.split_vesting(&participation, share);
+
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.run_vault.to_account_info(),
+                to: token_info.clone(),
+                authority: ctx.accounts.run.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+            token::transfer(cpi_ctx, paid_now)?;
+
+            ctx.accounts.run.record_payout(&mut participation, share, vesting_profit)?;
+            participation.exit(&crate::ID)?;
+
+            let platform = &mut ctx.accounts.platform;
+            platform.total_value_locked = platform.total_value_locked
+                .saturating_sub(participation.deposit_amount);
+            platform.record_withdrawal(paid_now)?;
+            paid_count += 1;
+        }
+
+        msg!("Pushed {} payouts for run #{} ({}/{} withdrawn)",
+            paid_count, run_id, ctx.accounts.run.withdrawn_count, ctx.accounts.run.participant_count);
+        Ok(())
+    }
+
+    /// Agree to have your settled share rolled into `next_run_id` instead of withdrawing it; pass
+    /// 0 to withdraw normally
+    pub fn set_capital_migration(
+        ctx: Context<SetCapitalMigration>,
+        run_id: u64,
+        next_run_id: u64,
+    ) -> Result<()> {
+        require!(next_run_id != run_id, ErrorCode::InvalidMigrationRun);
+        let participation = &mut ctx.accounts.user_participation;
+        require!(!participation.withdrawn, ErrorCode::AlreadyWithdrawn);
+        participation.migrate_to_run = next_run_id;
+        msg!("{} will roll their share of run #{} into run #{}", participation.user, run_id, next_run_id);
+        Ok(())
+    }
+
+    /// Move a consenting participant's settled share straight from a settled run's vault into the
+    /// next run's vault in one transfer, opening their participation there (admin only). Vesting
+    /// profit stays claimable in the old run, and a staker's share carries their vote-escrow boost
+    pub fn migrate_capital(
+        ctx: Context<MigrateCapital>,
+        old_run_id: u64,
+        new_run_id: u64,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        ctx.accounts.platform.require_feature(FEATURE_ROLLOVER)?;
+        BlacklistEntry::check_not_listed(&ctx.accounts.blacklist_entry)?;
+        require!(
+            ctx.accounts.old_participation.migrate_to_run == new_run_id,
+            ErrorCode::MigrationNotOptedIn
+        );
+        require!(!ctx.accounts.old_participation.withdrawn, ErrorCode::AlreadyWithdrawn);
+        ctx.accounts.old_participation.check_not_frozen()?;
+
+        let old_run = &ctx.accounts.old_run;
+        require!(old_run.status == RunStatus::Settled, ErrorCode::RunNotSettled);
+        old_run.check_claim_open(now)?;
+        old_run.check_solvency(ctx.accounts.old_run_vault.amount)?;
+        let bonuses_enabled = ctx.accounts.platform.is_feature_enabled(FEATURE_BONUSES);
+        let stake = Stake::read_optional(&ctx.accounts.stake)?;
+        let vote_boost_bps = Stake::vote_boost_bps(stake.as_ref(), ctx.accounts.platform.ve_boost_weight, now);
+        let share = old_run.settled_share(&ctx.accounts.old_participation, ctx.accounts.old_run_vault.amount, bonuses_enabled, vote_boost_bps)?;
+        let (migrated, vesting_profit) = old_run.split_vesting(&ctx.accounts.old_participation, share);
+
+        // The new run admits the rolled share like any other deposit, but KYC and invites
+        // need the user present
+        let new_run = &ctx.accounts.new_run;
+        require!(!new_run.requires_kyc && !new_run.is_private, ErrorCode::InvalidMigrationRun);
+        require!(new_run.waitlist_head == new_run.waitlist_tail, ErrorCode::WaitlistNotEmpty);
+        new_run.check_deposit(migrated, now)?;
+
+        let platform_key = ctx.accounts.platform.key();
+        let old_run_id_bytes = old_run_id.to_le_bytes();
+        let run_seeds = &[
+            b"run".as_ref(),
+            platform_key.as_ref(),
+            old_run_id_bytes.as_ref(),
+            &[old_run.bump],
+        ];
+        let signer = &[&run_seeds[..]];
+        write_memo(
+            &ctx.accounts.memo_program,
+            &format!("instinct:rollover run_id={} participation={}", new_run_id, ctx.accounts.new_participation.key()),
+        )?;
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.old_run_vault.to_account_info(),
+            to: ctx.accounts.new_run_vault.to_account_info(),
+            authority: ctx.accounts.old_run.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, migrated)?;
+
+        ctx.accounts.old_run.record_payout(&mut ctx.accounts.old_participation, share, vesting_profit)?;
+        let platform = &mut ctx.accounts.platform;
+        platform.total_value_locked = platform.total_value_locked
+            .saturating_sub(ctx.accounts.old_participation.deposit_amount);
+        platform.record_withdrawal(migrated)?;
+        platform.record_deposit(migrated)?;
+
+        let user = ctx.accounts.old_participation.user;
+        ctx.accounts.new_participation.record_deposit(user, new_run_id, migrated, ctx.bumps.new_participation);
+        let new_run = &mut ctx.accounts.new_run;
+        ctx.accounts.platform.admit_participant(
+            new_run,
+            &ctx.accounts.participant_page,
+            user,
+            ctx.bumps.participant_page,
+            migrated,
+            now,
+        )?;
+
+        msg!("Migrated {} for {} from run #{} into run #{}",
+            new_run.ui(migrated), user, old_run_id, new_run_id);
+        Ok(())
+    }
+
+    /// After the claim deadline, move everything left in the run vault - unclaimed shares and
+    /// unclaimed vesting profit - to the treasury (admin only)
+    pub fn sweep_unclaimed(
+        ctx: Context<SweepUnclaimed>,
+        run_id: u64,
+    ) -> Result<()> {
+        let run = &ctx.accounts.run;
+        require!(run.status == RunStatus::Settled, ErrorCode::RunNotSettled);
+        let deadline = run.claim_deadline().ok_or(ErrorCode::ClaimWindowOpen)?;
+        require!(Clock::get()?.unix_timestamp > deadline, ErrorCode::ClaimWindowOpen);
+
+        let amount = ctx.accounts.run_vault.amount;
+        let platform_key = run.platform;
+        let run_id_bytes = run.run_id.to_le_bytes();
+        let run_seeds = &[
+            b"run".as_ref(),
+            platform_key.as_ref(),
+            run_id_bytes.as_ref(),
+            &[run.bump],
+        ];
+        let signer = &[&run_seeds[..]];
+
+        write_memo(
+            &ctx.accounts.memo_program,
+            &format!("instinct:sweep_unclaimed run_id={}", run_id),
+        )?;
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.run_vault.to_account_info(),
+            to: ctx.accounts.platform_fee_vault.to_account_info(),
+            authority: ctx.accounts.run.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, amount)?;
+
+        let run = &mut ctx.accounts.run;
+        run.vesting_outstanding = 0;
+        run.total_paid_out = run.total_paid_out
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        msg!("Swept {} USDC unclaimed from run #{} to the treasury ({}/{} withdrawn)",
+            run.ui(amount), run_id, run.withdrawn_count, run.participant_count);
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::SweepUnclaimed, ctx.accounts.authority.key(), &run_id)?;
+        Ok(())
+    }
+
+    /// Give part of your settled share to another participant of the run who has not withdrawn,
+    /// e.g. to reward a top caller in the voting game; both participations record the tip
+    pub fn tip(
+        ctx: Context<Tip>,
+        run_id: u64,
+        recipient: Pubkey,
+        amount: u64,
+    ) -> Result<()> {
+        let run = &ctx.accounts.run;
+        let sender = &ctx.accounts.sender_participation;
+        require!(recipient != ctx.accounts.tipper.key(), ErrorCode::CannotTipSelf);
+        require!(amount > 0, ErrorCode::TipExceedsShare);
+        require!(run.status != RunStatus::Disputed, ErrorCode::SettlementDisputed);
+        require!(run.status == RunStatus::Settled, ErrorCode::RunNotSettled);
+        require!(!sender.withdrawn, ErrorCode::AlreadyWithdrawn);
+        require!(!ctx.accounts.recipient_participation.withdrawn, ErrorCode::AlreadyWithdrawn);
+        BlacklistEntry::check_not_listed(&ctx.accounts.blacklist_entry)?;
+        sender.check_not_frozen()?;
+        run.check_solvency(ctx.accounts.run_vault.amount)?;
+        run.check_claim_open(Clock::get()?.unix_timestamp)?;
+
+        // Without the stake's ve boost, which only ever adds to the share withdrawn later
+        let tippable = run.settled_share(
+            sender,
+            ctx.accounts.run_vault.amount,
+            ctx.accounts.platform.is_feature_enabled(FEATURE_BONUSES),
+            0,
+        )?;
+        require!(amount <= tippable, ErrorCode::TipExceedsShare);
+
+        let sender = &mut ctx.accounts.sender_participation;
+        sender.tips_sent = sender.tips_sent
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        let receiver = &mut ctx.accounts.recipient_participation;
+        receiver.tips_received = receiver.tips_received
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        msg!("{} tipped {} USDC of their share in run #{} to {}",
+            ctx.accounts.tipper.key(), ctx.accounts.run.ui(amount), run_id, recipient);
+        Ok(())
+    }
+
+    /// Claim the portion of withdrawn profits that has vested since settlement
+    pub fn claim_vested(
+        ctx: Context<Withdraw>,
+        run_id: u64,
+    ) -> Result<()> {
+        let run = &ctx.accounts.run;
+        let participation = &ctx.accounts.user_participation;
+
+        require!(run.status != RunStatus::Disputed, ErrorCode::SettlementDisputed);
+        require!(run.status == RunStatus::Settled, ErrorCode::RunNotSettled);
+        require!(participation.withdrawn, ErrorCode::NothingVested);
+        BlacklistEntry::check_not_listed(&ctx.accounts.blacklist_entry)?;
+        participation.check_not_frozen()?;
+        run.check_solvency(ctx.accounts.run_vault.amount)?;
+        let now = Clock::get()?.unix_timestamp;
+        run.check_claim_open(now)?;
+
+        let elapsed = now
+            .saturating_sub(run.ended_at)
+            .clamp(0, run.profit_vesting_secs);
+        let vested = (participation.vesting_total as u128)
+            .checked_mul(elapsed as u128)
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+            .checked_div(run.profit_vesting_secs.max(1) as u128)
+            .ok_or(ErrorCode::ArithmeticOverflow)? as u64;
+        let claimable = vested.saturating_sub(participation.vesting_claimed);
+        require!(claimable > 0, ErrorCode::NothingVested);
+
+        let platform_key = run.platform;
+        let run_id_bytes = run.run_id.to_le_bytes();
+        let run_seeds = &[
+            b"run".as_ref(),
+            platform_key.as_ref(),
+            run_id_bytes.as_ref(),
+            &[run.bump],
+        ];
+        let signer = &[&run_seeds[..]];
+
+        write_memo(
+            &ctx.accounts.memo_program,
+            &format!("instinct:claim_vested run_id={} participation={}", run_id, ctx.accounts.user_participation.key()),
+        )?;
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.run_vault.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: ctx.accounts.run.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, claimable)?;
+        ctx.accounts.platform.record_withdrawal(claimable)?;
+
+        let participation = &mut ctx.accounts.user_participation;
+        participation.vesting_claimed = participation.vesting_claimed
+            .checked_add(claimable)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        let run = &mut ctx.accounts.run;
+        run.vesting_outstanding = run.vesting_outstanding
+            .checked_sub(claimable)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        run.total_withdrawn = run.total_withdrawn
+            .checked_add(claimable)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        run.total_paid_out = run.total_paid_out
+            .checked_add(claimable)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        msg!(
+            "User {} claimed {} vested USDC from run #{} ({}/{} vested)",
+            ctx.accounts.user.key(),
+            run.ui(claimable),
+            run_id,
+            participation.vesting_claimed,
+            participation.vesting_total
+        );
+        Ok(())
+    }
+
+    /// Dispute a settlement during its dispute window (participants only)
+    pub fn dispute_settlement(
+        ctx: Context<DisputeSettlement>,
+        run_id: u64,
+    ) -> Result<()> {
+        let run = &mut ctx.accounts.run;
+
+        require!(run.status != RunStatus::Disputed, ErrorCode::SettlementDisputed);
+        require!(run.status == RunStatus::Settled, ErrorCode::RunNotSettled);
+        require!(!run.bond_resolved, ErrorCode::BondAlreadyResolved);
+        require!(
+            Clock::get()?.unix_timestamp <= run.dispute_deadline,
+            ErrorCode::DisputeWindowClosed
+        );
+
+        run.transition_to(RunStatus::Disputed)?;
+
+        msg!("Settlement of run #{} disputed by {}", run_id, ctx.accounts.user.key());
+        Ok(())
+    }
+
+    /// Resolve an open dispute (admin only). Upholding it re-settles the misreported participants:
+    /// `corrections` carries their corrected vote records, with a (participation, user's associated
+    /// token account) pair per correction in remaining accounts, and the bond is slashed to them
+    /// pro-rata to their deposits. A rejected dispute returns the bond to the settler
+    pub fn resolve_dispute<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ResolveDispute<'info>>,
+        run_id: u64,
+        upheld: bool,
+        corrections: Vec<VoteCorrection>,
+    ) -> Result<()> {
+        require!(ctx.accounts.run.status == RunStatus::Disputed, ErrorCode::NoActiveDispute);
+        require!(!ctx.accounts.run.bond_resolved, ErrorCode::BondAlreadyResolved);
+        require!(upheld != corrections.is_empty(), ErrorCode::InvalidDisputeCorrections);
+        require!(ctx.remaining_accounts.len() == corrections.len() * 2, ErrorCode::InvalidDisputeCorrections);
+
+        let bond = ctx.accounts.run.settlement_bond;
+        let run_bump = ctx.accounts.run.bump;
+        let platform_key = ctx.accounts.run.platform;
+        let run_id_bytes = ctx.accounts.run.run_id.to_le_bytes();
+        let run_seeds = &[
+            b"run".as_ref(),
+            platform_key.as_ref(),
+            run_id_bytes.as_ref(),
+            &[run_bump],
+        ];
+        let signer = &[&run_seeds[..]];
+
+        if upheld {
+            // Withdrawals wait for the dispute, so every share is recomputed from the corrected votes
+            let mut affected_deposits: u64 = 0;
+            for (correction, pair) in corrections.iter().zip(ctx.remaining_accounts.chunks(2)) {
+                let mut participation: Account<UserParticipation> = Account::try_from(&pair[0])?;
+                let expected = Pubkey::create_program_address(
+                    &[
+                        b"participation",
+                        platform_key.as_ref(),
+                        run_id_bytes.as_ref(),
+                        correction.user.as_ref(),
+                        &[participation.bump],
+                    ],
+                    &crate::ID,
+                )
+                .map_err(|_| ErrorCode::InvalidDisputeCorrections)?;
+                require_keys_eq!(pair[0].key(), expected, ErrorCode::InvalidDisputeCorrections);
+                require_keys_eq!(
+                    pair[1].key(),
+                    get_associated_token_address(&correction.user, &ctx.accounts.run.mint),
+                    ErrorCode::InvalidDisputeCorrections
+                );
+
+                let run = &mut ctx.accounts.run;
+                run.check_vote_stats(correction.correct_votes, correction.total_votes)?;
+                require!(
+                    correction.total_votes == 0 || run.is_vote_eligible(participation.deposit_amount),
+                    ErrorCode::BelowMinVoteDeposit
+                );
+                run.record_vote_stats(&participation, correction.correct_votes, correction.total_votes)?;
+                participation.correct_votes = correction.correct_votes;
+                participation.total_votes = correction.total_votes;
+                participation.exit(&crate::ID)?;
+                affected_deposits = affected_deposits
+                    .checked_add(participation.deposit_amount)
+                    .ok_or(ErrorCode::ArithmeticOverflow)?;
+            }
+
+            // The last affected participant takes the rounding remainder of the bond
+            let mut bond_left = bond;
+            for (index, pair) in ctx.remaining_accounts.chunks(2).enumerate() {
+                let participation: Account<UserParticipation> = Account::try_from(&pair[0])?;
+                let award = if index + 1 == corrections.len() {
+                    bond_left
+                } else {
+                    (bond as u128)
+                        .checked_mul(participation.deposit_amount as u128)
+                        .ok_or(ErrorCode::ArithmeticOverflow)?
+                        .checked_div(affected_deposits.max(1) as u128)
+                        .ok_or(ErrorCode::ArithmeticOverflow)? as u64
+                };
+                if award > 0 {
+                    let cpi_accounts = Transfer {
+                        from: ctx.accounts.bond_vault.to_account_info(),
+                        to: pair[1].clone(),
+                        authority: ctx.accounts.run.to_account_info(),
+                    };
+                    let cpi_program = ctx.accounts.token_program.to_account_info();
+                    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+                    token::transfer(cpi_ctx, award)?;
+                }
+                bond_left -= award;
+            }
+        } else if bond > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.bond_vault.to_account_info(),
+                to: ctx.accounts.settler_token_account.to_account_info(),
+                authority: ctx.accounts.run.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+            token::transfer(cpi_ctx, bond)?;
+        }
+
+        let run = &mut ctx.accounts.run;
+        if upheld {
+            run.agent_at_fault = run.agent != Pubkey::default();
+        }
+        run.transition_to(RunStatus::Settled)?;
+        run.bond_resolved = true;
+
+        msg!(
+            "Dispute on run #{} resolved - upheld: {} bond: {} participants re-settled: {}",
+            run_id,
+            upheld,
+            bond,
+            corrections.len()
+        );
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::ResolveDispute, ctx.accounts.authority.key(), &(run_id, upheld, corrections))?;
+        Ok(())
+    }
+
+    /// Return the settlement bond once the dispute window has passed undisputed
+    pub fn release_settlement_bond(
+        ctx: Context<ReleaseSettlementBond>,
+        run_id: u64,
+    ) -> Result<()> {
+        let run = &ctx.accounts.run;
+
+        require!(run.status != RunStatus::Disputed, ErrorCode::SettlementDisputed);
+        require!(run.status == RunStatus::Settled, ErrorCode::RunNotSettled);
+        require!(!run.bond_resolved, ErrorCode::BondAlreadyResolved);
+        require!(
+            Clock::get()?.unix_timestamp > run.dispute_deadline,
+            ErrorCode::DisputeWindowOpen
+        );
+
+        let bond = run.settlement_bond;
+        if bond > 0 {
+            let platform_key = run.platform;
+            let run_id_bytes = run.run_id.to_le_bytes();
+            let run_seeds = &[
+                b"run".as_ref(),
+                platform_key.as_ref(),
+                run_id_bytes.as_ref(),
+                &[run.bump],
+            ];
+            let signer = &[&run_seeds[..]];
+
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.bond_vault.to_account_info(),
+                to: ctx.accounts.settler_token_account.to_account_info(),
+                authority: ctx.accounts.run.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+            token::transfer(cpi_ctx, bond)?;
+        }
+
+        ctx.accounts.run.bond_resolved = true;
+
+        msg!("Settlement bond of {} released for run #{}", bond, run_id);
+        Ok(())
+    }
+
+    /// Update user's vote statistics (called by backend after each voting round)
+    pub fn update_vote_stats(
+        ctx: Context<UpdateVoteStats>,
+        _run_id: u64,
+        _user_pubkey: Pubkey,
+        correct_votes: u8,
+        total_votes: u8,
+    ) -> Result<()> {
+        let participation = &mut ctx.accounts.user_participation;
+        
+        ctx.accounts.platform.require_feature(FEATURE_VOTING)?;
+        require!(ctx.accounts.run.status == RunStatus::Active, ErrorCode::InvalidRunStatus);
+        require!(!ctx.accounts.run.is_paused, ErrorCode::RunPaused);
+        
+        ctx.accounts.run.check_vote_stats(correct_votes, total_votes)?;
+        // Dust deposits keep their pro-rata share but cannot earn the prediction bonus
+        require!(
+            total_votes == 0 || ctx.accounts.run.is_vote_eligible(participation.deposit_amount),
+            ErrorCode::BelowMinVoteDeposit
+        );
+        ctx.accounts.run.record_vote_stats(participation, correct_votes, total_votes)?;
+        participation.correct_votes = correct_votes;
+        participation.total_votes = total_votes;
+
+        Ok(())
+    }
+
+    /// Let another participant of the run vote with your snapshotted weight; your own votes stop
+    /// counting until you undelegate. Delegates cannot delegate onward
+    pub fn delegate_votes(
+        ctx: Context<DelegateVotes>,
+        run_id: u64,
+        delegate: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.platform.require_feature(FEATURE_VOTING)?;
+        require!(ctx.accounts.run.status == RunStatus::Active, ErrorCode::InvalidRunStatus);
+        ctx.accounts.run.check_no_open_round(Clock::get()?.unix_timestamp)?;
+        require_keys_neq!(delegate, ctx.accounts.user.key(), ErrorCode::CannotDelegateToSelf);
+
+        let participation = &mut ctx.accounts.user_participation;
+        let delegate_participation = &mut ctx.accounts.delegate_participation;
+        require!(participation.vote_weight > 0, ErrorCode::VoteWeightNotRecorded);
+        require!(participation.vote_delegate == Pubkey::default(), ErrorCode::VotesAlreadyDelegated);
+        require!(
+            participation.delegated_weight == 0 && delegate_participation.vote_delegate == Pubkey::default(),
+            ErrorCode::DelegationChain
+        );
+
+        participation.vote_delegate = delegate;
+        delegate_participation.delegated_weight = delegate_participation.delegated_weight
+            .checked_add(participation.vote_weight)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        msg!("{} delegated {} vote weight in run #{} to {}",
+            participation.user, participation.vote_weight, run_id, delegate);
+        Ok(())
+    }
+
+    /// Take back vote weight delegated with delegate_votes
+    pub fn undelegate_votes(
+        ctx: Context<UndelegateVotes>,
+        run_id: u64,
+    ) -> Result<()> {
+        ctx.accounts.run.check_no_open_round(Clock::get()?.unix_timestamp)?;
+        let participation = &mut ctx.accounts.user_participation;
+        let delegate_participation = &mut ctx.accounts.delegate_participation;
+        delegate_participation.delegated_weight = delegate_participation.delegated_weight
+            .saturating_sub(participation.vote_weight);
+        participation.vote_delegate = Pubkey::default();
+
+        msg!("{} took back their vote weight in run #{} from {}",
+            participation.user, run_id, delegate_participation.user);
+        Ok(())
+    }
+
+    /// Post the trade a vote round will decide on. Votes commit to the proposal hash, so they are
+    /// bound to this exact trade rather than an off-chain description (platform authority or manager)
+    pub fn post_trade_proposal(
+        ctx: Context<PostTradeProposal>,
+        run_id: u64,
+        round: u8,
+        proposal_hash: [u8; 32],
+        details_uri: String,
+    ) -> Result<()> {
+        let run = &ctx.accounts.run;
+        require!(run.status == RunStatus::Active, ErrorCode::InvalidRunStatus);
+        require!(round < run.max_vote_rounds, ErrorCode::InvalidVoteRound);
+        require!(details_uri.len() <= MAX_DETAILS_URI_LEN, ErrorCode::DetailsUriTooLong);
+
+        let proposal = &mut ctx.accounts.trade_proposal;
+        proposal.run_id = run_id;
+        proposal.round = round;
+        proposal.proposer = ctx.accounts.authority.key();
+        proposal.proposal_hash = proposal_hash;
+        proposal.details_uri = details_uri;
+        proposal.posted_at = Clock::get()?.unix_timestamp;
+        proposal.bump = ctx.bumps.trade_proposal;
+
+        msg!("Trade proposal for run #{} round {} posted by {}", run_id, round, proposal.proposer);
+        Ok(())
+    }
+
+    /// Open a vote round on its posted trade proposal and fund its reward pool from the operator's token account. Correct
+    /// voters split the pool by weight, so the smaller the winning side the larger each share
+    /// (platform authority or manager)
+    pub fn open_vote_round(
+        ctx: Context<OpenVoteRound>,
+        run_id: u64,
+        round: u8,
+        option_count: u8,
+        voting_ends_at: i64,
+        reward_pool: u64,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        ctx.accounts.platform.require_feature(FEATURE_VOTING)?;
+        let run = &mut ctx.accounts.run;
+        require!(run.status == RunStatus::Active, ErrorCode::InvalidRunStatus);
+        require!(round < run.max_vote_rounds, ErrorCode::InvalidVoteRound);
+        require!(
+            (2..=MAX_VOTE_OPTIONS as u8).contains(&option_count),
+            ErrorCode::InvalidVoteOption
+        );
+        require!(voting_ends_at > now, ErrorCode::InvalidDeadline);
+        run.check_no_open_round(now)?;
+        run.voting_open_until = voting_ends_at;
+
+        if reward_pool > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.authority_token_account.to_account_info(),
+                to: ctx.accounts.rewards_vault.to_account_info(),
+                authority: ctx.accounts.authority.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+            token::transfer(cpi_ctx, reward_pool)?;
+        }
+
+        let vote_round = &mut ctx.accounts.vote_round;
+        vote_round.run_id = run_id;
+        vote_round.round = round;
+        vote_round.proposal_hash = ctx.accounts.trade_proposal.proposal_hash;
+        vote_round.option_count = option_count;
+        vote_round.option_weights = [0; MAX_VOTE_OPTIONS];
+        vote_round.total_weight = 0;
+        vote_round.voting_ends_at = voting_ends_at;
+        vote_round.reward_pool = reward_pool;
+        vote_round.rewards_paid = 0;
+        vote_round.resolved = false;
+        vote_round.winning_option = 0;
+        vote_round.decision = 0;
+        vote_round.decision_finalized = false;
+        vote_round.capital_allocated = false;
+        vote_round.bump = ctx.bumps.vote_round;
+
+        msg!("Run #{} round {} open until {} with {} options and {} in rewards",
+            run_id, round, voting_ends_at, option_count, run.ui(reward_pool));
+        Ok(())
+    }
+
+    /// Vote in an open round with your snapshotted weight plus any weight delegated to you.
+    /// `proposal_hash` must match the round's trade proposal
+    pub fn cast_vote(
+        ctx: Context<CastVote>,
+        run_id: u64,
+        round: u8,
+        option: u8,
+        proposal_hash: [u8; 32],
+    ) -> Result<()> {
+        ctx.accounts.platform.require_feature(FEATURE_VOTING)?;
+        require!(ctx.accounts.run.status == RunStatus::Active, ErrorCode::InvalidRunStatus);
+        require!(!ctx.accounts.run.is_paused, ErrorCode::RunPaused);
+        BlacklistEntry::check_not_listed(&ctx.accounts.blacklist_entry)?;
+        ctx.accounts.user_participation.check_not_frozen()?;
+
+        let vote_round = &mut ctx.accounts.vote_round;
+        require!(vote_round.proposal_hash == proposal_hash, ErrorCode::ProposalMismatch);
+        let participation = &ctx.accounts.user_participation;
+        require!(Clock::get()?.unix_timestamp < vote_round.voting_ends_at, ErrorCode::VotingClosed);
+        require!(option < vote_round.option_count, ErrorCode::InvalidVoteOption);
+        require!(
+            ctx.accounts.run.is_vote_eligible(participation.deposit_amount),
+            ErrorCode::BelowMinVoteDeposit
+        );
+        require!(participation.vote_weight > 0, ErrorCode::VoteWeightNotRecorded);
+        require!(participation.vote_delegate == Pubkey::default(), ErrorCode::VotesAlreadyDelegated);
+
+        let weight = participation.vote_weight
+            .checked_add(participation.delegated_weight)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        vote_round.record_vote(option, weight)?;
+
+        let receipt = &mut ctx.accounts.vote_receipt;
+        receipt.user = ctx.accounts.user.key();
+        receipt.option = option;
+        receipt.weight = weight;
+        receipt.rewarded = false;
+        receipt.bump = ctx.bumps.vote_receipt;
+
+        msg!("{} voted {} with weight {} in run #{} round {}",
+            receipt.user, option, weight, run_id, round);
+        Ok(())
+    }
+
+    /// Tally a closed round's on-chain votes into the community decision, the option with the most
+    /// weight (ties go to the lower option). record_trade only accepts the round's trades for this
+    /// option, so the crowd's call can be checked against the chain (anyone)
+    pub fn finalize_round_decision(
+        ctx: Context<FinalizeRoundDecision>,
+        run_id: u64,
+        round: u8,
+    ) -> Result<()> {
+        let vote_round = &mut ctx.accounts.vote_round;
+        require!(!vote_round.decision_finalized, ErrorCode::DecisionAlreadyFinalized);
+        require!(Clock::get()?.unix_timestamp >= vote_round.voting_ends_at, ErrorCode::VotingOpen);
+        require!(vote_round.total_weight > 0, ErrorCode::NoVotesCast);
+        let decision = vote_round.majority_option();
+        vote_round.decision = decision;
+        vote_round.decision_finalized = true;
+
+        emit_cpi!(RoundDecisionFinalized {
+            run_id,
+            round,
+            decision,
+            decision_weight: vote_round.option_weights[decision as usize],
+            total_weight: vote_round.total_weight,
+        });
+        msg!("Run #{} round {} community decision: option {}", run_id, round, decision);
+        KeeperIncentive::reward(&mut ctx.accounts.keeper_incentive, &ctx.accounts.caller.to_account_info())?;
+        Ok(())
+    }
+
+    /// Record the correct option of a closed round; if nobody picked it, the reward pool goes back
+    /// to the operator (platform authority or manager)
+    pub fn resolve_vote_round(
+        ctx: Context<ResolveVoteRound>,
+        run_id: u64,
+        round: u8,
+        winning_option: u8,
+    ) -> Result<()> {
+        let vote_round = &mut ctx.accounts.vote_round;
+        require!(!vote_round.resolved, ErrorCode::VoteRoundResolved);
+        require!(Clock::get()?.unix_timestamp >= vote_round.voting_ends_at, ErrorCode::VotingOpen);
+        require!(winning_option < vote_round.option_count, ErrorCode::InvalidVoteOption);
+        vote_round.resolved = true;
+        vote_round.winning_option = winning_option;
+
+        let winning_weight = vote_round.option_weights[winning_option as usize];
+        let unclaimable = if winning_weight == 0 { vote_round.reward_pool } else { 0 };
+        if unclaimable > 0 {
+            vote_round.rewards_paid = unclaimable;
+            let run = &ctx.accounts.run;
+            let platform_key = run.platform;
+            let run_id_bytes = run.run_id.to_le_bytes();
+            let run_seeds = &[
+                b"run".as_ref(),
+                platform_key.as_ref(),
+                run_id_bytes.as_ref(),
+                &[run.bump],
+            ];
+            let signer = &[&run_seeds[..]];
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.rewards_vault.to_account_info(),
+                to: ctx.accounts.authority_token_account.to_account_info(),
+                authority: ctx.accounts.run.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+            token::transfer(cpi_ctx, unclaimable)?;
+        }
+
+        msg!("Run #{} round {} resolved to option {} ({} of {} weight correct)",
+            run_id, round, winning_option, winning_weight, vote_round.total_weight);
+        Ok(())
+    }
+
+    /// Claim your parimutuel share of a resolved round's reward pool for a correct vote
+    pub fn claim_round_reward(
+        ctx: Context<ClaimRoundReward>,
+        run_id: u64,
+        round: u8,
+    ) -> Result<()> {
+        BlacklistEntry::check_not_listed(&ctx.accounts.blacklist_entry)?;
+        let vote_round = &mut ctx.accounts.vote_round;
+        let receipt = &mut ctx.accounts.vote_receipt;
+        require!(vote_round.resolved, ErrorCode::VoteRoundNotResolved);
+        require!(receipt.option == vote_round.winning_option, ErrorCode::IncorrectVote);
+        require!(!receipt.rewarded, ErrorCode::AlreadyWithdrawn);
+
+        let reward = vote_round.reward_for(receipt.weight)?;
+        receipt.rewarded = true;
+        vote_round.rewards_paid = vote_round.rewards_paid
+            .checked_add(reward)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        let run = &ctx.accounts.run;
+        let platform_key = run.platform;
+        let run_id_bytes = run.run_id.to_le_bytes();
+        let run_seeds = &[
+            b"run".as_ref(),
+            platform_key.as_ref(),
+            run_id_bytes.as_ref(),
+            &[run.bump],
+        ];
+        let signer = &[&run_seeds[..]];
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.rewards_vault.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: ctx.accounts.run.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, reward)?;
+
+        msg!("{} claimed {} for a correct vote in run #{} round {}",
+            receipt.user, ctx.accounts.run.ui(reward), run_id, round);
+        Ok(())
+    }
+
+    /// Pay a resolved round's rewards to correct voters who have not claimed (platform authority or
+    /// manager). Remaining accounts are (vote receipt, voter's associated token account) pairs;
+    /// incorrect or already-rewarded receipts are skipped, and blacklisted wallets must be left
+    /// out by the caller
+    pub fn distribute_round_rewards<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DistributeRoundRewards<'info>>,
+        run_id: u64,
+        round: u8,
+    ) -> Result<()> {
+        require!(ctx.accounts.vote_round.resolved, ErrorCode::VoteRoundNotResolved);
+        require!(ctx.remaining_accounts.len().is_multiple_of(2), ErrorCode::InvalidPayoutAccounts);
+
+        let platform_key = ctx.accounts.platform.key();
+        let vote_round_key = ctx.accounts.vote_round.key();
+        let run_id_bytes = run_id.to_le_bytes();
+        let run_bump = ctx.accounts.run.bump;
+        let run_seeds = &[
+            b"run".as_ref(),
+            platform_key.as_ref(),
+            run_id_bytes.as_ref(),
+            &[run_bump],
+        ];
+        let signer = &[&run_seeds[..]];
+
+        let mut paid_count: u32 = 0;
+        let mut paid_total: u64 = 0;
+        for pair in ctx.remaining_accounts.chunks(2) {
+            let (receipt_info, token_info) = (&pair[0], &pair[1]);
+            let mut receipt: Account<VoteReceipt> = Account::try_from(receipt_info)?;
+            let expected = Pubkey::create_program_address(
+                &[
+                    b"vote",
+                    vote_round_key.as_ref(),
+                    receipt.user.as_ref(),
+                    &[receipt.bump],
+                ],
+                &crate::ID,
+            )
+            .map_err(|_| ErrorCode::InvalidPayoutAccounts)?;
+            require_keys_eq!(receipt_info.key(), expected, ErrorCode::InvalidPayoutAccounts);
+            if receipt.rewarded || receipt.option != ctx.accounts.vote_round.winning_option {
+                continue;
+            }
+            require_keys_eq!(
+                token_info.key(),
+                get_associated_token_address(&receipt.user, &ctx.accounts.run.mint),
+                ErrorCode::InvalidPayoutAccounts
+            );
+
+            let vote_round = &mut ctx.accounts.vote_round;
+            let reward = vote_round.reward_for(receipt.weight)?;
+            receipt.rewarded = true;
+            vote_round.rewards_paid = vote_round.rewards_paid
+                .checked_add(reward)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.rewards_vault.to_account_info(),
+                to: token_info.clone(),
+                authority: ctx.accounts.run.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+            token::transfer(cpi_ctx, reward)?;
+
+            receipt.exit(&crate::ID)?;
+            paid_total = paid_total.saturating_add(reward);
+            paid_count += 1;
+        }
+
+        msg!("Distributed {} to {} correct voters in run #{} round {}",
+            ctx.accounts.run.ui(paid_total), paid_count, run_id, round);
+        Ok(())
+    }
+
+    /// Block a wallet from depositing or withdrawing across all runs (admin only)
+    pub fn add_to_blacklist(
+        ctx: Context<AddToBlacklist>,
+        wallet: Pubkey,
+    ) -> Result<()> {
+        let entry = &mut ctx.accounts.blacklist_entry;
+        entry.wallet = wallet;
+        entry.added_at = Clock::get()?.unix_timestamp;
+        entry.bump = ctx.bumps.blacklist_entry;
+
+        msg!("Wallet {} blacklisted", wallet);
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::AddToBlacklist, ctx.accounts.authority.key(), &wallet)?;
+        Ok(())
+    }
+
+    /// Lift a wallet's blacklisting (admin only)
+    pub fn remove_from_blacklist(
+        ctx: Context<RemoveFromBlacklist>,
+        wallet: Pubkey,
+    ) -> Result<()> {
+        msg!("Wallet {} removed from blacklist", wallet);
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::RemoveFromBlacklist, ctx.accounts.authority.key(), &wallet)?;
+        Ok(())
+    }
+
+    /// Hold one participation's funds, e.g. while it is disputed, without pausing the run (admin only)
+    pub fn freeze_participation(
+        ctx: Context<SetParticipationFrozen>,
+        run_id: u64,
+        user: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.user_participation.frozen = true;
+
+        msg!("Participation of {} in run #{} frozen", user, run_id);
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::FreezeParticipation, ctx.accounts.authority.key(), &(run_id, user))?;
+        Ok(())
+    }
+
+    /// Release a frozen participation (admin only)
+    pub fn unfreeze_participation(
+        ctx: Context<SetParticipationFrozen>,
+        run_id: u64,
+        user: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.user_participation.frozen = false;
+
+        msg!("Participation of {} in run #{} unfrozen", user, run_id);
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::UnfreezeParticipation, ctx.accounts.authority.key(), &(run_id, user))?;
+        Ok(())
+    }
+
+    /// Settle a wallet's future runs without the performance fee (admin only)
+    pub fn add_fee_exemption(
+        ctx: Context<AddFeeExemption>,
+        wallet: Pubkey,
+    ) -> Result<()> {
+        let exemption = &mut ctx.accounts.fee_exempt;
+        exemption.wallet = wallet;
+        exemption.added_at = Clock::get()?.unix_timestamp;
+        exemption.bump = ctx.bumps.fee_exempt;
+
+        msg!("Wallet {} exempted from platform fees", wallet);
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::AddFeeExemption, ctx.accounts.authority.key(), &wallet)?;
+        Ok(())
+    }
+
+    /// Remove a wallet's fee exemption; runs already settled keep their snapshot (admin only)
+    pub fn remove_fee_exemption(
+        ctx: Context<RemoveFeeExemption>,
+        wallet: Pubkey,
+    ) -> Result<()> {
+        msg!("Wallet {} fee exemption removed", wallet);
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::RemoveFeeExemption, ctx.accounts.authority.key(), &wallet)?;
+        Ok(())
+    }
+
+    /// Set the authority allowed to issue KYC attestations (admin only)
+    pub fn set_compliance_authority(
+        ctx: Context<AdminAction>,
+        compliance_authority: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.platform.compliance_authority = compliance_authority;
+        msg!("Compliance authority set to {}", compliance_authority);
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::SetComplianceAuthority, ctx.accounts.authority.key(), &compliance_authority)?;
+        Ok(())
+    }
+
+    /// Issue or renew a wallet's KYC attestation (compliance authority only)
+    pub fn issue_kyc_attestation(
+        ctx: Context<IssueKycAttestation>,
+        user: Pubkey,
+        expires_at: i64,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(expires_at > now, ErrorCode::InvalidDeadline);
+
+        let attestation = &mut ctx.accounts.kyc_attestation;
+        attestation.user = user;
+        attestation.issuer = ctx.accounts.compliance_authority.key();
+        attestation.issued_at = now;
+        attestation.expires_at = expires_at;
+        attestation.revoked = false;
+        attestation.bump = ctx.bumps.kyc_attestation;
+
+        msg!("KYC attestation issued for {} until {}", user, expires_at);
+        Ok(())
+    }
+
+    /// Revoke a wallet's KYC attestation (compliance authority only)
+    pub fn revoke_kyc_attestation(
+        ctx: Context<RevokeKycAttestation>,
+        user: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.kyc_attestation.revoked = true;
+        msg!("KYC attestation revoked for {}", user);
+        Ok(())
+    }
+
+    /// Set up governance token staking (admin only, one-time)
+    pub fn initialize_staking(
+        ctx: Context<InitializeStaking>,
+        min_priority_stake: u64,
+    ) -> Result<()> {
+        let platform = &mut ctx.accounts.platform;
+        require!(platform.stake_mint == Pubkey::default(), ErrorCode::StakingAlreadyInitialized);
+
+        platform.stake_mint = ctx.accounts.stake_mint.key();
+        platform.min_priority_stake = min_priority_stake;
+
+        msg!("Staking initialized for mint {} - priority access from {} staked",
+            platform.stake_mint, min_priority_stake);
+        Ok(())
+    }
+
+    /// Set the stake required for priority deposit windows (admin only)
+    pub fn set_min_priority_stake(
+        ctx: Context<AdminAction>,
+        min_priority_stake: u64,
+    ) -> Result<()> {
+        ctx.accounts.platform.min_priority_stake = min_priority_stake;
+        msg!("Minimum priority stake set to {}", min_priority_stake);
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::SetMinPriorityStake, ctx.accounts.authority.key(), &min_priority_stake)?;
+        Ok(())
+    }
+
+    /// Stake platform governance tokens
+    pub fn stake(ctx: Context<StakeTokens>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidStakeAmount);
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.user_token_account.to_account_info(),
+            to: ctx.accounts.stake_vault.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+
+        let revenue_per_share = ctx.accounts.platform.revenue_per_share;
+        let stake = &mut ctx.accounts.stake;
+        stake.accrue_revenue(revenue_per_share)?;
+        stake.owner = ctx.accounts.user.key();
+        stake.amount = stake.amount
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        stake.bump = ctx.bumps.stake;
+        stake.checkpoint_revenue(revenue_per_share)?;
+
+        let platform = &mut ctx.accounts.platform;
+        platform.total_staked = platform.total_staked
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        msg!("User {} staked {} (total {})", stake.owner, amount, stake.amount);
+        Ok(())
+    }
+
+    /// Unstake platform governance tokens
+    pub fn unstake(ctx: Context<UnstakeTokens>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidStakeAmount);
+        require!(amount <= ctx.accounts.stake.amount, ErrorCode::InsufficientStake);
+        let unlocked = ctx.accounts.stake.amount
+            - ctx.accounts.stake.locked_balance(Clock::get()?.unix_timestamp);
+        require!(amount <= unlocked, ErrorCode::StakeLocked);
+
+        let platform_bump = ctx.accounts.platform.bump;
+        let platform_id_bytes = ctx.accounts.platform.platform_id.to_le_bytes();
+        let platform_seeds = &[
+            b"platform".as_ref(),
+            platform_id_bytes.as_ref(),
+            &[platform_bump],
+        ];
+        let signer = &[&platform_seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.stake_vault.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: ctx.accounts.platform.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, amount)?;
+
+        let revenue_per_share = ctx.accounts.platform.revenue_per_share;
+        let stake = &mut ctx.accounts.stake;
+        stake.accrue_revenue(revenue_per_share)?;
+        stake.amount = stake.amount
+            .checked_sub(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        // Tokens from an expired lock leave the lock with them, so they cannot be withdrawn again
+        stake.locked_amount = stake.locked_amount.min(stake.amount);
+        if stake.locked_amount == 0 {
+            stake.lock_end = 0;
+        }
+        stake.checkpoint_revenue(revenue_per_share)?;
+
+        let platform = &mut ctx.accounts.platform;
+        platform.total_staked = platform.total_staked
+            .checked_sub(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        msg!("User {} unstaked {} (remaining {})", stake.owner, amount, stake.amount);
+        Ok(())
+    }
+
+    /// Lock staked tokens (optionally adding more) until `lock_end` for ve_weight; locks can only be extended
+    pub fn extend_lock(
+        ctx: Context<StakeTokens>,
+        additional_amount: u64,
+        lock_end: i64,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let stake = &ctx.accounts.stake;
+
+        require!(lock_end > now, ErrorCode::InvalidLockEnd);
+        require!(lock_end >= stake.lock_end, ErrorCode::CannotShortenLock);
+        require!(lock_end - now <= MAX_LOCK_SECS, ErrorCode::LockTooLong);
+        // The whole stake joins the lock
+        let locked_amount = stake.amount
+            .checked_add(additional_amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        require!(locked_amount > 0, ErrorCode::InvalidStakeAmount);
+
+        if additional_amount > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.user_token_account.to_account_info(),
+                to: ctx.accounts.stake_vault.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+            token::transfer(cpi_ctx, additional_amount)?;
+        }
+
+        let revenue_per_share = ctx.accounts.platform.revenue_per_share;
+        let stake = &mut ctx.accounts.stake;
+        stake.accrue_revenue(revenue_per_share)?;
+        stake.owner = ctx.accounts.user.key();
+        stake.amount = locked_amount;
+        stake.bump = ctx.bumps.stake;
+        stake.locked_amount = locked_amount;
+        stake.lock_end = lock_end;
+        stake.checkpoint_revenue(revenue_per_share)?;
+
+        let platform = &mut ctx.accounts.platform;
+        platform.total_staked = platform.total_staked
+            .checked_add(additional_amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        msg!("User {} locked {} until {} (ve_weight {})",
+            stake.owner, locked_amount, lock_end, stake.ve_weight(now));
+        Ok(())
+    }
+
+    /// Withdraw the whole locked amount once its lock has expired
+    pub fn withdraw_expired_lock(ctx: Context<UnstakeTokens>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let amount = ctx.accounts.stake.locked_amount.min(ctx.accounts.stake.amount);
+        require!(amount > 0, ErrorCode::NoLockedStake);
+        require!(now >= ctx.accounts.stake.lock_end, ErrorCode::StakeLocked);
+
+        let platform_bump = ctx.accounts.platform.bump;
+        let platform_id_bytes = ctx.accounts.platform.platform_id.to_le_bytes();
+        let platform_seeds = &[
+            b"platform".as_ref(),
+            platform_id_bytes.as_ref(),
+            &[platform_bump],
+        ];
+        let signer = &[&platform_seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.stake_vault.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: ctx.accounts.platform.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, amount)?;
+
+        let revenue_per_share = ctx.accounts.platform.revenue_per_share;
+        let stake = &mut ctx.accounts.stake;
+        stake.accrue_revenue(revenue_per_share)?;
+        stake.amount = stake.amount
+            .checked_sub(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        stake.locked_amount = 0;
+        stake.lock_end = 0;
+        stake.checkpoint_revenue(revenue_per_share)?;
+
+        let platform = &mut ctx.accounts.platform;
+        platform.total_staked = platform.total_staked
+            .checked_sub(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        msg!("User {} withdrew expired lock of {} (remaining {})", stake.owner, amount, stake.amount);
+        Ok(())
+    }
+
+    /// Set the ve_weight that earns the full vote bonus boost; 0 turns boosts off (admin only)
+    pub fn set_ve_boost_weight(
+        ctx: Context<AdminAction>,
+        ve_boost_weight: u64,
+    ) -> Result<()> {
+        ctx.accounts.platform.ve_boost_weight = ve_boost_weight;
+        msg!("Full vote boost at ve_weight {}", ve_boost_weight);
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::SetVeBoostWeight, ctx.accounts.authority.key(), &ve_boost_weight)?;
+        Ok(())
+    }
+
+    /// Set the stake thresholds and fee discounts of each tier (admin only)
+    pub fn set_stake_tiers(
+        ctx: Context<AdminAction>,
+        stake_tiers: [StakeTier; STAKE_TIER_COUNT],
+    ) -> Result<()> {
+        for pair in stake_tiers.windows(2) {
+            require!(
+                pair[0].min_stake <= pair[1].min_stake && pair[0].discount_bps <= pair[1].discount_bps,
+                ErrorCode::InvalidStakeTiers
+            );
+        }
+        require!(
+            stake_tiers.iter().all(|tier| tier.discount_bps <= 10000),
+            ErrorCode::InvalidStakeTiers
+        );
+
+        ctx.accounts.platform.stake_tiers = stake_tiers;
+        for tier in stake_tiers.iter() {
+            msg!("Stake tier: {} staked -> {}% discount",
+                tier.min_stake, tier.discount_bps as f64 / 100.0);
+        }
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::SetStakeTiers, ctx.accounts.authority.key(), &stake_tiers)?;
+        Ok(())
+    }
+
+    /// Create the stakers' revenue vault and set their share of settlement fees (admin only)
+    pub fn initialize_revenue_share(
+        ctx: Context<InitializeRevenueShare>,
+        revenue_share_bps: u16,
+    ) -> Result<()> {
+        require!(revenue_share_bps <= 10000, ErrorCode::InvalidFee);
+
+        let platform = &mut ctx.accounts.platform;
+        require!(platform.revenue_vault == Pubkey::default(), ErrorCode::RevenueShareAlreadyInitialized);
+        platform.revenue_vault = ctx.accounts.revenue_vault.key();
+        platform.revenue_share_bps = revenue_share_bps;
+        platform.last_revenue_distribution = Clock::get()?.unix_timestamp;
+
+        msg!("Revenue sharing initialized - {}% of fees to stakers", revenue_share_bps as f64 / 100.0);
+        Ok(())
+    }
+
+    /// Change the stakers' share of settlement fees (admin only)
+    pub fn set_revenue_share_bps(
+        ctx: Context<AdminAction>,
+        revenue_share_bps: u16,
+    ) -> Result<()> {
+        require!(revenue_share_bps <= 10000, ErrorCode::InvalidFee);
+        let platform = &mut ctx.accounts.platform;
+        require!(platform.revenue_vault != Pubkey::default(), ErrorCode::RevenueShareNotInitialized);
+
+        platform.revenue_share_bps = revenue_share_bps;
+        msg!("Revenue share set to {}%", revenue_share_bps as f64 / 100.0);
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::SetRevenueShareBps, ctx.accounts.authority.key(), &revenue_share_bps)?;
+        Ok(())
+    }
+
+    /// Close the current epoch and credit its collected revenue to stakers pro-rata (anyone)
+    pub fn distribute_revenue(ctx: Context<DistributeRevenue>) -> Result<()> {
+        let platform = &mut ctx.accounts.platform;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(platform.revenue_vault != Pubkey::default(), ErrorCode::RevenueShareNotInitialized);
+        require!(
+            now >= platform.last_revenue_distribution.saturating_add(REVENUE_EPOCH_SECS),
+            ErrorCode::RevenueEpochNotOver
+        );
+        require!(
+            platform.pending_revenue > 0 && platform.total_staked > 0,
+            ErrorCode::NothingToDistribute
+        );
+
+        let increment = (platform.pending_revenue as u128)
+            .checked_mul(REVENUE_PRECISION)
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+            / platform.total_staked as u128;
+        // Rounding dust stays pending for the next epoch
+        let distributed = (increment * platform.total_staked as u128 / REVENUE_PRECISION) as u64;
+
+        platform.revenue_per_share = platform.revenue_per_share
+            .checked_add(increment)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        platform.pending_revenue -= distributed;
+        platform.revenue_epoch += 1;
+        platform.last_revenue_distribution = now;
+
+        msg!("Revenue epoch #{} distributed {} across {} staked",
+            platform.revenue_epoch, distributed, platform.total_staked);
+        if let Some(keeper) = &ctx.accounts.keeper {
+            KeeperIncentive::reward(&mut ctx.accounts.keeper_incentive, &keeper.to_account_info())?;
+        }
+        Ok(())
+    }
+
+    /// Claim revenue accrued on staked tokens
+    pub fn claim_revenue(ctx: Context<ClaimRevenue>) -> Result<()> {
+        let revenue_per_share = ctx.accounts.platform.revenue_per_share;
+        let stake = &mut ctx.accounts.stake;
+        stake.accrue_revenue(revenue_per_share)?;
+        let amount = stake.unclaimed_revenue;
+        require!(amount > 0, ErrorCode::NoRevenueToClaim);
+        stake.unclaimed_revenue = 0;
+
+        let platform_bump = ctx.accounts.platform.bump;
+        let platform_id_bytes = ctx.accounts.platform.platform_id.to_le_bytes();
+        let platform_seeds = &[
+            b"platform".as_ref(),
+            platform_id_bytes.as_ref(),
+            &[platform_bump],
+        ];
+        let signer = &[&platform_seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.revenue_vault.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: ctx.accounts.platform.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, amount)?;
+
+        msg!("User {} claimed {} USDC of staking revenue", ctx.accounts.user.key(), amount);
+        Ok(())
+    }
+
+    /// Emergency pause (admin or guardian)
+    pub fn pause_platform(ctx: Context<PausePlatform>) -> Result<()> {
+        ctx.accounts.platform.is_paused = true;
+        msg!("Platform paused by {}", ctx.accounts.pauser.key());
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::PausePlatform, ctx.accounts.pauser.key(), &())?;
+        Ok(())
+    }
+
+    /// Unpause platform (admin only)
+    pub fn unpause_platform(ctx: Context<AdminAction>) -> Result<()> {
+        ctx.accounts.platform.is_paused = false;
+        msg!("Platform unpaused by authority");
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::UnpausePlatform, ctx.accounts.authority.key(), &())?;
+        Ok(())
+    }
+
+    /// Give every authority key a lifetime of `rotation_secs`, starting now for the current one;
+    /// once it lapses, admin instructions fail until a successor is accepted. 0 turns expiry off
+    /// (admin only)
+    pub fn set_authority_rotation(ctx: Context<AdminAction>, rotation_secs: i64) -> Result<()> {
+        require!(rotation_secs >= 0, ErrorCode::InvalidDuration);
+        let now = Clock::get()?.unix_timestamp;
+        let platform = &mut ctx.accounts.platform;
+        platform.authority_rotation_secs = rotation_secs;
+        platform.authority_expires_at = if rotation_secs > 0 {
+            now.checked_add(rotation_secs).ok_or(ErrorCode::ArithmeticOverflow)?
+        } else {
+            0
+        };
+        msg!("Authority keys now expire after {}s; current key expires at {}", rotation_secs, platform.authority_expires_at);
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::SetAuthorityRotation, ctx.accounts.authority.key(), &rotation_secs)?;
+        Ok(())
+    }
+
+    /// Nominate the next authority; it takes over once it signs accept_authority. Works even after
+    /// the current key has expired, so the platform can always be rotated (admin only)
+    pub fn propose_authority(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+        let platform = &mut ctx.accounts.platform;
+        require_keys_neq!(new_authority, platform.authority, ErrorCode::AuthorityUnchanged);
+        platform.pending_authority = new_authority;
+        msg!("Authority {} proposed as successor to {}", new_authority, platform.authority);
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::ProposeAuthority, ctx.accounts.authority.key(), &new_authority)?;
+        Ok(())
+    }
+
+    /// Take over as platform authority, starting a fresh key lifetime (proposed authority)
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let platform = &mut ctx.accounts.platform;
+        let previous = platform.authority;
+        platform.rotate_authority(ctx.accounts.new_authority.key(), now)?;
+        // Handing off to a realm goes through set_governance
+        platform.governance_program = Pubkey::default();
+        msg!("Platform authority rotated from {} to {}, expiring at {}", previous, platform.authority, platform.authority_expires_at);
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::AcceptAuthority, ctx.accounts.new_authority.key(), &previous)?;
+        Ok(())
+    }
+
+    /// Hand platform authority to a Realms governance account (admin only)
+    pub fn set_governance(ctx: Context<SetGovernance>) -> Result<()> {
+        let platform = &mut ctx.accounts.platform;
+        // A realm rotates its own signers, so its governance account never expires
+        platform.authority_rotation_secs = 0;
+        platform.rotate_authority(ctx.accounts.governance.key(), Clock::get()?.unix_timestamp)?;
+        platform.governance_program = ctx.accounts.governance_program.key();
+
+        msg!("Platform authority handed to governance {} of program {}",
+            platform.authority, platform.governance_program);
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::SetGovernance, ctx.accounts.authority.key(), &ctx.accounts.platform.governance_program)?;
+        Ok(())
+    }
+
+    /// Change the performance fee through an executed governance proposal
+    pub fn governance_set_platform_fee(
+        ctx: Context<GovernanceAction>,
+        platform_fee_bps: u16,
+    ) -> Result<()> {
+        require!(platform_fee_bps <= 10000, ErrorCode::InvalidFee);
+        let platform = &mut ctx.accounts.platform;
+        platform.check_governance_caller(&ctx.accounts.instructions)?;
+
+        platform.platform_fee_bps = platform_fee_bps;
+        msg!("Platform fee set to {}% by governance", platform_fee_bps as f64 / 100.0);
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::GovernanceSetPlatformFee, ctx.accounts.authority.key(), &platform_fee_bps)?;
+        Ok(())
+    }
+
+    /// Pause the platform through an executed governance proposal
+    pub fn governance_pause_platform(ctx: Context<GovernanceAction>) -> Result<()> {
+        let platform = &mut ctx.accounts.platform;
+        platform.check_governance_caller(&ctx.accounts.instructions)?;
+
+        platform.is_paused = true;
+        msg!("Platform paused by governance");
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::GovernancePausePlatform, ctx.accounts.authority.key(), &())?;
+        Ok(())
+    }
+
+    /// Unpause the platform through an executed governance proposal
+    pub fn governance_unpause_platform(ctx: Context<GovernanceAction>) -> Result<()> {
+        let platform = &mut ctx.accounts.platform;
+        platform.check_governance_caller(&ctx.accounts.instructions)?;
+
+        platform.is_paused = false;
+        msg!("Platform unpaused by governance");
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::GovernanceUnpausePlatform, ctx.accounts.authority.key(), &())?;
+        Ok(())
+    }
+
+    /// Queue an admin action for execution after the timelock (admin only)
+    pub fn queue_action(
+        ctx: Context<QueueAction>,
+        action_id: u64,
+        kind: ScheduledActionKind,
+        params_hash: [u8; 32],
+        execute_after: i64,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            execute_after >= now.saturating_add(MIN_ACTION_DELAY_SECS),
+            ErrorCode::ActionDelayTooShort
+        );
+
+        let action = &mut ctx.accounts.scheduled_action;
+        action.platform = ctx.accounts.platform.key();
+        action.action_id = action_id;
+        action.kind = kind;
+        action.params_hash = params_hash;
+        action.queued_at = now;
+        action.execute_after = execute_after;
+        action.bump = ctx.bumps.scheduled_action;
+
+        msg!("Scheduled action #{} queued, executable after {}", action_id, execute_after);
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::QueueAction, ctx.accounts.authority.key(), &(action_id, kind, params_hash, execute_after))?;
+        Ok(())
+    }
+
+    /// Drop a queued action before it runs (admin only)
+    pub fn cancel_action(ctx: Context<CancelAction>, action_id: u64) -> Result<()> {
+        msg!("Scheduled action #{} cancelled", action_id);
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::CancelAction, ctx.accounts.authority.key(), &action_id)?;
+        Ok(())
+    }
+
+    /// Execute a queued action once its timelock has passed, revealing the committed parameters (anyone)
+    pub fn execute_action(
+        ctx: Context<ExecuteAction>,
+        action_id: u64,
+        params: ScheduledActionParams,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(now >= ctx.accounts.scheduled_action.execute_after, ErrorCode::ActionNotReady);
+        ctx.accounts.scheduled_action.check_params(&params)?;
+
+        match params {
+            ScheduledActionParams::SetPlatformFee { platform_fee_bps } => {
+                require!(platform_fee_bps <= 10000, ErrorCode::InvalidFee);
+                ctx.accounts.platform.platform_fee_bps = platform_fee_bps;
+                msg!("Platform fee set to {}%", platform_fee_bps as f64 / 100.0);
+            }
+            ScheduledActionParams::TransferAuthority { new_authority } => {
+                let platform = &mut ctx.accounts.platform;
+                platform.rotate_authority(new_authority, now)?;
+                // Handing off to a realm goes through set_governance
+                platform.governance_program = Pubkey::default();
+                msg!("Platform authority transferred to {}", new_authority);
+            }
+            ScheduledActionParams::SetSettlementCommittee { ref approvers, threshold } => {
+                let committee = ctx.accounts.settlement_committee
+                    .as_mut()
+                    .ok_or(ErrorCode::ActionParamsMismatch)?;
+                let enabled = committee.replace(approvers.clone(), threshold)?;
+                ctx.accounts.platform.settlement_committee = if enabled { committee.key() } else { Pubkey::default() };
+                msg!("Settlement committee set to {} of {} approvers", threshold, approvers.len());
+            }
+            ScheduledActionParams::TreasurySpend { amount, destination } => {
+                let destination_token_account = ctx.accounts.destination_token_account
+                    .as_ref()
+                    .ok_or(ErrorCode::ActionParamsMismatch)?;
+                require_keys_eq!(destination_token_account.key(), destination, ErrorCode::ActionParamsMismatch);
+                require!(
+                    amount <= ctx.accounts.platform_fee_vault.amount,
+                    ErrorCode::InsufficientVaultFunds
+                );
+
+                let platform_bump = ctx.accounts.platform.bump;
+                let platform_id_bytes = ctx.accounts.platform.platform_id.to_le_bytes();
+                let platform_seeds = &[
+                    b"platform".as_ref(),
+                    platform_id_bytes.as_ref(),
+                    &[platform_bump],
+                ];
+                let signer = &[&platform_seeds[..]];
+
+                let cpi_accounts = Transfer {
+                    from: ctx.accounts.platform_fee_vault.to_account_info(),
+                    to: destination_token_account.to_account_info(),
+                    authority: ctx.accounts.platform.to_account_info(),
+                };
+                let cpi_program = ctx.accounts.token_program.to_account_info();
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+                token::transfer(cpi_ctx, amount)?;
+                msg!("Treasury spend of {} USDC to {}", amount, destination);
+            }
+        }
+
+        msg!("Scheduled action #{} executed", action_id);
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::ExecuteAction, ctx.accounts.executor.key(), &(action_id, params))?;
+        Ok(())
+    }
+
+    /// Freeze a single run without pausing the platform (admin or guardian)
+    pub fn pause_run(ctx: Context<PauseRun>, run_id: u64) -> Result<()> {
+        ctx.accounts.run.is_paused = true;
+        msg!("Run #{} paused by {}", run_id, ctx.accounts.pauser.key());
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::PauseRun, ctx.accounts.pauser.key(), &run_id)?;
+        Ok(())
+    }
+
+    /// Register the caller as a run manager with an on-chain track record
+    pub fn register_manager(ctx: Context<RegisterManager>) -> Result<()> {
+        let profile = &mut ctx.accounts.manager_profile;
+        profile.wallet = ctx.accounts.wallet.key();
+        profile.registered_at = Clock::get()?.unix_timestamp;
+        profile.runs_settled = 0;
+        profile.total_deposited = 0;
+        profile.total_pnl = 0;
+        profile.equity_index = EQUITY_INDEX_BASE;
+        profile.peak_equity_index = EQUITY_INDEX_BASE;
+        profile.max_drawdown_bps = 0;
+        profile.worst_run_return_bps = 0;
+        profile.bump = ctx.bumps.manager_profile;
+        profile.fees_earned = 0;
+
+        msg!("Manager {} registered", profile.wallet);
+        Ok(())
+    }
+
+    /// Pay out fees the manager has earned across settled runs
+    pub fn claim_manager_fees(ctx: Context<ClaimManagerFees>) -> Result<()> {
+        let amount = ctx.accounts.manager_fee_vault.amount;
+        require!(amount > 0, ErrorCode::NoManagerFees);
+
+        let platform_key = ctx.accounts.platform.key();
+        let wallet_key = ctx.accounts.wallet.key();
+        let manager_seeds = &[
+            b"manager".as_ref(),
+            platform_key.as_ref(),
+            wallet_key.as_ref(),
+            &[ctx.accounts.manager_profile.bump],
+        ];
+        let signer = &[&manager_seeds[..]];
+
+        write_memo(
+            &ctx.accounts.memo_program,
+            &format!("instinct:manager_fees platform={} manager={}", platform_key, wallet_key),
+        )?;
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.manager_fee_vault.to_account_info(),
+            to: ctx.accounts.destination_token_account.to_account_info(),
+            authority: ctx.accounts.manager_profile.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, amount)?;
+
+        msg!("Manager {} claimed {} USDC of fees", wallet_key, amount);
+        Ok(())
+    }
+
+    /// Register an AI agent with a bonded stake that can be slashed into the runs it trades
+    pub fn register_agent(ctx: Context<RegisterAgent>, bond: u64) -> Result<()> {
+        require!(bond >= MIN_AGENT_BOND, ErrorCode::AgentBondTooSmall);
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.wallet_token_account.to_account_info(),
+            to: ctx.accounts.agent_bond_vault.to_account_info(),
+            authority: ctx.accounts.wallet.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::transfer(cpi_ctx, bond)?;
+
+        let agent = &mut ctx.accounts.agent;
+        agent.wallet = ctx.accounts.wallet.key();
+        agent.registered_at = Clock::get()?.unix_timestamp;
+        agent.bond = bond;
+        agent.slashed_total = 0;
+        agent.unbonding_at = 0;
+        agent.bump = ctx.bumps.agent;
+
+        msg!("Agent {} registered with a bond of {} USDC", agent.wallet, bond);
+        Ok(())
+    }
+
+    /// Add to an agent's bond
+    pub fn add_agent_bond(ctx: Context<AddAgentBond>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidDepositAmount);
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.wallet_token_account.to_account_info(),
+            to: ctx.accounts.agent_bond_vault.to_account_info(),
+            authority: ctx.accounts.wallet.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+
+        let agent = &mut ctx.accounts.agent;
+        agent.bond = agent.bond
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        msg!("Agent {} bond raised to {} USDC", agent.wallet, agent.bond);
+        Ok(())
+    }
+
+    /// Stop taking new runs and start the unbonding wait; the bond stays slashable until it ends
+    pub fn begin_agent_unbonding(ctx: Context<BeginAgentUnbonding>) -> Result<()> {
+        let agent = &mut ctx.accounts.agent;
+        require!(agent.is_bonded(), ErrorCode::AgentUnbonding);
+        agent.unbonding_at = Clock::get()?.unix_timestamp;
+
+        msg!("Agent {} unbonding until {}", agent.wallet, agent.unbonding_at + AGENT_UNBONDING_SECS);
+        Ok(())
+    }
+
+    /// Withdraw an agent's remaining bond once the unbonding wait has passed
+    pub fn withdraw_agent_bond(ctx: Context<WithdrawAgentBond>) -> Result<()> {
+        let agent = &ctx.accounts.agent;
+        require!(!agent.is_bonded(), ErrorCode::AgentBonded);
+        require!(
+            Clock::get()?.unix_timestamp >= agent.unbonding_at + AGENT_UNBONDING_SECS,
+            ErrorCode::AgentUnbonding
+        );
+        let amount = ctx.accounts.agent_bond_vault.amount;
+
+        let platform_key = ctx.accounts.platform.key();
+        let wallet_key = ctx.accounts.wallet.key();
+        let agent_seeds = &[
+            b"agent".as_ref(),
+            platform_key.as_ref(),
+            wallet_key.as_ref(),
+            &[agent.bump],
+        ];
+        let signer = &[&agent_seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.agent_bond_vault.to_account_info(),
+            to: ctx.accounts.destination_token_account.to_account_info(),
+            authority: ctx.accounts.agent.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, amount)?;
+
+        ctx.accounts.agent.bond = 0;
+        msg!("Agent {} withdrew its bond of {} USDC", wallet_key, amount);
+        Ok(())
+    }
+
+    /// Assign the bonded agent trading a run, or clear it with the default key (admin only)
+    pub fn set_run_agent(
+        ctx: Context<SetRunAgent>,
+        run_id: u64,
+        agent: Pubkey,
+    ) -> Result<()> {
+        if agent != Pubkey::default() {
+            let profile = ctx.accounts.agent_profile.as_ref().ok_or(ErrorCode::AgentNotRegistered)?;
+            require!(profile.is_bonded(), ErrorCode::AgentUnbonding);
+        }
+        let run = &mut ctx.accounts.run;
+        require!(
+            matches!(run.status, RunStatus::Waiting | RunStatus::Active),
+            ErrorCode::InvalidRunStatus
+        );
+        run.agent = agent;
+        run.agent_at_fault = false;
+        msg!("Run #{} agent set to {}", run_id, agent);
+        Ok(())
+    }
+
+    /// Add an agent strategy to a run with its own vault. Strategy `index` trades the capital
+    /// voted to option `index` of each round (admin only)
+    pub fn add_run_agent(
+        ctx: Context<AddRunAgent>,
+        run_id: u64,
+        index: u8,
+        agent: Pubkey,
+    ) -> Result<()> {
+        require!(ctx.accounts.agent_profile.is_bonded(), ErrorCode::AgentUnbonding);
+        let run = &mut ctx.accounts.run;
+        require!(
+            matches!(run.status, RunStatus::Waiting | RunStatus::Active),
+            ErrorCode::InvalidRunStatus
+        );
+        require!(
+            index == run.strategy_count && (index as usize) < MAX_VOTE_OPTIONS,
+            ErrorCode::InvalidStrategyIndex
+        );
+        run.strategy_count += 1;
+
+        let strategy = &mut ctx.accounts.strategy;
+        strategy.run_id = run_id;
+        strategy.index = index;
+        strategy.agent = agent;
+        strategy.token_account = ctx.accounts.strategy_vault.key();
+        strategy.allocated = 0;
+        strategy.bump = ctx.bumps.strategy;
+        strategy.capital_in = 0;
+        strategy.capital_out = 0;
+        strategy.realized_pnl = 0;
+        strategy.settled = false;
+
+        msg!("Run #{} strategy {} assigned to agent {}", run_id, index, agent);
+        Ok(())
+    }
+
+    /// Split capital between the run vault and its strategy vaults by a finalized round's vote
+    /// weights: strategy `i` gets the share voted to option `i`, and options past the run's
+    /// strategies (e.g. a hold option) keep theirs in the run vault. Remaining accounts are
+    /// (strategy, strategy vault) pairs for every strategy in index order. Capital must be back
+    /// in the run vault before settlement (platform authority or manager)
+    pub fn allocate_capital<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AllocateCapital<'info>>,
+        run_id: u64,
+        round: u8,
+    ) -> Result<()> {
+        ctx.accounts.platform.require_feature(FEATURE_TRADING_CPI)?;
+        let vote_round = &ctx.accounts.vote_round;
+        require!(vote_round.decision_finalized, ErrorCode::DecisionNotFinalized);
+        require!(!vote_round.capital_allocated, ErrorCode::CapitalAlreadyAllocated);
+        let strategy_count = ctx.accounts.run.strategy_count as usize;
+        require!(strategy_count > 0, ErrorCode::InvalidStrategyIndex);
+
+        let platform_key = ctx.accounts.platform.key();
+        let run_id_bytes = run_id.to_le_bytes();
+        let mut strategies = StrategyVault::load_all(ctx.remaining_accounts, &platform_key, run_id, strategy_count)?;
+        let mut total = ctx.accounts.run_vault.amount;
+        for (_, balance) in strategies.iter() {
+            total = total
+                .checked_add(*balance)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+        }
+
+        let run_seeds = &[
+            b"run".as_ref(),
+            platform_key.as_ref(),
+            run_id_bytes.as_ref(),
+            &[ctx.accounts.run.bump],
+        ];
+        let signer = &[&run_seeds[..]];
+        let run_vault_info = ctx.accounts.run_vault.to_account_info();
+        let transfer = |from: AccountInfo<'info>, to: AccountInfo<'info>, amount: u64| -> Result<()> {
+            let cpi_accounts = Transfer {
+                from,
+                to,
+                authority: ctx.accounts.run.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+            token::transfer(cpi_ctx, amount)
+        };
+
+        // Pull excess back first so the run vault can cover every shortfall
+        let targets = strategies
+            .iter()
+            .map(|(strategy, _)| vote_round.capital_share(strategy.index, total))
+            .collect::<Result<Vec<u64>>>()?;
+        for (index, (_, balance)) in strategies.iter().enumerate() {
+            if *balance > targets[index] {
+                transfer(ctx.remaining_accounts[index * 2 + 1].clone(), run_vault_info.clone(), balance - targets[index])?;
+            }
+        }
+        for (index, (_, balance)) in strategies.iter().enumerate() {
+            if *balance < targets[index] {
+                transfer(run_vault_info.clone(), ctx.remaining_accounts[index * 2 + 1].clone(), targets[index] - balance)?;
+            }
+        }
+        for (index, (strategy, balance)) in strategies.iter_mut().enumerate() {
+            strategy.record_allocation(*balance, targets[index])?;
+            strategy.exit(&crate::ID)?;
+        }
+
+        ctx.accounts.vote_round.capital_allocated = true;
+        msg!("Run #{} round {} allocated {} across {} strategies",
+            run_id, round, ctx.accounts.run.ui(total), strategy_count);
+        Ok(())
+    }
+
+    /// Move run capital into a strategy's sub-vault (platform authority or manager)
+    pub fn fund_subvault(
+        ctx: Context<MoveStrategyCapital>,
+        run_id: u64,
+        index: u8,
+        amount: u64,
+    ) -> Result<()> {
+        ctx.accounts.platform.require_feature(FEATURE_TRADING_CPI)?;
+        require!(ctx.accounts.run.status == RunStatus::Active, ErrorCode::InvalidRunStatus);
+        require!(amount > 0, ErrorCode::InvalidDepositAmount);
+        ctx.accounts.transfer_with_run_seeds(
+            ctx.accounts.run_vault.to_account_info(),
+            ctx.accounts.strategy_vault.to_account_info(),
+            amount,
+        )?;
+        let strategy = &mut ctx.accounts.strategy;
+        let balance = ctx.accounts.strategy_vault.amount;
+        strategy.record_allocation(balance, balance + amount)?;
+
+        msg!("Funded run #{} strategy {} with {}", run_id, index, ctx.accounts.run.ui(amount));
+        Ok(())
+    }
+
+    /// Move capital from a strategy's sub-vault back to the run vault; every strategy must be
+    /// emptied before the run settles, so this stays open with trading CPIs disabled
+    /// (platform authority or manager)
+    pub fn defund_subvault(
+        ctx: Context<MoveStrategyCapital>,
+        run_id: u64,
+        index: u8,
+        amount: u64,
+    ) -> Result<()> {
+        require!(ctx.accounts.run.status == RunStatus::Active, ErrorCode::InvalidRunStatus);
+        require!(amount > 0, ErrorCode::InvalidDepositAmount);
+        ctx.accounts.transfer_with_run_seeds(
+            ctx.accounts.strategy_vault.to_account_info(),
+            ctx.accounts.run_vault.to_account_info(),
+            amount,
+        )?;
+        let strategy = &mut ctx.accounts.strategy;
+        let balance = ctx.accounts.strategy_vault.amount;
+        strategy.record_allocation(balance, balance - amount)?;
+
+        msg!("Defunded {} from run #{} strategy {}", ctx.accounts.run.ui(amount), run_id, index);
+        Ok(())
+    }
+
+    /// Create the ledger that records a run's rebalances (platform authority or manager)
+    pub fn init_position_ledger(
+        ctx: Context<InitPositionLedger>,
+        run_id: u64,
+    ) -> Result<()> {
+        let ledger = &mut ctx.accounts.position_ledger.load_init()?;
+        ledger.run_id = run_id;
+        ledger.bump = ctx.bumps.position_ledger;
+
+        msg!("Position ledger created for run #{}", run_id);
+        Ok(())
+    }
+
+    /// Move capital between two strategy sub-vaults, within the run's per-round movement cap and
+    /// leaving the source strategy its liquidity buffer (platform authority or manager)
+    pub fn rebalance(
+        ctx: Context<Rebalance>,
+        run_id: u64,
+        from_subvault: u8,
+        to_subvault: u8,
+        amount: u64,
+    ) -> Result<()> {
+        ctx.accounts.platform.require_feature(FEATURE_TRADING_CPI)?;
+        let run = &ctx.accounts.run;
+        require!(run.status == RunStatus::Active, ErrorCode::InvalidRunStatus);
+        require!(from_subvault != to_subvault, ErrorCode::InvalidStrategyIndex);
+        require!(amount > 0, ErrorCode::InvalidDepositAmount);
+
+        let from_balance = ctx.accounts.from_vault.amount;
+        let movable = (from_balance as u128 * (10000 - run.liquidity_buffer_bps) as u128 / 10000) as u64;
+        require!(amount <= movable, ErrorCode::LiquidityBufferBreached);
+        let round_cap = (run.total_deposited as u128 * run.max_rebalance_bps_per_round as u128 / 10000) as u64;
+        let moved_this_round = ctx.accounts.position_ledger.load_mut()?
+            .record(run.voting_open_until, from_subvault, to_subvault, amount)?;
+        require!(moved_this_round <= round_cap, ErrorCode::RebalanceCapExceeded);
+
+        let platform_key = ctx.accounts.platform.key();
+        let run_id_bytes = run_id.to_le_bytes();
+        let run_seeds = &[
+            b"run".as_ref(),
+            platform_key.as_ref(),
+            run_id_bytes.as_ref(),
+            &[run.bump],
+        ];
+        let signer = &[&run_seeds[..]];
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.from_vault.to_account_info(),
+            to: ctx.accounts.to_vault.to_account_info(),
+            authority: ctx.accounts.run.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, amount)?;
+
+        ctx.accounts.from_strategy.record_allocation(from_balance, from_balance - amount)?;
+        let to_balance = ctx.accounts.to_vault.amount;
+        ctx.accounts.to_strategy.record_allocation(to_balance, to_balance + amount)?;
+
+        emit_cpi!(Rebalanced {
+            run_id,
+            from_subvault,
+            to_subvault,
+            amount,
+            moved_this_round,
+        });
+        msg!("Rebalanced {} in run #{} from strategy {} to {}",
+            ctx.accounts.run.ui(amount), run_id, from_subvault, to_subvault);
+        Ok(())
+    }
+
+    /// Record a trade the run's agent executed for a vote round, taking the option the community
+    /// decided and staying within the run's RiskConfig limits; leverage is measured against the
+    /// run's deposits (platform authority or manager)
+    pub fn record_trade(
+        ctx: Context<RecordTrade>,
+        run_id: u64,
+        round: u8,
+        option: u8,
+        market_index: u16,
+        size: u64,
+    ) -> Result<()> {
+        ctx.accounts.platform.require_feature(FEATURE_TRADING_CPI)?;
+        let run = &ctx.accounts.run;
+        require!(run.status == RunStatus::Active, ErrorCode::InvalidRunStatus);
+        require!(!run.is_paused, ErrorCode::RunPaused);
+        ctx.accounts.vote_round.check_decision(option)?;
+        let risk_config = &mut ctx.accounts.risk_config;
+        risk_config.check_trade(market_index, size, run.total_deposited, Clock::get()?.unix_timestamp)?;
+
+        emit_cpi!(TradeRecorded {
+            run_id,
+            round,
+            option,
+            market_index,
+            size,
+            trades_today: risk_config.trades_today,
+        });
+        msg!("Run #{} round {} traded {} on market {} for option {}",
+            run_id, round, run.ui(size), market_index, option);
+        Ok(())
+    }
+
+    /// Attribute a strategy's P/L once its run has settled: everything it returned to the run vault
+    /// less everything it was given (anyone)
+    pub fn settle_strategy(
+        ctx: Context<SettleStrategy>,
+        run_id: u64,
+        index: u8,
+    ) -> Result<()> {
+        require!(ctx.accounts.run.status == RunStatus::Settled, ErrorCode::RunNotSettled);
+        require!(ctx.accounts.strategy_vault.amount == 0, ErrorCode::StrategyNotEmpty);
+        let strategy = &mut ctx.accounts.strategy;
+        require!(!strategy.settled, ErrorCode::StrategyAlreadySettled);
+        strategy.realized_pnl = strategy.capital_out as i64 - strategy.capital_in as i64;
+        strategy.settled = true;
+
+        emit_cpi!(StrategySettled {
+            run_id,
+            index,
+            agent: strategy.agent,
+            capital_in: strategy.capital_in,
+            capital_out: strategy.capital_out,
+            pnl: strategy.realized_pnl,
+        });
+        msg!("Run #{} strategy {} settled with P/L {}", run_id, index, strategy.realized_pnl);
+        Ok(())
+    }
+
+    /// Slash part of the bond of an agent whose run had a dispute upheld or tripped its drawdown
+    /// breaker into that run's vault (admin only)
+    pub fn slash_agent(
+        ctx: Context<SlashAgent>,
+        run_id: u64,
+        slash_bps: u16,
+    ) -> Result<()> {
+        require!(slash_bps > 0 && slash_bps <= 10000, ErrorCode::InvalidFee);
+        require!(ctx.accounts.run.agent_at_fault, ErrorCode::AgentNotAtFault);
+
+        let agent = &ctx.accounts.agent;
+        let amount = (agent.bond as u128 * slash_bps as u128 / 10000) as u64;
+        let platform_key = ctx.accounts.platform.key();
+        let wallet_key = agent.wallet;
+        let agent_seeds = &[
+            b"agent".as_ref(),
+            platform_key.as_ref(),
+            wallet_key.as_ref(),
+            &[agent.bump],
+        ];
+        let signer = &[&agent_seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.agent_bond_vault.to_account_info(),
+            to: ctx.accounts.run_vault.to_account_info(),
+            authority: ctx.accounts.agent.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, amount)?;
+
+        let agent = &mut ctx.accounts.agent;
+        agent.bond -= amount;
+        agent.slashed_total = agent.slashed_total
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        let run = &mut ctx.accounts.run;
+        run.agent_at_fault = false;
+        // Settled payouts are computed from final_balance, so the slash has to land there too
+        if run.status == RunStatus::Settled {
+            run.final_balance = run.final_balance
+                .checked_add(amount)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+        }
+
+        msg!("Agent {} slashed {} USDC into run #{}", wallet_key, amount, run_id);
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::SlashAgent, ctx.accounts.authority.key(), &(run_id, wallet_key, amount))?;
+        Ok(())
+    }
+
+    /// Assign a registered manager and their profit share to a run; default key removes it (admin only)
+    pub fn set_run_manager(
+        ctx: Context<SetRunManager>,
+        run_id: u64,
+        manager: Pubkey,
+        manager_fee_bps: u16,
+    ) -> Result<()> {
+        require!(
+            manager == Pubkey::default() || ctx.accounts.manager_profile.is_some(),
+            ErrorCode::ManagerNotRegistered
+        );
+        require!(
+            manager_fee_bps <= 10000 && (manager != Pubkey::default() || manager_fee_bps == 0),
+            ErrorCode::InvalidFee
+        );
+        let run = &mut ctx.accounts.run;
+        // Depositors joined under the fee in place when they deposited
+        if manager_fee_bps != run.manager_fee_bps {
+            require!(run.participant_count == 0, ErrorCode::RunHasDeposits);
+        }
+        run.manager = manager;
+        run.manager_fee_bps = manager_fee_bps;
+        msg!("Run #{} manager set to {} with {}% of profit", run_id, manager, manager_fee_bps as f64 / 100.0);
+        Ok(())
+    }
+
+    /// Set the attestor that must co-sign settlements; default key removes it (admin only)
+    pub fn set_attestor(ctx: Context<AdminAction>, attestor: Pubkey) -> Result<()> {
+        ctx.accounts.platform.attestor = attestor;
+        msg!("Settlement attestor set to {}", attestor);
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::SetAttestor, ctx.accounts.authority.key(), &attestor)?;
+        Ok(())
+    }
+
+    /// Set the risk service whose signed report every settlement must carry; default key removes it
+    /// (admin only)
+    pub fn set_risk_oracle(ctx: Context<AdminAction>, risk_oracle: Pubkey) -> Result<()> {
+        ctx.accounts.platform.risk_oracle = risk_oracle;
+        msg!("Risk oracle set to {}", risk_oracle);
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::SetRiskOracle, ctx.accounts.authority.key(), &risk_oracle)?;
+        Ok(())
+    }
+
+    /// Require every settlement to be approved by `threshold` of `approvers` through
+    /// approve_settlement first. Only while no committee is in force: changing or removing one
+    /// goes through the timelock with a SetSettlementCommittee scheduled action (admin only)
+    pub fn set_settlement_committee(
+        ctx: Context<SetSettlementCommittee>,
+        approvers: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        require_keys_eq!(
+            ctx.accounts.platform.settlement_committee,
+            Pubkey::default(),
+            ErrorCode::SettlementCommitteeTimelocked
+        );
+        let committee = &mut ctx.accounts.settlement_committee;
+        committee.bump = ctx.bumps.settlement_committee;
+        let enabled = committee.replace(approvers.clone(), threshold)?;
+        ctx.accounts.platform.settlement_committee = if enabled { committee.key() } else { Pubkey::default() };
+
+        msg!("Settlement committee set to {} of {} approvers", threshold, approvers.len());
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::SetSettlementCommittee, ctx.accounts.authority.key(), &(approvers, threshold))?;
+        Ok(())
+    }
+
+    /// Approve settling a run at `final_balance` with the share list hashing to `shares_hash`
+    /// (SHA-256 of the Borsh-encoded Vec<ParticipantShare>). Each proposed pair of figures gathers
+    /// approvals in its own account, so approving other figures never resets them (settlement
+    /// committee members)
+    pub fn approve_settlement(
+        ctx: Context<ApproveSettlement>,
+        run_id: u64,
+        final_balance: u64,
+        shares_hash: [u8; 32],
+    ) -> Result<()> {
+        require_keys_neq!(ctx.accounts.platform.settlement_committee, Pubkey::default(), ErrorCode::NoSettlementCommittee);
+        require!(
+            matches!(ctx.accounts.run.status, RunStatus::Active | RunStatus::SettlementPending),
+            ErrorCode::InvalidRunStatus
+        );
+        let committee = &ctx.accounts.settlement_committee;
+        let index = committee
+            .approver_index(ctx.accounts.approver.key())
+            .ok_or(ErrorCode::NotSettlementApprover)?;
+
+        let approval = &mut ctx.accounts.settlement_approval;
+        approval.bump = ctx.bumps.settlement_approval;
+        approval.final_balance = final_balance;
+        approval.shares_hash = shares_hash;
+        let approvals = approval.approve(committee, index);
+
+        msg!("Settlement of run #{} at {} approved by {} ({}/{})",
+            run_id, ctx.accounts.run.ui(final_balance), ctx.accounts.approver.key(), approvals, committee.threshold);
+        Ok(())
+    }
+
+    /// Set the guardian allowed to pause the platform and runs; default key removes it (admin only)
+    pub fn set_guardian(ctx: Context<AdminAction>, guardian: Pubkey) -> Result<()> {
+        ctx.accounts.platform.guardian = guardian;
+        msg!("Guardian set to {}", guardian);
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::SetGuardian, ctx.accounts.authority.key(), &guardian)?;
+        Ok(())
+    }
+
+    /// Resume a paused run (admin only)
+    pub fn resume_run(ctx: Context<RunAdminAction>, run_id: u64) -> Result<()> {
+        ctx.accounts.run.is_paused = false;
+        ctx.accounts.run.breaker_tripped = false;
+        msg!("Run #{} resumed by authority", run_id);
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::ResumeRun, ctx.accounts.authority.key(), &run_id)?;
+        Ok(())
+    }
+
+    /// Adjust deposit limits and deadlines of a run that has not started (admin only)
+    pub fn update_run_config(
+        ctx: Context<RunAdminAction>,
+        run_id: u64,
+        config: UpdateRunConfigParams,
+    ) -> Result<()> {
+        let run = &mut ctx.accounts.run;
+        require!(run.status == RunStatus::Waiting, ErrorCode::RunNotInWaitingPhase);
+
+        config.apply(run, Clock::get()?.unix_timestamp)?;
+
+        msg!("Run #{} config updated - Min: {} Max: {} Participants: {} Deadline: {}",
+            run_id, run.min_deposit, run.max_deposit, run.max_participants, run.deposit_deadline);
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::UpdateRunConfig, ctx.accounts.authority.key(), &(run_id, config))?;
+        Ok(())
+    }
+
+    /// Keep an under-subscribed run open longer, up to MAX_DEADLINE_EXTENSION_SECS in total (admin only)
+    pub fn extend_deposit_deadline(
+        ctx: Context<RunAdminAction>,
+        run_id: u64,
+        new_deadline: i64,
+    ) -> Result<()> {
+        let run = &mut ctx.accounts.run;
+        require!(run.status == RunStatus::Waiting, ErrorCode::RunNotInWaitingPhase);
+        require!(new_deadline > run.deposit_deadline, ErrorCode::InvalidDeadline);
+
+        let extension = run.deadline_extension
+            .checked_add(new_deadline - run.deposit_deadline)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        require!(extension <= MAX_DEADLINE_EXTENSION_SECS, ErrorCode::DeadlineExtensionTooLong);
+
+        run.deposit_deadline = new_deadline;
+        run.deadline_extension = extension;
+
+        msg!("Run #{} deposit deadline extended to {} ({}s extended in total)",
+            run_id, new_deadline, extension);
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::ExtendDepositDeadline, ctx.accounts.authority.key(), &(run_id, new_deadline))?;
+        Ok(())
+    }
+
+    /// Set the platform-wide TVL cap, 0 = no cap (admin only)
+    pub fn set_platform_tvl_cap(
+        ctx: Context<AdminAction>,
+        max_platform_tvl: u64,
+    ) -> Result<()> {
+        ctx.accounts.platform.max_platform_tvl = max_platform_tvl;
+        msg!("Platform TVL cap set to {} USDC", max_platform_tvl);
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::SetPlatformTvlCap, ctx.accounts.authority.key(), &max_platform_tvl)?;
+        Ok(())
+    }
+
+    /// Cap how many runs may be active at once and the capital started runs may hold in total,
+    /// 0 = no cap; existing runs over a lowered cap are unaffected (admin only)
+    pub fn set_platform_limits(
+        ctx: Context<AdminAction>,
+        max_active_runs: u32,
+        max_total_exposure: u64,
+    ) -> Result<()> {
+        let platform = &mut ctx.accounts.platform;
+        platform.max_active_runs = max_active_runs;
+        platform.max_total_exposure = max_total_exposure;
+        msg!("Platform limits set to {} active runs and {} USDC exposure", max_active_runs, max_total_exposure);
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::SetPlatformLimits, ctx.accounts.authority.key(), &(max_active_runs, max_total_exposure))?;
+        Ok(())
+    }
+
+    /// Enable or disable a single optional subsystem (admin only)
+    pub fn set_feature_flag(
+        ctx: Context<AdminAction>,
+        feature: u64,
+        enabled: bool,
+    ) -> Result<()> {
+        require!(
+            feature.is_power_of_two() && feature & ALL_FEATURES == feature,
+            ErrorCode::UnknownFeature
+        );
+
+        let platform = &mut ctx.accounts.platform;
+        if enabled {
+            platform.features |= feature;
+        } else {
+            platform.features &= !feature;
+        }
+        msg!("Feature {:#x} {} - flags now {:#x}",
+            feature, if enabled { "enabled" } else { "disabled" }, platform.features);
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::SetFeatureFlag, ctx.accounts.authority.key(), &(feature, enabled))?;
+        Ok(())
+    }
+
+    /// Configure the emergency exit penalty and where it goes (admin only)
+    pub fn set_exit_penalty(
+        ctx: Context<AdminAction>,
+        exit_penalty_bps: u16,
+        to_treasury: bool,
+    ) -> Result<()> {
+        require!(exit_penalty_bps <= 10000, ErrorCode::InvalidFee);
+
+        let platform = &mut ctx.accounts.platform;
+        platform.exit_penalty_bps = exit_penalty_bps;
+        platform.exit_penalty_to_treasury = to_treasury;
+
+        msg!(
+            "Exit penalty set to {}% routed to {}",
+            exit_penalty_bps as f64 / 100.0,
+            if to_treasury { "the treasury" } else { "remaining participants" }
+        );
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::SetExitPenalty, ctx.accounts.authority.key(), &(exit_penalty_bps, to_treasury))?;
+        Ok(())
+    }
+
+    /// Set how long settled funds stay frozen before finalize_settlement can open withdrawals (admin only)
+    pub fn set_settlement_escrow(
+        ctx: Context<AdminAction>,
+        settlement_escrow_secs: i64,
+    ) -> Result<()> {
+        require!(settlement_escrow_secs >= 0, ErrorCode::InvalidDuration);
+        ctx.accounts.platform.settlement_escrow_secs = settlement_escrow_secs;
+
+        msg!("Settlement escrow set to {}s", settlement_escrow_secs);
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::SetSettlementEscrow, ctx.accounts.authority.key(), &settlement_escrow_secs)?;
+        Ok(())
+    }
+
+    /// Configure the settlement bond and dispute window (admin only)
+    pub fn set_settlement_bond(
+        ctx: Context<AdminAction>,
+        settlement_bond: u64,
+        dispute_window_secs: i64,
+    ) -> Result<()> {
+        require!(dispute_window_secs >= 0, ErrorCode::InvalidDisputeWindow);
+
+        let platform = &mut ctx.accounts.platform;
+        platform.settlement_bond = settlement_bond;
+        platform.dispute_window_secs = dispute_window_secs;
+
+        msg!(
+            "Settlement bond set to {} with {}s dispute window",
+            settlement_bond,
+            dispute_window_secs
+        );
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::SetSettlementBond, ctx.accounts.authority.key(), &(settlement_bond, dispute_window_secs))?;
+        Ok(())
+    }
+
+    /// Withdraw collected platform fees (admin only)
+    pub fn withdraw_platform_fees(
+        ctx: Context<WithdrawPlatformFees>,
+        amount: u64,
+    ) -> Result<()> {
+        require!(
+            amount <= ctx.accounts.platform_fee_vault.amount,
+            ErrorCode::InsufficientVaultFunds
+        );
+
+        let platform_bump = ctx.accounts.platform.bump;
+        let platform_id_bytes = ctx.accounts.platform.platform_id.to_le_bytes();
+        let platform_seeds = &[
+            b"platform".as_ref(),
+            platform_id_bytes.as_ref(),
+            &[platform_bump],
+        ];
+        let signer = &[&platform_seeds[..]];
+
+        write_memo(
+            &ctx.accounts.memo_program,
+            &format!("instinct:platform_fees platform={}", ctx.accounts.platform.key()),
+        )?;
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.platform_fee_vault.to_account_info(),
+            to: ctx.accounts.destination_token_account.to_account_info(),
+            authority: ctx.accounts.platform.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, amount)?;
+
+        msg!("Platform fees withdrawn: {} USDC", amount);
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::WithdrawPlatformFees, ctx.accounts.authority.key(), &amount)?;
+        Ok(())
+    }
+
+    /// Freeze a run and open pro-rata refunds to its depositors (admin only)
+    pub fn enable_emergency_refunds(
+        ctx: Context<EnableEmergencyRefunds>,
+        run_id: u64,
+    ) -> Result<()> {
+        let vault_balance = ctx.accounts.run_vault.amount;
+        let run = &mut ctx.accounts.run;
+
+        // Nothing has traded yet on a Waiting run, so freezing it cancels the run outright
+        let next = if run.status == RunStatus::Waiting {
+            RunStatus::Cancelled
+        } else {
+            RunStatus::Refunding
+        };
+        run.enter_refund_mode(next, vault_balance, Clock::get()?.unix_timestamp)?;
+        ctx.accounts.platform.end_run(run);
+
+        msg!(
+            "Emergency refunds enabled for run #{} - {} USDC across {} participants",
+            run_id,
+            run.ui(vault_balance),
+            run.participant_count
+        );
+        AuditLog::record(&ctx.accounts.audit_log, AuditAction::EnableEmergencyRefunds, ctx.accounts.authority.key(), &run_id)?;
+        Ok(())
+    }
+
+    /// Dead-man switch: anyone can open refunds once an active run, or one whose staged settlement
+    /// was never completed, is overdue for settlement
+    pub fn force_refund_mode(
+        ctx: Context<ForceRefundMode>,
+        run_id: u64,
+    ) -> Result<()> {
+        let vault_balance = ctx.accounts.run_vault.amount;
+        let run = &mut ctx.accounts.run;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(
+            matches!(run.status, RunStatus::Active | RunStatus::SettlementPending),
+            ErrorCode::InvalidRunStatus
+        );
+
+        let deadline = run.started_at
+            .checked_add(run.max_duration)
+            .and_then(|t| t.checked_add(SETTLEMENT_GRACE_PERIOD_SECS))
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        require!(now > deadline, ErrorCode::SettlementNotOverdue);
+
+        run.enter_refund_mode(RunStatus::Refunding, vault_balance, now)?;
+        ctx.accounts.platform.end_run(run);
+
+        msg!(
+            "Run #{} overdue since {} - forced into refund mode with {} USDC",
+            run_id,
+            deadline,
+            run.ui(vault_balance)
+        );
+        if let Some(keeper) = &ctx.accounts.keeper {
+            KeeperIncentive::reward(&mut ctx.accounts.keeper_incentive, &keeper.to_account_info())?;
+        }
+        Ok(())
+    }
+
+    /// Open refunds on a run that missed its quorum by the deposit deadline (anyone)
+    pub fn expire_unfilled_run(
+        ctx: Context<ForceRefundMode>,
+        run_id: u64,
+    ) -> Result<()> {
+        let vault_balance = ctx.accounts.run_vault.amount;
+        let run = &mut ctx.accounts.run;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(run.status == RunStatus::Waiting, ErrorCode::InvalidRunStatus);
+        require!(now > run.deposit_deadline, ErrorCode::DepositWindowOpen);
+        require!(!run.is_quorum_met(), ErrorCode::QuorumAlreadyMet);
+
+        run.enter_refund_mode(RunStatus::Expired, vault_balance, now)?;
+        ctx.accounts.platform.end_run(run);
+
+        msg!(
+            "Run #{} expired unfilled - {} participants, {} USDC to refund",
+            run_id,
+            run.participant_count,
+            run.ui(vault_balance)
+        );
+        if let Some(keeper) = &ctx.accounts.keeper {
+            KeeperIncentive::reward(&mut ctx.accounts.keeper_incentive, &keeper.to_account_info())?;
+        }
+        Ok(())
+    }
+
+    /// Claim back a pro-rata share of the vault from a run in refund mode
+    pub fn claim_refund(
+        ctx: Context<Withdraw>,
+        run_id: u64,
+    ) -> Result<()> {
+        let run = &ctx.accounts.run;
+
+        require!(run.status.is_refundable(), ErrorCode::RefundsNotEnabled);
+        require!(!ctx.accounts.user_participation.withdrawn, ErrorCode::AlreadyWithdrawn);
+        BlacklistEntry::check_not_listed(&ctx.accounts.blacklist_entry)?;
+        ctx.accounts.user_participation.check_not_frozen()?;
+        run.check_solvency(ctx.accounts.run_vault.amount)?;
+        // Stablecoin deposits share in the vault only once converted into it; until then
+        // claim_sub_vault_refund returns them in kind
+        if ctx.accounts.user_participation.deposit_mint != Pubkey::default() {
+            let accepted_mint = ctx.accounts.accepted_mint.as_ref().ok_or(ErrorCode::AltDepositLocked)?;
+            require!(accepted_mint.unconverted_notional == 0, ErrorCode::AltDepositLocked);
+        }
+
+        let refund = run.refund_share(ctx.accounts.user_participation.deposit_amount, ctx.accounts.run_vault.amount)?;
+
+        let platform_key = run.platform;
+        let run_id_bytes = run.run_id.to_le_bytes();
+        let run_seeds = &[
+            b"run".as_ref(),
+            platform_key.as_ref(),
+            run_id_bytes.as_ref(),
+            &[run.bump],
+        ];
+        let signer = &[&run_seeds[..]];
+
+        write_memo(
+            &ctx.accounts.memo_program,
+            &format!("instinct:refund run_id={} participation={}", run_id, ctx.accounts.user_participation.key()),
+        )?;
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.run_vault.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: ctx.accounts.run.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, refund)?;
+
+        let run = &mut ctx.accounts.run;
+        run.record_payout(&mut ctx.accounts.user_participation, refund, 0)?;
+
+        // Principal leaves the platform's TVL regardless of the P/L paid out
+        let platform = &mut ctx.accounts.platform;
+        platform.total_value_locked = platform.total_value_locked
+            .saturating_sub(ctx.accounts.user_participation.deposit_amount);
+        platform.record_withdrawal(refund)?;
+
+        msg!(
+            "User {} refunded {} USDC from run #{} ({}/{})",
+            ctx.accounts.user.key(),
+            run.ui(refund),
+            run_id,
+            run.withdrawn_count,
+            run.participant_count
+        );
+        Ok(())
+    }
+
+    /// Mint test USDC to the caller's associated token account, creating the program's test mint on
+    /// first use. Platforms set up on devnet use that mint as their USDC (devnet builds only)
+    #[cfg(feature = "devnet")]
+    pub fn faucet_mint_test_usdc(ctx: Context<FaucetMintTestUsdc>, amount: u64) -> Result<()> {
+        require!(amount <= FAUCET_MAX_AMOUNT, ErrorCode::FaucetLimitExceeded);
+
+        let signer: &[&[&[u8]]] = &[&[b"faucet_authority", &[ctx.bumps.faucet_authority]]];
+        let cpi_accounts = token::MintTo {
+            mint: ctx.accounts.test_usdc_mint.to_account_info(),
+            to: ctx.accounts.caller_token_account.to_account_info(),
+            authority: ctx.accounts.faucet_authority.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        token::mint_to(CpiContext::new_with_signer(cpi_program, cpi_accounts, signer), amount)?;
+
+        msg!("Faucet minted {} test USDC to {}",
+            UiAmount { amount, decimals: USDC_DECIMALS }, ctx.accounts.caller.key());
+        Ok(())
+    }
+
+    /// Grow a Platform, Run or UserParticipation created under an older layout to the
+    /// current size and stamp the current version (anyone; payer tops up rent).
+    /// Runs from before v12 pass their vault as the first remaining account to record the mint.
+    /// Accounts from before platform instances (Platform and Run v1) live at seeds no instruction
+    /// derives any more and are rejected; moving to platform instances was a redeploy
+    pub fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+        let target = ctx.accounts.target.to_account_info();
+        require_keys_eq!(*target.owner, crate::ID, ErrorCode::InvalidMigrationTarget);
+
+        // Oldest supported layout size, current size, version byte offset, current version
+        let (min_len, new_len, version_offset, version) = {
+            let data = target.try_borrow_data()?;
+            require!(data.len() >= 8, ErrorCode::InvalidMigrationTarget);
+            let discriminator = &data[..8];
+            if discriminator == Platform::DISCRIMINATOR {
+                (Platform::MIN_MIGRATABLE_LEN, Platform::LEN, Platform::VERSION_OFFSET, PLATFORM_VERSION)
+            } else if discriminator == Run::DISCRIMINATOR {
+                (Run::MIN_MIGRATABLE_LEN, Run::LEN, Run::VERSION_OFFSET, RUN_VERSION)
+            } else if discriminator == UserParticipation::DISCRIMINATOR {
+                (
+                    UserParticipation::VERSION_OFFSET,
+                    UserParticipation::LEN,
+                    UserParticipation::VERSION_OFFSET,
+                    PARTICIPATION_VERSION,
+                )
+            } else {
+                return err!(ErrorCode::InvalidMigrationTarget);
+            }
+        };
+
+        let old_len = target.data_len();
+        require!(old_len >= min_len && old_len <= new_len, ErrorCode::InvalidMigrationTarget);
+        if old_len < new_len {
+            let rent_due = Rent::get()?
+                .minimum_balance(new_len)
+                .saturating_sub(target.lamports());
+            if rent_due > 0 {
+                let cpi_accounts = system_program::Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: target.clone(),
+                };
+                let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+                system_program::transfer(cpi_ctx, rent_due)?;
+            }
+            // New bytes are zeroed, so appended fields start at their defaults
+            target.resize(new_len)?;
+        }
+
+        let mut data = target.try_borrow_mut_data()?;
+        let old_version = data[version_offset];
+        require!(old_version <= version, ErrorCode::InvalidMigrationTarget);
+        data[version_offset] = version;
+        // v3 replaced the dispute and refund flags with statuses; their bytes stay reserved and zeroed
+        if data[..8] == *Run::DISCRIMINATOR && old_version < 3 {
+            if data[Run::REFUNDS_FLAG_OFFSET] != 0 {
+                data[Run::STATUS_OFFSET] = if data[Run::STATUS_OFFSET] == RunStatus::Waiting as u8 {
+                    RunStatus::Cancelled as u8
+                } else {
+                    RunStatus::Refunding as u8
+                };
+            } else if data[Run::DISPUTED_FLAG_OFFSET] != 0 {
+                data[Run::STATUS_OFFSET] = RunStatus::Disputed as u8;
+            }
+            data[Run::DISPUTED_FLAG_OFFSET] = 0;
+            data[Run::REFUNDS_FLAG_OFFSET] = 0;
+        }
+        // Runs before v11 all used 6-decimal USDC vaults
+        if data[..8] == *Run::DISCRIMINATOR && old_version < 11 {
+            data[Run::MINT_DECIMALS_OFFSET] = USDC_DECIMALS;
+        }
+        if data[..8] == *Run::DISCRIMINATOR && old_version < 12 {
+            if let Some(vault_info) = ctx.remaining_accounts.first() {
+                let platform = &data[Run::VERSION_OFFSET + 1..Run::VERSION_OFFSET + 33];
+                let (vault_key, _) = Pubkey::find_program_address(&[b"vault", platform, &data[8..16]], &crate::ID);
+                require_keys_eq!(vault_info.key(), vault_key, ErrorCode::InvalidMigrationTarget);
+                let vault = TokenAccount::try_deserialize(&mut &vault_info.try_borrow_data()?[..])?;
+                data[Run::MINT_OFFSET..Run::MINT_OFFSET + 32].copy_from_slice(vault.mint.as_ref());
+            }
+        }
+        // Runs before v16 paid a fixed, uncapped 1% per correct vote
+        if data[..8] == *Run::DISCRIMINATOR && old_version < 16 {
+            data[Run::BONUS_RATE_OFFSET..Run::BONUS_RATE_OFFSET + 2]
+                .copy_from_slice(&DEFAULT_BONUS_BPS_PER_CORRECT_VOTE.to_le_bytes());
+        }
+        // The round limit was never enforced before v19, so older runs keep no limit
+        if data[..8] == *Run::DISCRIMINATOR && old_version < 19 {
+            data[Run::MAX_VOTE_ROUNDS_OFFSET] = u8::MAX;
+        }
+        // Before v31 the exit penalty slot held every fee taken; keep only what settlement did not charge
+        if data[..8] == *Run::DISCRIMINATOR && old_version < 31 {
+            let read_u64 = |offset: usize| u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
+            let exit_penalties = read_u64(Run::EXIT_PENALTIES_OFFSET)
+                .saturating_sub(read_u64(Run::PLATFORM_FEE_OFFSET))
+                .saturating_sub(read_u64(Run::MANAGEMENT_FEE_OFFSET))
+                .saturating_sub(read_u64(Run::MANAGER_FEE_OFFSET));
+            data[Run::EXIT_PENALTIES_OFFSET..Run::EXIT_PENALTIES_OFFSET + 8]
+                .copy_from_slice(&exit_penalties.to_le_bytes());
+        }
+
+        msg!("Migrated account {} from v{} ({} bytes) to v{} ({} bytes)",
+            target.key(), old_version, old_len, version, new_len);
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Account Structures
+// ============================================================================
+
+#[account]
+pub struct Platform {
+    pub authority: Pubkey,           // Platform admin
+    pub platform_fee_bps: u16,       // Fee in basis points (1500 = 15%)
+    pub total_runs: u64,             // Total runs created
+    pub is_paused: bool,             // Emergency pause flag
+    pub bump: u8,                    // PDA bump
+    pub total_fees_collected: u64,   // Total fees collected across all runs
+    pub platform_fee_vault: Pubkey,  // Platform fee vault address
+    pub settlement_bond: u64,        // Bond the settler must post with each settlement
+    pub dispute_window_secs: i64,    // Seconds after settlement during which disputes are accepted
+    pub max_platform_tvl: u64,       // Cap on principal held across all runs (0 = no cap)
+    pub total_value_locked: u64,     // Principal currently held across all runs
+    pub stake_mint: Pubkey,          // Governance token accepted for staking (default = disabled)
+    pub min_priority_stake: u64,     // Locked stake required to deposit during priority windows
+    pub total_staked: u64,           // Governance tokens staked across all users
+    pub compliance_authority: Pubkey, // Issuer of KYC attestations (default = none)
+    pub version: u8,                 // Account layout version
+    pub platform_id: u64,            // Instance identifier in the platform PDA seeds
+    pub features: u64,               // Enabled FEATURE_* bits
+    pub exit_penalty_bps: u16,       // Penalty on mid-run emergency exits
+    pub exit_penalty_to_treasury: bool, // Penalty goes to the fee vault instead of staying with the run
+    pub revenue_vault: Pubkey,       // Stakers' revenue vault (default = revenue sharing disabled)
+    pub revenue_share_bps: u16,      // Portion of settlement fees routed to stakers
+    pub revenue_per_share: u128,     // Cumulative revenue per staked token, scaled by REVENUE_PRECISION
+    pub pending_revenue: u64,        // Revenue collected but not yet distributed
+    pub revenue_epoch: u64,          // Distributions so far
+    pub last_revenue_distribution: i64, // Unix timestamp of the last distribution
+    pub stake_tiers: [StakeTier; STAKE_TIER_COUNT], // Fee discounts by stake, ascending
+    pub ve_boost_weight: u64,        // ve_weight earning the full vote bonus boost (0 = boosts off)
+    pub governance_program: Pubkey,  // Realms program owning `authority` (default = key-controlled)
+    pub guardian: Pubkey,            // May pause the platform and runs, nothing else (default = none)
+    pub bridge_authority: Pubkey,    // Signer that relays verified CCTP/Wormhole deposits (default = bridging off)
+    pub total_deposited_lifetime: u64, // Deposits into runs since metrics were added
+    pub total_withdrawn_lifetime: u64, // Withdrawals, refunds and exits paid to users since metrics were added
+    pub active_runs: u32,            // Runs created and not yet settled or in refund mode
+    pub settlement_escrow_secs: i64, // Seconds settled funds stay frozen for auditors before withdrawals open
+    pub attestor: Pubkey,            // Must co-sign every settlement alongside the settler (default = none)
+    pub risk_oracle: Pubkey,         // Must sign a SettlementReport for every settlement (default = none)
+    pub max_active_runs: u32,        // Cap on active_runs (0 = no cap)
+    pub max_total_exposure: u64,     // Cap on total_exposure (0 = no cap)
+    pub total_exposure: u64,         // Capital of started runs not yet settled or in refund mode
+    pub settlement_committee: Pubkey, // Committee whose M-of-N approval every settlement needs (default = none)
+    pub authority_expires_at: i64,   // Admin instructions fail from this timestamp until a new authority is accepted (0 = never)
+    pub authority_rotation_secs: i64, // Lifetime given to each newly accepted authority (0 = keys do not expire)
+    pub pending_authority: Pubkey,   // Proposed successor awaiting accept_authority (default = none)
+}
+
+impl Platform {
+    /// Byte offset of `version`; fields added later go after it
+    pub const VERSION_OFFSET: usize = 8 + 32 + 2 + 8 + 1 + 1 + 8 + 32 + 8 + 8 + 8 + 8 + 32 + 8 + 8 + 32;
+    pub const LEN: usize = Self::VERSION_OFFSET + 1 + 8 + 8 + 2 + 1 + 32 + 2 + 16 + 8 + 8 + 8
+        + StakeTier::LEN * STAKE_TIER_COUNT + 8 + 32 + 32 + 32 + 8 + 8 + 4 + 8 + 32 + 32 + 4 + 8 + 8 + 32 + 8 + 8 + 32;
+    /// Size of the v2 layout, the first keyed by `platform_id` and the oldest that can be migrated
+    pub const MIN_MIGRATABLE_LEN: usize = Self::VERSION_OFFSET + 1 + 8;
+
+    /// Id the next created run gets; ids are assigned sequentially from 1
+    pub fn next_run_id(&self) -> Result<u64> {
+        Ok(self.total_runs.checked_add(1).ok_or(ErrorCode::ArithmeticOverflow)?)
+    }
+
+    /// Count a deposit into a run and add it to the platform TVL
+    pub fn record_deposit(&mut self, amount: u64) -> Result<()> {
+        self.add_value_locked(amount)?;
+        self.total_deposited_lifetime = self.total_deposited_lifetime
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Count tokens paid out of a run vault to a user
+    pub fn record_withdrawal(&mut self, amount: u64) -> Result<()> {
+        self.total_withdrawn_lifetime = self.total_withdrawn_lifetime
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Count a newly created run, enforcing the active run cap
+    pub fn begin_run(&mut self) -> Result<()> {
+        require!(
+            self.max_active_runs == 0 || self.active_runs < self.max_active_runs,
+            ErrorCode::ActiveRunCapReached
+        );
+        self.active_runs = self.active_runs
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Count a started run's capital toward the platform exposure, enforcing the exposure cap
+    pub fn add_exposure(&mut self, run: &mut Run) -> Result<()> {
+        let new_exposure = self.total_exposure
+            .checked_add(run.total_deposited)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        require!(
+            self.max_total_exposure == 0 || new_exposure <= self.max_total_exposure,
+            ErrorCode::PlatformExposureCapExceeded
+        );
+        self.total_exposure = new_exposure;
+        run.exposure = run.total_deposited;
+        Ok(())
+    }
+
+    /// Register a new participant's deposit in a run; the deposit that fills an auto-start run
+    /// starts it and counts its capital toward the platform exposure
+    pub fn admit_participant(
+        &mut self,
+        run: &mut Run,
+        page: &AccountLoader<ParticipantPage>,
+        participant: Pubkey,
+        page_bump: u8,
+        amount: u64,
+        now: i64,
+    ) -> Result<()> {
+        ParticipantPage::append(page, run, participant, page_bump)?;
+        if run.record_deposit(amount, now)? {
+            self.add_exposure(run)?;
+            msg!("Run #{} filled and auto-started with {} participants and {} USDC",
+                run.run_id, run.participant_count, run.ui(run.total_deposited));
+        }
+        Ok(())
+    }
+
+    /// A run reached Settled or a refund status; platforms migrated mid-run may undercount, so saturate
+    pub fn end_run(&mut self, run: &Run) {
+        self.active_runs = self.active_runs.saturating_sub(1);
+        self.total_exposure = self.total_exposure.saturating_sub(run.exposure);
+    }
+
+    /// Add principal to the platform TVL, enforcing the platform-wide cap
+    pub fn add_value_locked(&mut self, amount: u64) -> Result<()> {
+        let new_tvl = self.total_value_locked
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        require!(
+            self.max_platform_tvl == 0 || new_tvl <= self.max_platform_tvl,
+            ErrorCode::PlatformTvlCapExceeded
+        );
+        self.total_value_locked = new_tvl;
+        Ok(())
+    }
+
+    pub fn is_feature_enabled(&self, feature: u64) -> bool {
+        self.features & feature == feature
+    }
+
+    pub fn require_feature(&self, feature: u64) -> Result<()> {
+        require!(self.is_feature_enabled(feature), ErrorCode::FeatureDisabled);
+        Ok(())
+    }
+
+    /// Require the executing top-level instruction to belong to the governance program,
+    /// i.e. this call is a CPI from an approved Realms proposal
+    pub fn check_governance_caller(&self, instructions: &AccountInfo) -> Result<()> {
+        require!(self.governance_program != Pubkey::default(), ErrorCode::GovernanceNotConfigured);
+        let current = sysvar_instructions::load_current_index_checked(instructions)?;
+        let caller = sysvar_instructions::load_instruction_at_checked(current as usize, instructions)?;
+        require_keys_eq!(caller.program_id, self.governance_program, ErrorCode::NotCalledByGovernance);
+        Ok(())
+    }
+
+    /// With an attestor configured, settlements need its signature as well as the settler's
+    pub fn check_attestor(&self, signer: Option<Pubkey>) -> Result<()> {
+        if self.attestor != Pubkey::default() {
+            require!(signer == Some(self.attestor), ErrorCode::AttestorSignatureRequired);
+        }
+        Ok(())
+    }
+
+    /// Whether the authority key is within its lifetime; an expired key can still propose its
+    /// successor and pause, nothing else
+    pub fn is_authority_current(&self) -> bool {
+        self.authority_expires_at == 0
+            || Clock::get().is_ok_and(|clock| clock.unix_timestamp < self.authority_expires_at)
+    }
+
+    /// Install a new authority, starting its lifetime when rotation is enforced
+    pub fn rotate_authority(&mut self, new_authority: Pubkey, now: i64) -> Result<()> {
+        self.authority = new_authority;
+        self.pending_authority = Pubkey::default();
+        self.authority_expires_at = if self.authority_rotation_secs > 0 {
+            now.checked_add(self.authority_rotation_secs).ok_or(ErrorCode::ArithmeticOverflow)?
+        } else {
+            0
+        };
+        Ok(())
+    }
+
+    /// Pausing is open to the authority and the guardian
+    pub fn can_pause(&self, signer: Pubkey) -> bool {
+        signer == self.authority || (self.guardian != Pubkey::default() && signer == self.guardian)
+    }
+
+    /// Fee discount of the highest stake tier reached by `staked`
+    pub fn stake_discount_bps(&self, staked: u64) -> u16 {
+        self.stake_tiers
+            .iter()
+            .filter(|tier| tier.min_stake > 0 && staked >= tier.min_stake)
+            .map(|tier| tier.discount_bps)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+#[account]
+pub struct Run {
+    pub run_id: u64,                 // Unique run identifier
+    pub settler: Pubkey,             // Signer of the settlement, whose bond backs it (default = not settled)
+    pub status: RunStatus,           // Current status
+    pub total_deposited: u64,        // Total USDC deposited
+    pub final_balance: u64,          // Final balance after trading (after fee deduction)
+    pub platform_fee_amount: u64,    // Platform fee collected for this run
+    pub total_withdrawn: u64,        // Total amount withdrawn by users
+    pub withdrawn_count: u16,        // Number of users who have withdrawn
+    pub participant_count: u16,      // Number of participants
+    pub min_deposit: u64,            // Minimum deposit (e.g., 10 USDC)
+    pub max_deposit: u64,            // Maximum deposit (e.g., 100 USDC)
+    pub max_participants: u16,       // Max participants (e.g., 100)
+    pub created_at: i64,             // Unix timestamp
+    pub started_at: i64,             // Unix timestamp
+    pub ended_at: i64,               // Unix timestamp
+    pub bump: u8,                    // PDA bump
+    pub settlement_bond: u64,        // Bond posted by the settler
+    pub shares_hash: [u8; 32],       // Hash of the reported participant shares
+    pub dispute_deadline: i64,       // Disputes accepted until this timestamp
+    pub _reserved_disputed: u8,      // Former dispute flag (v1-v2), replaced by RunStatus::Disputed; kept zeroed
+    pub bond_resolved: bool,         // Bond has been released or slashed
+    pub total_paid_out: u64,         // All vault outflows since settlement
+    pub exit_penalties_collected: u64, // Exit penalties sent from the vault to the treasury
+    pub _reserved_refunds: u8,       // Former refund flag (v1-v2), replaced by the refundable statuses; kept zeroed
+    pub max_duration: i64,           // Seconds the run may stay active before settlement is due
+    pub is_paused: bool,             // Per-run pause flag
+    pub max_run_tvl: u64,            // Cap on total deposits for this run (0 = no cap)
+    pub min_participants: u16,       // Participants required to start
+    pub min_total_deposit: u64,      // Total deposits required to start
+    pub deposit_deadline: i64,       // Deposits accepted until this timestamp
+    pub auto_start: bool,            // Start automatically on the deposit that fills the run
+    pub waitlist_head: u64,          // Position of the next waitlist entry to promote
+    pub waitlist_tail: u64,          // Position assigned to the next waitlist entry
+    pub early_access_until: i64,     // Only locked stakers may deposit before this timestamp
+    pub requires_kyc: bool,          // Depositors must hold a valid KYC attestation
+    pub is_private: bool,            // Deposits require an invite code
+    pub version: u8,                 // Account layout version
+    pub platform: Pubkey,            // Platform instance that owns this run
+    pub deadline_extension: i64,     // Seconds the deposit deadline has been pushed back
+    pub profit_vesting_secs: i64,    // Profits vest linearly over this many seconds after settlement (0 = immediate)
+    pub vesting_outstanding: u64,    // Vesting profits not yet claimed, reserved in the vault
+    pub management_fee_bps_per_year: u16, // Annualized fee on deposits, accrued over the active period
+    pub management_fee_amount: u64,  // Management fee taken at settlement
+    pub exempt_deposits: u64,        // Deposits of fee-exempt participants, snapshotted at settlement
+    pub manager: Pubkey,             // Strategy operator running this run (default = platform authority only)
+    pub manager_fee_bps: u16,        // Manager's cut of profit, taken at settlement alongside the platform fee
+    pub manager_fee_amount: u64,     // Manager fee taken at settlement
+    pub series_id: u64,              // Recurring series the run belongs to (0 = standalone)
+    pub mint_decimals: u8,           // Decimals of the vault mint, set when the vault is created
+    pub mint: Pubkey,                // Vault mint, set when the vault is created
+    pub claim_window_secs: i64,      // Shares must be claimed within this long of settlement (0 = no deadline)
+    pub share_mint: Pubkey,          // Receipt token of a share-token run (default = participation accounting)
+    pub open_ended: bool,            // Share-token run that takes deposits and redemptions at NAV while trading
+    pub marked_nav: u64,             // Total assets of an open-ended run as last marked by the operator
+    pub nav_marked_at: i64,          // Unix timestamp of the last NAV mark
+    pub tier: Pubkey,                // Tier preset the run was created from (default = custom parameters)
+    pub bonus_bps_per_correct_vote: u16, // Vote-accuracy bonus on the profit share per correct vote
+    pub max_bonus_bps: u16,          // Cap on the vote-accuracy bonus before any ve boost (0 = uncapped)
+    pub registered_participants: u32, // Participants appended to the run's ParticipantPages
+    pub bonus_mode: BonusMode,       // How vote-accuracy bonuses are funded
+    pub vote_bonus_weight: u128,     // Sum of deposit × bonus bps over participants (zero-sum mode)
+    pub vote_penalty_weight: u128,   // Sum of deposit × penalty bps over participants (zero-sum mode)
+    pub max_vote_rounds: u8,         // Votes a participant can be credited with
+    pub min_vote_deposit: u64,       // Deposit needed to vote and earn the bonus (0 = any deposit)
+    pub voting_open_until: i64,      // End of the current vote round; delegations are frozen until then
+    pub agent: Pubkey,               // Bonded AI agent trading the run (default = none)
+    pub agent_at_fault: bool,        // A fault was found against the agent and it can be slashed
+    pub strategy_count: u8,          // Agent strategies with their own vault, one per vote option
+    pub max_rebalance_bps_per_round: u16, // Capital that may move between strategies per vote round, of total deposits (0 = none)
+    pub liquidity_buffer_bps: u16,   // Share of a strategy's balance a rebalance must leave in place
+    pub escrow_until: i64,           // Settled funds frozen until this timestamp
+    pub in_escrow: bool,             // Withdrawals wait for finalize_settlement
+    pub max_drawdown_bps: u16,       // Marked loss on total deposits that pauses the run (0 = no breaker)
+    pub breaker_tripped: bool,       // Paused by the drawdown breaker until the authority resumes it
+    pub stop_loss_nav: u64,          // Marked NAV below which anyone may stop the run into refunds (0 = none)
+    pub exposure: u64,               // Capital counted toward the platform's total_exposure when the run started
+    pub shares_recorded: u16,        // Cursor of a staged settlement: shares written to the share table so far
+    pub lookup_table: Pubkey,        // Address lookup table owned by the run (default = none)
+    pub unconverted_deposits: u64,   // USD notional of stablecoin deposits still in their sub-vaults
+}
+
+impl Run {
+    /// Byte offset of `version`; fields added later go after it
+    pub const VERSION_OFFSET: usize = Self::REFUNDS_FLAG_OFFSET + 1 + 8 + 1 + 8 + 2 + 8 + 8 + 1 + 8 + 8 + 8 + 1 + 1;
+    /// Byte offset of `status`
+    pub const STATUS_OFFSET: usize = 8 + 8 + 32;
+    /// Byte offset of the reserved former dispute flag, folded into `status` when v1-v2 runs are migrated
+    pub const DISPUTED_FLAG_OFFSET: usize = Self::STATUS_OFFSET + 1 + 8 + 8 + 8 + 8 + 2 + 2 + 8 + 8 + 2 + 8 + 8 + 8 + 1
+        + 8 + 32 + 8;
+    /// Byte offset of the reserved former refund flag, folded into `status` when v1-v2 runs are migrated
+    pub const REFUNDS_FLAG_OFFSET: usize = Self::DISPUTED_FLAG_OFFSET + 1 + 1 + 8 + 8;
+    pub const LEN: usize = Self::MAX_VOTE_ROUNDS_OFFSET + 1 + 8 + 8 + 32 + 1 + 1 + 2 + 2 + 8 + 1 + 2 + 1 + 8 + 8 + 2 + 32 + 8;
+    /// Byte offset of `mint_decimals`, backfilled when older runs are migrated
+    pub const MINT_DECIMALS_OFFSET: usize = Self::VERSION_OFFSET + 1 + 32 + 8 + 8 + 8 + 2 + 8 + 8 + 32 + 2 + 8 + 8;
+    /// Byte offset of `mint`, backfilled from the run vault when older runs are migrated
+    pub const MINT_OFFSET: usize = Self::MINT_DECIMALS_OFFSET + 1;
+    /// Byte offset of `bonus_bps_per_correct_vote`, backfilled when older runs are migrated
+    pub const BONUS_RATE_OFFSET: usize = Self::MINT_OFFSET + 32 + 8 + 32 + 1 + 8 + 8 + 32;
+    /// Byte offset of `max_vote_rounds`, backfilled when older runs are migrated
+    pub const MAX_VOTE_ROUNDS_OFFSET: usize = Self::BONUS_RATE_OFFSET + 2 + 2 + 4 + 1 + 16 + 16;
+    /// Byte offsets of the fee amounts, used to split `exit_penalties_collected` out of the fee
+    /// total older runs kept in its slot
+    pub const PLATFORM_FEE_OFFSET: usize = Self::STATUS_OFFSET + 1 + 8 + 8;
+    pub const EXIT_PENALTIES_OFFSET: usize = Self::REFUNDS_FLAG_OFFSET - 8;
+    pub const MANAGEMENT_FEE_OFFSET: usize = Self::VERSION_OFFSET + 1 + 32 + 8 + 8 + 8 + 2;
+    pub const MANAGER_FEE_OFFSET: usize = Self::MANAGEMENT_FEE_OFFSET + 8 + 8 + 32 + 2;
+    /// Size of the v2 layout, the first keyed by platform and the oldest that can be migrated in place
+    pub const MIN_MIGRATABLE_LEN: usize = Self::VERSION_OFFSET + 1 + 32;
+
+    /// Adopt the vault mint's decimals, rescaling deposit limits that were quoted in USDC units
+    pub fn set_mint_decimals(&mut self, decimals: u8) -> Result<()> {
+        let from = self.mint_decimals;
+        self.min_deposit = rescale_amount(self.min_deposit, from, decimals)?;
+        self.max_deposit = rescale_amount(self.max_deposit, from, decimals)?;
+        self.max_run_tvl = rescale_amount(self.max_run_tvl, from, decimals)?;
+        self.min_total_deposit = rescale_amount(self.min_total_deposit, from, decimals)?;
+        self.min_vote_deposit = rescale_amount(self.min_vote_deposit, from, decimals)?;
+        self.stop_loss_nav = rescale_amount(self.stop_loss_nav, from, decimals)?;
+        require!(self.min_deposit > 0, ErrorCode::InvalidDepositAmount);
+        self.mint_decimals = decimals;
+        Ok(())
+    }
+
+    /// When unclaimed shares become forfeit, if the run has a claim window
+    pub fn claim_deadline(&self) -> Option<i64> {
+        (self.claim_window_secs > 0 && self.ended_at > 0)
+            .then(|| self.ended_at.saturating_add(self.claim_window_secs))
+    }
+
+    pub fn check_claim_open(&self, now: i64) -> Result<()> {
+        require!(!self.in_escrow, ErrorCode::SettlementInEscrow);
+        // Nothing leaves the vault while the settlement can still be disputed
+        require!(self.bond_resolved || now > self.dispute_deadline, ErrorCode::DisputeWindowOpen);
+        if let Some(deadline) = self.claim_deadline() {
+            require!(now <= deadline, ErrorCode::ClaimDeadlinePassed);
+        }
+        Ok(())
+    }
+
+    /// Display form of an amount of the run's mint
+    pub fn ui(&self, amount: u64) -> UiAmount {
+        UiAmount { amount, decimals: self.mint_decimals }
+    }
+
+    /// Checks for deposits made on a user's behalf by a relayer or crank; those cannot present invite codes
+    pub fn check_delegated_deposit(
+        &self,
+        platform: &Platform,
+        blacklist_entry: &AccountInfo,
+        stake: &Option<Account<Stake>>,
+        kyc_attestation: Option<&KycAttestation>,
+        amount: u64,
+        now: i64,
+    ) -> Result<()> {
+        require!(!platform.is_paused, ErrorCode::PlatformPaused);
+        BlacklistEntry::check_not_listed(blacklist_entry)?;
+        self.check_deposit(amount, now)?;
+        require!(self.waitlist_head == self.waitlist_tail, ErrorCode::WaitlistNotEmpty);
+        require!(!self.is_private, ErrorCode::InviteRequired);
+
+        if now < self.early_access_until {
+            let staked = Stake::priority_amount_of(stake, self.early_access_until);
+            require!(
+                staked > 0 && staked >= platform.min_priority_stake,
+                ErrorCode::EarlyAccessOnly
+            );
+        }
+
+        if self.requires_kyc {
+            KycAttestation::check(kyc_attestation, platform.compliance_authority, now)?;
+        }
+        Ok(())
+    }
+
+    /// Check that the run's vault exists as a token account of the run mint owned by the run PDA,
+    /// so a run whose vault was never created fails with a clear error
+    pub fn check_vault(&self, run_key: Pubkey, vault: &AccountInfo) -> Result<()> {
+        require!(
+            *vault.owner == token::ID && !vault.data_is_empty(),
+            ErrorCode::VaultNotInitialized
+        );
+        let vault = TokenAccount::try_deserialize(&mut &vault.try_borrow_data()?[..])
+            .map_err(|_| error!(ErrorCode::VaultNotInitialized))?;
+        require!(
+            vault.mint == self.mint && vault.owner == run_key,
+            ErrorCode::VaultNotInitialized
+        );
+        Ok(())
+    }
+
+    /// Whether deposits are represented by share tokens instead of participation accounts
+    pub fn is_share_run(&self) -> bool {
+        self.share_mint != Pubkey::default()
+    }
+
+    /// Shares minted for `assets` at the current vault NAV (1:1 while the vault is empty)
+    pub fn convert_to_shares(assets: u64, total_assets: u64, supply: u64) -> Result<u64> {
+        if supply == 0 || total_assets == 0 {
+            return Ok(assets);
+        }
+        Ok((assets as u128)
+            .checked_mul(supply as u128)
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+            .checked_div(total_assets as u128)
+            .ok_or(ErrorCode::ArithmeticOverflow)? as u64)
+    }
+
+    /// Assets paid for burning `shares` at the current vault NAV, rounding down
+    pub fn convert_to_assets(shares: u64, total_assets: u64, supply: u64) -> Result<u64> {
+        require!(shares <= supply, ErrorCode::InsufficientShares);
+        Ok((shares as u128)
+            .checked_mul(total_assets as u128)
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+            .checked_div(supply.max(1) as u128)
+            .ok_or(ErrorCode::ArithmeticOverflow)? as u64)
+    }
+
+    /// Record a NAV mark and return the drawdown on total deposits; breaching `max_drawdown_bps`
+    /// pauses the run and puts its agent at fault
+    pub fn record_mark(&mut self, nav: u64, now: i64) -> u64 {
+        self.marked_nav = nav;
+        self.nav_marked_at = now;
+        let drawdown_bps = (self.total_deposited.saturating_sub(nav) as u128 * 10000
+            / self.total_deposited.max(1) as u128) as u64;
+        if self.max_drawdown_bps > 0 && drawdown_bps >= self.max_drawdown_bps as u64 && !self.breaker_tripped {
+            self.is_paused = true;
+            self.breaker_tripped = true;
+            self.agent_at_fault = self.agent != Pubkey::default();
+        }
+        drawdown_bps
+    }
+
+    /// Whether deposits and redemptions are priced off the operator's NAV mark rather than the vault
+    pub fn uses_marked_nav(&self) -> bool {
+        self.open_ended && self.status == RunStatus::Active
+    }
+
+    /// Total assets behind the share supply: the fresh NAV mark while an open-ended run trades,
+    /// otherwise the vault balance
+    pub fn nav_assets(&self, vault_amount: u64, now: i64) -> Result<u64> {
+        if !self.uses_marked_nav() {
+            return Ok(vault_amount);
+        }
+        require!(now.saturating_sub(self.nav_marked_at) <= MAX_NAV_AGE_SECS, ErrorCode::StaleNav);
+        Ok(self.marked_nav)
+    }
+
+    /// Principal released by redeeming `shares`; fixed runs mint shares 1:1 with principal
+    pub fn principal_of(&self, shares: u64, supply: u64) -> Result<u64> {
+        if !self.open_ended {
+            return Ok(shares);
+        }
+        Ok((self.total_deposited as u128)
+            .checked_mul(shares as u128)
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+            .checked_div(supply.max(1) as u128)
+            .ok_or(ErrorCode::ArithmeticOverflow)? as u64)
+    }
+
+    /// ParticipantPage the next registered participant is appended to
+    pub fn participant_page_index(&self) -> u32 {
+        self.registered_participants / PARTICIPANT_PAGE_SIZE as u32
+    }
+
+    /// Whether anyone has deposited, through participations or share tokens
+    pub fn has_deposits(&self) -> bool {
+        self.participant_count > 0 || (self.is_share_run() && self.total_deposited > 0)
+    }
+
+    /// The platform's current authority, while its key has not expired, or the run's manager may
+    /// start, trade and settle the run
+    pub fn is_operator(&self, platform: &Platform, key: Pubkey) -> bool {
+        (key == platform.authority && platform.is_authority_current())
+            || (self.manager != Pubkey::default() && key == self.manager)
+    }
+
+    /// Settled share owed to a participant, including the vote-accuracy bonus on profit.
+    /// The last withdrawer takes whatever is left so no rounding dust stays in the vault
+    pub fn settled_share(
+        &self,
+        participation: &UserParticipation,
+        vault_amount: u64,
+        bonuses_enabled: bool,
+        vote_boost_bps: u64,
+    ) -> Result<u64> {
+        let deposit_amount = participation.deposit_amount;
+        self.check_vote_stats(participation.correct_votes, participation.total_votes)?;
+        // Profits still vesting for earlier withdrawers are not available to this one
+        let available = vault_amount
+            .checked_sub(self.vesting_outstanding)
+            .ok_or(ErrorCode::VaultInsolvent)?;
+
+        if self.withdrawn_count + 1 == self.participant_count {
+            return Ok(available);
+        }
+
+        // Calculate proportional share for non-last users
+        let base_share = self.base_share(deposit_amount, participation.fee_exempt)?;
+
+        // Calculate bonus ONLY if there was profit (FIX #3)
+        let user_share = if self.final_balance > self.total_deposited && bonuses_enabled {
+            // Calculate this user's share of the profit
+            let profit_ratio = self.final_balance
+                .checked_sub(self.total_deposited)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+            
+            let user_profit_share = (deposit_amount as u128)
+                .checked_mul(profit_ratio as u128)
+                .ok_or(ErrorCode::ArithmeticOverflow)?
+                .checked_div(self.total_deposited as u128)
+                .ok_or(ErrorCode::ArithmeticOverflow)? as u64;
+
+            if self.bonus_mode == BonusMode::ZeroSum {
+                let user_share = self.zero_sum_share(participation, base_share, user_profit_share)?;
+                return participation.with_tips(user_share, available);
+            }
+
+            // Apply bonus to profit share only (the run's rate per correct vote, capped, boosted by ve_weight)
+            let correct_vote_bonus_bps = self.vote_bps(participation.correct_votes)
+                .checked_mul(10000 + vote_boost_bps)
+                .ok_or(ErrorCode::ArithmeticOverflow)?
+                / 10000;
+            
+            let bonus = (user_profit_share as u128)
+                .checked_mul(correct_vote_bonus_bps as u128)
+                .ok_or(ErrorCode::ArithmeticOverflow)?
+                .checked_div(10000)
+                .ok_or(ErrorCode::ArithmeticOverflow)? as u64;
+
+            base_share
+                .checked_add(bonus)
+                .ok_or(ErrorCode::ArithmeticOverflow)?
+        } else {
+            // No bonus on losses or while bonuses are switched off
+            base_share
+        };
+
+        // Ensure we don't exceed vault balance
+        participation.with_tips(user_share, available)
+    }
+
+    /// Zero-sum settlement: correct voters' bonuses are funded by proportional cuts to incorrect
+    /// voters' profit shares, both scaled to whichever side is smaller so the totals cancel out.
+    /// Vote-escrow boosts do not apply in this mode
+    fn zero_sum_share(
+        &self,
+        participation: &UserParticipation,
+        base_share: u64,
+        user_profit_share: u64,
+    ) -> Result<u64> {
+        let funded = self.vote_bonus_weight.min(self.vote_penalty_weight);
+        let mut user_share = base_share as u128;
+        if funded > 0 {
+            let bonus_bps = self.vote_bps(participation.correct_votes) as u128;
+            let penalty_bps = self.vote_bps(participation.total_votes.saturating_sub(participation.correct_votes)) as u128;
+            let bonus = (user_profit_share as u128)
+                .checked_mul(bonus_bps)
+                .and_then(|v| v.checked_mul(funded))
+                .ok_or(ErrorCode::ArithmeticOverflow)?
+                / (self.vote_bonus_weight * 10000);
+            // Round cuts up and bonuses down so rounding never leaves the vault short
+            let penalty = (user_profit_share as u128)
+                .checked_mul(penalty_bps)
+                .and_then(|v| v.checked_mul(funded))
+                .ok_or(ErrorCode::ArithmeticOverflow)?
+                .div_ceil(self.vote_penalty_weight * 10000);
+            user_share = (user_share + bonus).saturating_sub(penalty);
+        }
+        Ok(u64::try_from(user_share).map_err(|_| ErrorCode::ArithmeticOverflow)?)
+    }
+
+    /// Bonus (or zero-sum penalty) in bps of the profit share for `votes` votes, at the run's
+    /// rate and cap
+    pub fn vote_bps(&self, votes: u8) -> u64 {
+        let bps = votes as u64 * self.bonus_bps_per_correct_vote as u64;
+        if self.max_bonus_bps > 0 {
+            bps.min(self.max_bonus_bps as u64)
+        } else {
+            bps
+        }
+    }
+
+    /// Whether a deposit is large enough to vote and earn the bonus
+    pub fn is_vote_eligible(&self, deposit_amount: u64) -> bool {
+        deposit_amount >= self.min_vote_deposit
+    }
+
+    /// Only one vote round is open at a time, and delegations cannot move while it is
+    pub fn check_no_open_round(&self, now: i64) -> Result<()> {
+        require!(now >= self.voting_open_until, ErrorCode::VotingOpen);
+        Ok(())
+    }
+
+    /// Vote counts must fit within the run's rounds
+    pub fn check_vote_stats(&self, correct_votes: u8, total_votes: u8) -> Result<()> {
+        require!(
+            correct_votes <= total_votes && total_votes <= self.max_vote_rounds,
+            ErrorCode::InvalidVoteStats
+        );
+        Ok(())
+    }
+
+    /// Replace a participant's vote contribution to the zero-sum bonus and penalty weights
+    pub fn record_vote_stats(
+        &mut self,
+        participation: &UserParticipation,
+        correct_votes: u8,
+        total_votes: u8,
+    ) -> Result<()> {
+        let deposit = participation.deposit_amount as u128;
+        let incorrect = |correct: u8, total: u8| total.saturating_sub(correct);
+        self.vote_bonus_weight = self.vote_bonus_weight
+            .saturating_sub(deposit * self.vote_bps(participation.correct_votes) as u128)
+            .checked_add(deposit * self.vote_bps(correct_votes) as u128)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        self.vote_penalty_weight = self.vote_penalty_weight
+            .saturating_sub(deposit * self.vote_bps(incorrect(participation.correct_votes, participation.total_votes)) as u128)
+            .checked_add(deposit * self.vote_bps(incorrect(correct_votes, total_votes)) as u128)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Split a share into (paid now, profit left vesting); principal is always paid now,
+    /// and with vesting on profit is released later through claim_vested
+    pub fn split_vesting(&self, participation: &UserParticipation, share: u64) -> (u64, u64) {
+        let vesting_profit = if self.profit_vesting_secs > 0 {
+            share.saturating_sub(participation.deposit_amount)
+        } else {
+            0
+        };
+        (share - vesting_profit, vesting_profit)
+    }
+
+    /// Mark a participant as withdrawn and track what left the vault
+    pub fn record_payout(
+        &mut self,
+        participation: &mut UserParticipation,
+        share: u64,
+        vesting_profit: u64,
+    ) -> Result<()> {
+        let paid_now = share - vesting_profit;
+        participation.final_share = share;
+        participation.withdrawn = true;
+        participation.vesting_total = vesting_profit;
+
+        // Update run withdrawal tracking (FIX #2)
+        self.total_withdrawn = self.total_withdrawn
+            .checked_add(paid_now)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        self.total_paid_out = self.total_paid_out
+            .checked_add(paid_now)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        self.vesting_outstanding = self.vesting_outstanding
+            .checked_add(vesting_profit)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        self.withdrawn_count = self.withdrawn_count
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Pro-rata share of the settled balance; the performance fee is borne only by non-exempt deposits
+    pub fn base_share(&self, deposit_amount: u64, fee_exempt: bool) -> Result<u64> {
+        if self.exempt_deposits == 0 {
+            return Ok((deposit_amount as u128)
+                .checked_mul(self.final_balance as u128)
+                .ok_or(ErrorCode::ArithmeticOverflow)?
+                .checked_div(self.total_deposited as u128)
+                .ok_or(ErrorCode::ArithmeticOverflow)? as u64);
+        }
+
+        let before_fee = self.final_balance
+            .checked_add(self.platform_fee_amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        let gross_share = (deposit_amount as u128)
+            .checked_mul(before_fee as u128)
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+            .checked_div(self.total_deposited as u128)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        let fee_share = if fee_exempt {
+            0
+        } else {
+            (deposit_amount as u128)
+                .checked_mul(self.platform_fee_amount as u128)
+                .ok_or(ErrorCode::ArithmeticOverflow)?
+                .checked_div(self.total_deposited.saturating_sub(self.exempt_deposits) as u128)
+                .ok_or(ErrorCode::ArithmeticOverflow)?
+        };
+        Ok(gross_share.saturating_sub(fee_share) as u64)
+    }
+
+    /// Pro-rata refund of the refundable balance; the last claimant takes the remainder so no
+    /// rounding dust is stranded. Stablecoin deposits never converted are refunded from their
+    /// sub-vault instead, so they do not share in the vault
+    pub fn refund_share(&self, deposit_amount: u64, vault_amount: u64) -> Result<u64> {
+        if self.withdrawn_count + 1 == self.participant_count {
+            return Ok(vault_amount);
+        }
+        Ok((deposit_amount as u128)
+            .checked_mul(self.final_balance as u128)
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+            .checked_div(self.total_deposited.saturating_sub(self.unconverted_deposits) as u128)
+            .ok_or(ErrorCode::ArithmeticOverflow)? as u64)
+    }
+
+    /// Every fee taken out of the vault: the fees charged at settlement plus exit penalties sent
+    /// to the treasury
+    pub fn total_fees_collected(&self) -> u64 {
+        self.platform_fee_amount
+            .saturating_add(self.management_fee_amount)
+            .saturating_add(self.manager_fee_amount)
+            .saturating_add(self.exit_penalties_collected)
+    }
+
+    /// After settlement, everything paid out plus what is left must equal the settled balance
+    pub fn check_solvency(&self, vault_amount: u64) -> Result<()> {
+        let accounted = self.total_paid_out
+            .checked_add(vault_amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        require!(accounted == self.final_balance, ErrorCode::VaultInsolvent);
+        Ok(())
+    }
+
+    /// Checks shared by every path that adds a participant to the run
+    pub fn check_deposit(&self, amount: u64, now: i64) -> Result<()> {
+        let open_ended_live = self.open_ended && self.status == RunStatus::Active;
+        require!(
+            self.status == RunStatus::Waiting || open_ended_live,
+            ErrorCode::RunNotInWaitingPhase
+        );
+        require!(!self.is_paused, ErrorCode::RunPaused);
+        // Open-ended runs keep taking deposits once trading, past the initial deposit window
+        require!(open_ended_live || now <= self.deposit_deadline, ErrorCode::DepositWindowClosed);
+        require!(amount >= self.min_deposit, ErrorCode::DepositTooLow);
+        require!(amount <= self.max_deposit, ErrorCode::DepositTooHigh);
+        require!(self.participant_count < self.max_participants, ErrorCode::RunFull);
+
+        let new_total = self.total_deposited
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        require!(
+            self.max_run_tvl == 0 || new_total <= self.max_run_tvl,
+            ErrorCode::RunTvlCapExceeded
+        );
+        Ok(())
+    }
+
+    /// Add a participant's deposit to the run totals; returns true if this auto-started the run
+    pub fn record_deposit(&mut self, amount: u64, now: i64) -> Result<bool> {
+        require!(!self.is_share_run(), ErrorCode::ShareTokenRun);
+        self.total_deposited = self.total_deposited
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        self.participant_count = self.participant_count
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        // The deposit that fills an auto-start run starts it immediately
+        if self.auto_start && self.is_full() && self.is_quorum_met() {
+            self.start(now)?;
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    /// Whether a queued deposit can never be admitted as it stands: past the deposit deadline,
+    /// outside the deposit limits, or over the run or platform TVL cap
+    pub fn rejects_waitlisted(&self, platform: &Platform, amount: u64, now: i64) -> bool {
+        now > self.deposit_deadline
+            || amount < self.min_deposit
+            || amount > self.max_deposit
+            || (self.max_run_tvl > 0 && self.total_deposited.saturating_add(amount) > self.max_run_tvl)
+            || (platform.max_platform_tvl > 0
+                && platform.total_value_locked.saturating_add(amount) > platform.max_platform_tvl)
+    }
+
+    /// Whether the run has enough participants and capital to start
+    pub fn is_quorum_met(&self) -> bool {
+        self.participant_count >= self.min_participants
+            && self.total_deposited >= self.min_total_deposit
+    }
+
+    /// Whether the run has hit its participant limit or TVL cap
+    pub fn is_full(&self) -> bool {
+        self.participant_count >= self.max_participants
+            || (self.max_run_tvl > 0 && self.total_deposited >= self.max_run_tvl)
+    }
+
+    /// Move to `next`, rejecting transitions the state machine does not allow
+    pub fn transition_to(&mut self, next: RunStatus) -> Result<()> {
+        require!(self.status.can_transition_to(next), ErrorCode::InvalidStatusTransition);
+        self.status = next;
+        Ok(())
+    }
+
+    /// Move the run from Waiting to Active
+    pub fn start(&mut self, now: i64) -> Result<()> {
+        self.transition_to(RunStatus::Active)?;
+        self.started_at = now;
+        Ok(())
+    }
+
+    /// Freeze the run in a refundable status, snapshotting the vault so every claim uses the same denominator
+    pub fn enter_refund_mode(&mut self, status: RunStatus, vault_balance: u64, now: i64) -> Result<()> {
+        require!(status.is_refundable(), ErrorCode::InvalidStatusTransition);
+        self.transition_to(status)?;
+        self.final_balance = vault_balance;
+        self.total_paid_out = 0;
+        self.ended_at = now;
+        Ok(())
+    }
+}
+
+#[account]
+pub struct UserParticipation {
+    pub user: Pubkey,                // User wallet
+    pub run_id: u64,                 // Associated run
+    pub deposit_amount: u64,         // Amount deposited
+    pub final_share: u64,            // Final share received
+    pub withdrawn: bool,             // Withdrawal status
+    pub correct_votes: u8,           // Number of correct votes
+    pub total_votes: u8,             // Total votes cast
+    pub bump: u8,                    // PDA bump
+    pub version: u8,                 // Account layout version
+    pub vesting_total: u64,          // Profit vesting after withdrawal
+    pub vesting_claimed: u64,        // Vested profit already claimed
+    pub fee_exempt: bool,            // Exempt from the performance fee, snapshotted at settlement
+    pub deposit_mint: Pubkey,        // Stablecoin deposited in place of USDC (default = USDC)
+    pub sponsored_rent: u64,         // Rent reimbursed by the rent treasury, returned to it on close
+    pub vote_weight: u64,            // Deposit-based vote weight, snapshotted once the run starts (0 = not yet)
+    pub vote_delegate: Pubkey,       // Participant voting with this weight (default = votes itself)
+    pub delegated_weight: u64,       // Vote weight other participants delegated to this one
+    pub migrate_to_run: u64,         // Run the user agreed to roll their settled share into (0 = none)
+    pub frozen: bool,                // Held by the admin; no funds leave until unfrozen
+    pub tips_sent: u64,              // Settled-share value tipped to other participants
+    pub tips_received: u64,          // Settled-share value tipped by other participants
+}
+
+impl UserParticipation {
+    /// Byte offset of `version`; fields added later go after it
+    pub const VERSION_OFFSET: usize = 8 + 32 + 8 + 8 + 8 + 1 + 1 + 1 + 1;
+    pub const LEN: usize = Self::VERSION_OFFSET + 1 + 8 + 8 + 1 + 32 + 8 + 8 + 32 + 8 + 8 + 1 + 8 + 8;
+
+    /// Initialize a freshly created participation with the user's deposit
+    pub fn record_deposit(&mut self, user: Pubkey, run_id: u64, amount: u64, bump: u8) {
+        self.user = user;
+        self.run_id = run_id;
+        self.deposit_amount = amount;
+        self.final_share = 0;
+        self.withdrawn = false;
+        self.correct_votes = 0;
+        self.total_votes = 0;
+        self.bump = bump;
+        self.version = PARTICIPATION_VERSION;
+        self.vesting_total = 0;
+        self.vesting_claimed = 0;
+        self.fee_exempt = false;
+        self.deposit_mint = Pubkey::default();
+        self.sponsored_rent = 0;
+        self.vote_weight = 0;
+        self.vote_delegate = Pubkey::default();
+        self.delegated_weight = 0;
+        self.migrate_to_run = 0;
+        self.frozen = false;
+        self.tips_sent = 0;
+        self.tips_received = 0;
+    }
+
+    /// Frozen participations cannot withdraw, refund, exit or roll over
+    pub fn check_not_frozen(&self) -> Result<()> {
+        require!(!self.frozen, ErrorCode::ParticipationFrozen);
+        Ok(())
+    }
+
+    /// Settled share moved by tips: plus what others tipped in, minus what was tipped away,
+    /// within what the vault has available
+    pub fn with_tips(&self, share: u64, available: u64) -> Result<u64> {
+        let share = share
+            .checked_add(self.tips_received)
+            .and_then(|share| share.checked_sub(self.tips_sent))
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        require!(share <= available, ErrorCode::InsufficientVaultFunds);
+        Ok(share)
+    }
+
+    /// Snapshot the vote weight of each participation in `accounts`, skipping any already recorded;
+    /// returns how many were recorded
+    pub fn snapshot_vote_weights<'info>(
+        accounts: &'info [AccountInfo<'info>],
+        platform: &Pubkey,
+        run_id: u64,
+    ) -> Result<u32> {
+        let run_id_bytes = run_id.to_le_bytes();
+        let mut recorded = 0u32;
+        for info in accounts {
+            let mut participation = Account::<UserParticipation>::try_from(info)?;
+            let participation_key = Pubkey::create_program_address(
+                &[
+                    b"participation",
+                    platform.as_ref(),
+                    run_id_bytes.as_ref(),
+                    participation.user.as_ref(),
+                    &[participation.bump],
+                ],
+                &crate::ID,
+            )
+            .map_err(|_| ErrorCode::InvalidParticipation)?;
+            require_keys_eq!(info.key(), participation_key, ErrorCode::InvalidParticipation);
+            if participation.vote_weight > 0 {
+                continue;
+            }
+            participation.vote_weight = participation.deposit_amount;
+            participation.exit(&crate::ID)?;
+            recorded += 1;
+        }
+        Ok(recorded)
+    }
+}
+
+#[account]
+pub struct WaitlistEntry {
+    pub run_id: u64,                 // Run being queued for
+    pub user: Pubkey,                // Queued wallet
+    pub amount: u64,                 // Escrowed deposit
+    pub position: u64,               // FIFO position in the run's queue
+    pub bump: u8,                    // PDA bump
+}
+
+impl WaitlistEntry {
+    pub const LEN: usize = 8 + 8 + 32 + 8 + 8 + 1;
+}
+
+/// Marks a wallet as queued for a run, so it cannot take a second place in the queue
+#[account]
+pub struct WaitlistMarker {
+    pub position: u64,               // Position of the wallet's waitlist entry
+    pub bump: u8,                    // PDA bump
+}
+
+impl WaitlistMarker {
+    pub const LEN: usize = 8 + 8 + 1;
+}
+
+#[account]
+pub struct Squad {
+    pub leader: Pubkey,              // Wallet that created the squad and commits its pool
+    pub run_id: u64,                 // Run the pool is deposited into
+    pub total_contributed: u64,      // Sum of member contributions; the squad participation's deposit
+    pub member_count: u16,           // Members holding a contribution
+    pub claimed_count: u16,          // Members paid out after the squad withdrew
+    pub committed: bool,             // Pool deposited into the run; contributions are locked
+    pub bump: u8,                    // PDA bump
+}
+
+impl Squad {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 2 + 2 + 1 + 1;
+
+    /// A member's cut of the squad's payout, pro rata to their contribution. The last member
+    /// to claim takes what is left so no rounding dust stays in the squad vault
+    pub fn member_share(&self, contribution: u64, squad_payout: u64, vault_amount: u64) -> Result<u64> {
+        if self.claimed_count + 1 == self.member_count {
+            return Ok(vault_amount);
+        }
+        Ok((contribution as u128)
+            .checked_mul(squad_payout as u128)
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+            .checked_div(self.total_contributed as u128)
+            .ok_or(ErrorCode::ArithmeticOverflow)? as u64)
+    }
+}
+
+#[account]
+pub struct SquadMember {
+    pub squad: Pubkey,               // Squad contributed to
+    pub member: Pubkey,              // Contributing wallet
+    pub contribution: u64,           // Amount pooled into the squad
+    pub bump: u8,                    // PDA bump
+}
+
+impl SquadMember {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 1;
+}
+
+#[account]
+pub struct Stake {
+    pub owner: Pubkey,               // Staker wallet
+    pub amount: u64,                 // Governance tokens staked
+    pub bump: u8,                    // PDA bump
+    pub revenue_debt: u128,          // amount × revenue_per_share already accounted for
+    pub unclaimed_revenue: u64,      // Accrued revenue awaiting claim_revenue
+    pub locked_amount: u64,          // Part of `amount` under a vote-escrow lock
+    pub lock_end: i64,               // Unix timestamp the lock expires
+}
+
+impl Stake {
+    pub const LEN: usize = 8 + 32 + 8 + 1 + 16 + 8 + 8 + 8;
+
+    /// Tokens staked by the wallet behind an optional stake account (0 when absent)
+    pub fn amount_of(stake: &Option<Account<Stake>>) -> u64 {
+        stake.as_ref().map_or(0, |stake| stake.amount)
+    }
+
+    /// Stake plus ve_weight, so locked tokens count up to double toward stake tiers
+    pub fn boosted_amount_of(stake: &Option<Account<Stake>>, now: i64) -> u64 {
+        stake.as_ref().map_or(0, |stake| stake.amount.saturating_add(stake.ve_weight(now)))
+    }
+
+    /// Stake counted for a priority window ending at `until`: only tokens locked at least that
+    /// long, so a stake opened and withdrawn around a single deposit does not qualify
+    pub fn priority_amount_of(stake: &Option<Account<Stake>>, until: i64) -> u64 {
+        stake.as_ref().map_or(0, |stake| if stake.lock_end >= until { stake.locked_amount } else { 0 })
+    }
+
+    /// Tokens that cannot be unstaked yet
+    pub fn locked_balance(&self, now: i64) -> u64 {
+        if now < self.lock_end { self.locked_amount } else { 0 }
+    }
+
+    /// Locked amount scaled by the remaining lock time, decaying linearly to zero at `lock_end`
+    pub fn ve_weight(&self, now: i64) -> u64 {
+        let remaining = self.lock_end.saturating_sub(now).clamp(0, MAX_LOCK_SECS);
+        ((self.locked_amount as u128) * remaining as u128 / MAX_LOCK_SECS as u128) as u64
+    }
+
+    /// Read a stake PDA passed without a typed account; None while the wallet has never staked
+    pub fn read_optional(info: &AccountInfo) -> Result<Option<Stake>> {
+        if info.data_is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(Stake::try_deserialize(&mut &info.try_borrow_data()?[..])?))
+    }
+
+    /// Boost applied to the vote-accuracy bonus, reaching MAX_VE_VOTE_BOOST_BPS at `ve_boost_weight`
+    pub fn vote_boost_bps(stake: Option<&Stake>, ve_boost_weight: u64, now: i64) -> u64 {
+        if ve_boost_weight == 0 {
+            return 0;
+        }
+        let ve_weight = stake.map_or(0, |stake| stake.ve_weight(now));
+        (MAX_VE_VOTE_BOOST_BPS as u128 * ve_weight.min(ve_boost_weight) as u128
+            / ve_boost_weight as u128) as u64
+    }
+
+    fn accrued(&self, revenue_per_share: u128) -> Result<u128> {
+        Ok((self.amount as u128)
+            .checked_mul(revenue_per_share)
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+            / REVENUE_PRECISION)
+    }
+
+    /// Fold revenue earned since the last checkpoint into `unclaimed_revenue`; call before `amount` changes
+    pub fn accrue_revenue(&mut self, revenue_per_share: u128) -> Result<()> {
+        let accrued = self.accrued(revenue_per_share)?;
+        let owed = accrued.saturating_sub(self.revenue_debt) as u64;
+        self.unclaimed_revenue = self.unclaimed_revenue
+            .checked_add(owed)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        self.revenue_debt = accrued;
+        Ok(())
+    }
+
+    /// Re-checkpoint after `amount` changed so past revenue is not earned on the new balance
+    pub fn checkpoint_revenue(&mut self, revenue_per_share: u128) -> Result<()> {
+        self.revenue_debt = self.accrued(revenue_per_share)?;
+        Ok(())
+    }
+}
+
+#[account]
+pub struct RentTreasury {
+    pub bump: u8,                    // PDA bump
+    pub total_sponsored: u64,        // Lamports reimbursed for participation rent
+    pub total_recaptured: u64,       // Lamports returned when sponsored participations closed
+}
+
+impl RentTreasury {
+    pub const LEN: usize = 8 + 1 + 8 + 8;
+
+    /// Reimburse the payer of a new participation's rent, keeping the treasury rent-exempt
+    pub fn sponsor(
+        treasury: &mut Account<RentTreasury>,
+        payer: &AccountInfo,
+        participation: &mut Account<UserParticipation>,
+    ) -> Result<()> {
+        let rent = participation.to_account_info().lamports();
+        let treasury_info = treasury.to_account_info();
+        let spare = treasury_info
+            .lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(Self::LEN));
+        require!(spare >= rent, ErrorCode::RentTreasuryEmpty);
+
+        treasury_info.sub_lamports(rent)?;
+        payer.add_lamports(rent)?;
+        participation.sponsored_rent = rent;
+        treasury.total_sponsored = treasury.total_sponsored
+            .checked_add(rent)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Return a sponsored participation's rent to the treasury before it is closed
+    pub fn recapture(
+        treasury: &mut Option<Account<RentTreasury>>,
+        participation: &mut Account<UserParticipation>,
+    ) -> Result<()> {
+        let rent = participation.sponsored_rent;
+        if rent == 0 {
+            return Ok(());
+        }
+        let treasury = treasury.as_mut().ok_or(ErrorCode::RentTreasuryRequired)?;
+        participation.to_account_info().sub_lamports(rent)?;
+        treasury.to_account_info().add_lamports(rent)?;
+        participation.sponsored_rent = 0;
+        treasury.total_recaptured = treasury.total_recaptured
+            .checked_add(rent)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
+}
+
+/// Lamports paid to whoever runs a permissionless crank, so expiry, refunds, auto-enrolment and
+/// settlement finalization keep moving without the official backend
+#[account]
+pub struct KeeperIncentive {
+    pub bump: u8,                    // PDA bump
+    pub bounty_lamports: u64,        // Paid per successful crank (0 = off)
+    pub total_paid: u64,             // Lamports paid to keepers so far
+}
+
+impl KeeperIncentive {
+    pub const LEN: usize = 8 + 1 + 8 + 8;
+
+    /// Pay the crank's caller the bounty while the vault can cover it and stay rent-exempt.
+    /// A missing or drained vault never blocks the crank itself
+    pub fn reward(incentive: &mut Option<Account<KeeperIncentive>>, keeper: &AccountInfo) -> Result<()> {
+        let incentive = match incentive.as_mut() {
+            Some(incentive) => incentive,
+            None => return Ok(()),
+        };
+        let bounty = incentive.bounty_lamports;
+        let incentive_info = incentive.to_account_info();
+        let spare = incentive_info
+            .lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(Self::LEN));
+        if bounty == 0 || spare < bounty {
+            return Ok(());
+        }
+
+        incentive_info.sub_lamports(bounty)?;
+        keeper.add_lamports(bounty)?;
+        incentive.total_paid = incentive.total_paid
+            .checked_add(bounty)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        msg!("Keeper {} paid {} lamports", keeper.key(), bounty);
+        Ok(())
+    }
+}
+
+#[account]
+pub struct KycAttestation {
+    pub user: Pubkey,                // Attested wallet
+    pub issuer: Pubkey,              // Compliance authority that issued it
+    pub issued_at: i64,              // Unix timestamp
+    pub expires_at: i64,             // Unix timestamp
+    pub revoked: bool,               // Revocation flag
+    pub bump: u8,                    // PDA bump
+}
+
+/// Zero-copy header; `capacity` fixed-size entries follow it at manual offsets so
+/// large tables are never Borsh-deserialized as a whole.
+#[account(zero_copy)]
+pub struct ShareTable {
+    pub run_id: u64,                 // Associated run
+    pub capacity: u32,               // Entries the allocated space can hold
+    pub len: u32,                    // Entries written at settlement
+    pub bump: u8,                    // PDA bump
+    pub _padding: [u8; 7],           // Explicit alignment padding
+}
+
+impl ShareTable {
+    pub const HEADER_LEN: usize = 8 + std::mem::size_of::<ShareTable>();
+    pub const ENTRY_LEN: usize = 32 + 8; // user + share_amount
+
+    pub fn space_for(entries: usize) -> usize {
+        Self::HEADER_LEN + entries * Self::ENTRY_LEN
+    }
+
+    pub fn capacity_for(data_len: usize) -> u32 {
+        (data_len.saturating_sub(Self::HEADER_LEN) / Self::ENTRY_LEN) as u32
+    }
+
+    /// Next account size when growing toward `participants` entries, bounded by the per-instruction realloc limit
+    pub fn next_len(current_len: usize, participants: u16) -> usize {
+        let target = Self::space_for(participants as usize);
+        target.min(current_len + MAX_PERMITTED_DATA_INCREASE).max(current_len)
+    }
+
+    fn entry_offset(index: usize) -> usize {
+        Self::HEADER_LEN + index * Self::ENTRY_LEN
+    }
+
+    /// Write reported shares into the entry region from `start` on and record how many are written
+    pub fn write_entries(loader: &AccountLoader<ShareTable>, start: usize, shares: &[ParticipantShare]) -> Result<()> {
+        let len = start + shares.len();
+        require!(len <= loader.load()?.capacity as usize, ErrorCode::ShareTableTooSmall);
+        {
+            let info = loader.as_ref();
+            let mut data = info.try_borrow_mut_data()?;
+            for (index, share) in shares.iter().enumerate() {
+                let offset = Self::entry_offset(start + index);
+                data[offset..offset + 32].copy_from_slice(share.user.as_ref());
+                data[offset + 32..offset + 40].copy_from_slice(&share.share_amount.to_le_bytes());
+            }
+        }
+        loader.load_mut()?.len = len as u32;
+        Ok(())
+    }
+
+    /// SHA-256 of the written entries as a Borsh-encoded Vec<ParticipantShare>, which the entry
+    /// layout matches byte for byte, so batched and single-transaction settlements hash alike
+    pub fn shares_hash(loader: &AccountLoader<ShareTable>) -> Result<[u8; 32]> {
+        let len = loader.load()?.len;
+        let data = loader.as_ref().try_borrow_data()?;
+        let entries = &data[Self::HEADER_LEN..Self::entry_offset(len as usize)];
+        Ok(anchor_lang::solana_program::hash::hashv(&[&len.to_le_bytes(), entries]).to_bytes())
+    }
+
+    /// Read a single entry without touching the rest of the table
+    pub fn read_entry(data: &[u8], index: usize) -> ParticipantShare {
+        let offset = Self::entry_offset(index);
+        let mut user = [0u8; 32];
+        user.copy_from_slice(&data[offset..offset + 32]);
+        let mut amount = [0u8; 8];
+        amount.copy_from_slice(&data[offset + 32..offset + 40]);
+        ParticipantShare {
+            user: Pubkey::new_from_array(user),
+            share_amount: u64::from_le_bytes(amount),
+        }
+    }
+}
+
+/// Approvers who must sign off on every settlement of a platform, M of N
+#[account]
+pub struct SettlementCommittee {
+    pub bump: u8,                    // PDA bump
+    pub threshold: u8,               // Approvals a settlement needs
+    pub version: u32,                // Bumped on every change, voiding approvals gathered under the old set
+    pub approvers: Vec<Pubkey>,      // Up to MAX_SETTLEMENT_APPROVERS distinct keys
+}
+
+impl SettlementCommittee {
+    pub const LEN: usize = 8 + 1 + 1 + 4 + 4 + 32 * MAX_SETTLEMENT_APPROVERS;
+
+    pub fn validate(approvers: &[Pubkey], threshold: u8) -> Result<()> {
+        require!(approvers.len() <= MAX_SETTLEMENT_APPROVERS, ErrorCode::InvalidSettlementCommittee);
+        require!(
+            threshold > 0 && threshold as usize <= approvers.len(),
+            ErrorCode::InvalidSettlementCommittee
+        );
+        let distinct = approvers.iter().enumerate().all(|(i, key)| !approvers[..i].contains(key));
+        require!(distinct, ErrorCode::InvalidSettlementCommittee);
+        Ok(())
+    }
+
+    pub fn approver_index(&self, key: Pubkey) -> Option<usize> {
+        self.approvers.iter().position(|approver| *approver == key)
+    }
+
+    /// Install a new approver set, voiding approvals gathered under the old one; an empty list
+    /// with threshold 0 removes the committee. Returns whether a committee is now in force
+    pub fn replace(&mut self, approvers: Vec<Pubkey>, threshold: u8) -> Result<bool> {
+        let enabled = !approvers.is_empty();
+        if enabled {
+            Self::validate(&approvers, threshold)?;
+        } else {
+            require!(threshold == 0, ErrorCode::InvalidSettlementCommittee);
+        }
+        self.version = self.version
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        self.threshold = threshold;
+        self.approvers = approvers;
+        Ok(enabled)
+    }
+}
+
+/// Committee approvals gathered for one proposed set of a run's settlement figures
+#[account]
+pub struct SettlementApproval {
+    pub bump: u8,                    // PDA bump
+    pub committee_version: u32,      // Committee version the approvals were given under
+    pub final_balance: u64,          // Approved vault balance
+    pub shares_hash: [u8; 32],       // Approved share list hash, as ShareTable::shares_hash computes it
+    pub approvals: u16,              // Bit i set = approvers[i] approved these figures
+}
+
+impl SettlementApproval {
+    pub const LEN: usize = 8 + 1 + 4 + 8 + 32 + 2;
+
+    /// Record approver `index`'s approval and return how many the figures now have. Approvals
+    /// given under an older committee are dropped, so they never count with the current one
+    pub fn approve(&mut self, committee: &SettlementCommittee, index: usize) -> u32 {
+        if self.committee_version != committee.version {
+            self.committee_version = committee.version;
+            self.approvals = 0;
+        }
+        self.approvals |= 1 << index;
+        self.approvals.count_ones()
+    }
+
+    /// The settlement being executed matches figures the committee approved, at its threshold
+    pub fn check(
+        approval: Option<&SettlementApproval>,
+        committee: Option<&SettlementCommittee>,
+        final_balance: u64,
+        shares_hash: [u8; 32],
+    ) -> Result<()> {
+        let (approval, committee) = approval.zip(committee).ok_or(ErrorCode::SettlementNotApproved)?;
+        require!(
+            approval.committee_version == committee.version
+                && approval.final_balance == final_balance
+                && approval.shares_hash == shares_hash
+                && approval.approvals.count_ones() >= committee.threshold as u32,
+            ErrorCode::SettlementNotApproved
+        );
+        Ok(())
+    }
+}
+
+/// Trading limits of a run, fixed at creation so depositors can audit them before joining
+#[account]
+pub struct RiskConfig {
+    pub run_id: u64,                 // Associated run
+    pub max_position_size: u64,      // Largest notional of a single position
+    pub max_leverage_bps: u32,       // Largest position notional over run capital (10000 = 1x)
+    pub allowed_markets: u64,        // Bit i set = market index i may be traded
+    pub max_daily_trades: u16,       // Trades allowed per UTC day
+    pub trade_day: i64,              // Day (unix time / SECONDS_PER_DAY) trades_today counts
+    pub trades_today: u16,           // Trades made on trade_day
+    pub bump: u8,                    // PDA bump
+}
+
+impl RiskConfig {
+    pub const LEN: usize = 8 + 8 + 8 + 4 + 8 + 2 + 8 + 2 + 1;
+
+    /// Check a trade against the run's limits and count it; record_trade calls this for every trade
+    pub fn check_trade(&mut self, market_index: u16, size: u64, capital: u64, now: i64) -> Result<()> {
+        require!(
+            market_index < 64 && self.allowed_markets & (1 << market_index) != 0,
+            ErrorCode::MarketNotAllowed
+        );
+        require!(size <= self.max_position_size, ErrorCode::PositionTooLarge);
+        require!(
+            size as u128 * 10000 <= capital as u128 * self.max_leverage_bps as u128,
+            ErrorCode::LeverageTooHigh
+        );
+        let day = now / SECONDS_PER_DAY;
+        if day != self.trade_day {
+            self.trade_day = day;
+            self.trades_today = 0;
+        }
+        require!(self.trades_today < self.max_daily_trades, ErrorCode::DailyTradeLimitReached);
+        self.trades_today += 1;
+        Ok(())
+    }
+}
+
+#[account]
+pub struct TradeProposal {
+    pub run_id: u64,                 // Associated run
+    pub round: u8,                   // Vote round deciding on the trade
+    pub proposer: Pubkey,            // Operator that posted the proposal
+    pub proposal_hash: [u8; 32],     // Hash of the full trade details
+    pub details_uri: String,         // Where the trade details can be fetched
+    pub posted_at: i64,              // Timestamp posted
+    pub bump: u8,                    // PDA bump
+}
+
+impl TradeProposal {
+    pub const LEN: usize = 8 + 8 + 1 + 32 + 32 + 4 + MAX_DETAILS_URI_LEN + 8 + 1;
+}
+
+#[account]
+pub struct VoteRound {
+    pub run_id: u64,                 // Associated run
+    pub round: u8,                   // Round number within the run
+    pub proposal_hash: [u8; 32],     // Trade proposal the round votes on
+    pub option_count: u8,            // Options voters choose between
+    pub option_weights: [u64; MAX_VOTE_OPTIONS], // Vote weight behind each option
+    pub total_weight: u64,           // Vote weight cast in the round
+    pub voting_ends_at: i64,         // Votes accepted until this timestamp
+    pub reward_pool: u64,            // Rewards split among correct voters
+    pub rewards_paid: u64,           // Rewards claimed (or returned when nobody was correct)
+    pub resolved: bool,              // Correct option has been recorded
+    pub winning_option: u8,          // Correct option, once resolved
+    pub decision: u8,                // Majority option, once finalized
+    pub decision_finalized: bool,    // Community decision has been tallied
+    pub capital_allocated: bool,     // Strategy capital has been moved to the round's vote split
+    pub bump: u8,                    // PDA bump
+}
+
+impl VoteRound {
+    pub const LEN: usize = 8 + 8 + 1 + 32 + 1 + 8 * MAX_VOTE_OPTIONS + 8 + 8 + 8 + 8 + 1 + 1 + 1 + 1 + 1 + 1;
+
+    /// Option with the most weight; the lowest option wins a tie
+    pub fn majority_option(&self) -> u8 {
+        let mut best = 0u8;
+        for option in 1..self.option_count {
+            if self.option_weights[option as usize] > self.option_weights[best as usize] {
+                best = option;
+            }
+        }
+        best
+    }
+
+    /// Capital for strategy `index`: its option's share of the vote weight
+    pub fn capital_share(&self, index: u8, total: u64) -> Result<u64> {
+        Ok((total as u128)
+            .checked_mul(self.option_weights[index as usize] as u128)
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+            .checked_div(self.total_weight.max(1) as u128)
+            .ok_or(ErrorCode::ArithmeticOverflow)? as u64)
+    }
+
+    /// Trades for this round must follow the finalized community decision
+    pub fn check_decision(&self, option: u8) -> Result<()> {
+        require!(self.decision_finalized, ErrorCode::DecisionNotFinalized);
+        require!(self.decision == option, ErrorCode::DecisionMismatch);
+        Ok(())
+    }
+
+    pub fn record_vote(&mut self, option: u8, weight: u64) -> Result<()> {
+        let option_weight = &mut self.option_weights[option as usize];
+        *option_weight = option_weight
+            .checked_add(weight)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        self.total_weight = self.total_weight
+            .checked_add(weight)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Parimutuel reward for `weight` on the winning option: the pool split over the winning side
+    pub fn reward_for(&self, weight: u64) -> Result<u64> {
+        let winning_weight = self.option_weights[self.winning_option as usize];
+        Ok((self.reward_pool as u128)
+            .checked_mul(weight as u128)
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+            .checked_div(winning_weight.max(1) as u128)
+            .ok_or(ErrorCode::ArithmeticOverflow)? as u64)
+    }
+}
+
+#[account]
+pub struct VoteReceipt {
+    pub user: Pubkey,                // Voter
+    pub option: u8,                  // Option voted for
+    pub weight: u64,                 // Own plus delegated weight behind the vote
+    pub rewarded: bool,              // Round reward has been paid
+    pub bump: u8,                    // PDA bump
+}
+
+impl VoteReceipt {
+    pub const LEN: usize = 8 + 32 + 1 + 8 + 1 + 1;
+}
+
+/// Page of a run's participant registry, filled in deposit order. Entries are never removed, so
+/// cranks skip wallets whose participation has since been closed
+#[account(zero_copy)]
+pub struct ParticipantPage {
+    pub run_id: u64,                 // Associated run
+    pub page_index: u32,             // Position of this page in the registry
+    pub len: u32,                    // Entries written so far
+    pub bump: u8,                    // PDA bump
+    pub _padding: [u8; 7],           // Explicit alignment padding
+    pub participants: [Pubkey; PARTICIPANT_PAGE_SIZE],
+}
+
+impl ParticipantPage {
+    pub const LEN: usize = 8 + std::mem::size_of::<ParticipantPage>();
+
+    /// Register a new participant on the run's current page, initializing the page on its first entry
+    pub fn append(loader: &AccountLoader<ParticipantPage>, run: &mut Run, user: Pubkey, bump: u8) -> Result<()> {
+        let slot = run.registered_participants as usize % PARTICIPANT_PAGE_SIZE;
+        let mut page = if slot == 0 {
+            let mut page = loader.load_init()?;
+            page.run_id = run.run_id;
+            page.page_index = run.participant_page_index();
+            page.bump = bump;
+            page
+        } else {
+            loader.load_mut()?
+        };
+        page.participants[slot] = user;
+        page.len = slot as u32 + 1;
+        run.registered_participants = run.registered_participants
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
+}
+
+/// Ring buffer of a run's last POSITION_LEDGER_CAPACITY rebalances between strategies
+#[account(zero_copy)]
+pub struct PositionLedger {
+    pub run_id: u64,                 // Associated run
+    pub total_entries: u64,          // Rebalances recorded since creation; the next slot is this modulo capacity
+    pub round_marker: i64,           // Vote round the movement total belongs to, by its voting end
+    pub moved_this_round: u64,       // Capital moved between strategies in that round
+    pub bump: u8,                    // PDA bump
+    pub _padding: [u8; 7],           // Explicit alignment padding
+    pub entries: [PositionEntry; POSITION_LEDGER_CAPACITY],
+}
+
+impl PositionLedger {
+    pub const LEN: usize = 8 + std::mem::size_of::<PositionLedger>();
+
+    /// Append a rebalance and return the capital moved so far in the current round, including it
+    pub fn record(&mut self, round_marker: i64, from: u8, to: u8, amount: u64) -> Result<u64> {
+        if self.round_marker != round_marker {
+            self.round_marker = round_marker;
+            self.moved_this_round = 0;
+        }
+        self.moved_this_round = self.moved_this_round
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        let slot = (self.total_entries % POSITION_LEDGER_CAPACITY as u64) as usize;
+        self.entries[slot] = PositionEntry {
+            timestamp: Clock::get()?.unix_timestamp,
+            amount,
+            from,
+            to,
+            _padding: [0; 6],
+        };
+        self.total_entries += 1;
+        Ok(self.moved_this_round)
+    }
+}
+
+#[zero_copy]
+pub struct PositionEntry {
+    pub timestamp: i64,              // Unix timestamp
+    pub amount: u64,                 // Capital moved
+    pub from: u8,                    // Source strategy index
+    pub to: u8,                      // Destination strategy index
+    pub _padding: [u8; 6],           // Explicit alignment padding
+}
+
+/// Ring buffer of the last AUDIT_LOG_CAPACITY privileged actions on a platform
+#[account(zero_copy)]
+pub struct AuditLog {
+    pub total_entries: u64,          // Actions recorded since creation; the next slot is this modulo capacity
+    pub bump: u8,                    // PDA bump
+    pub _padding: [u8; 7],           // Explicit alignment padding
+    pub entries: [AuditEntry; AUDIT_LOG_CAPACITY],
+}
+
+impl AuditLog {
+    pub const LEN: usize = 8 + std::mem::size_of::<AuditLog>();
+
+    /// Append an entry, overwriting the oldest once the buffer is full
+    pub fn record<T: AnchorSerialize>(
+        loader: &AccountLoader<AuditLog>,
+        action: AuditAction,
+        actor: Pubkey,
+        params: &T,
+    ) -> Result<()> {
+        let params_hash = anchor_lang::solana_program::hash::hash(&params.try_to_vec()?).to_bytes();
+        let mut log = loader.load_mut()?;
+        let slot = (log.total_entries % AUDIT_LOG_CAPACITY as u64) as usize;
+        log.entries[slot] = AuditEntry {
+            timestamp: Clock::get()?.unix_timestamp,
+            actor,
+            params_hash,
+            action: action as u8,
+            _padding: [0; 7],
+        };
+        log.total_entries += 1;
+        Ok(())
+    }
+}
+
+#[zero_copy]
+pub struct AuditEntry {
+    pub timestamp: i64,              // Unix timestamp
+    pub actor: Pubkey,               // Signer that performed the action
+    pub params_hash: [u8; 32],       // SHA-256 of the Borsh-encoded instruction arguments
+    pub action: u8,                  // AuditAction discriminant
+    pub _padding: [u8; 7],           // Explicit alignment padding
+}
+
+#[account]
+pub struct Invite {
+    pub run_id: u64,                 // Private run this invite admits to
+    pub code_hash: [u8; 32],         // SHA-256 of the invite code
+    pub remaining_uses: u16,         // Deposits still admitted by this code
+    pub bump: u8,                    // PDA bump
+    pub platform: Pubkey,            // Platform instance of the run
+    pub wallet: Pubkey,              // Only wallet admitted (default = any holder of the code)
+}
+
+impl Invite {
+    pub const LEN: usize = 8 + 8 + 32 + 2 + 1 + 32 + 32;
+
+    /// Check the presented code against this invite and consume one use
+    pub fn redeem(&mut self, run_id: u64, user: Pubkey, code: &[u8]) -> Result<()> {
+        require!(self.run_id == run_id, ErrorCode::InvalidInviteCode);
+        require!(
+            self.wallet == Pubkey::default() || self.wallet == user,
+            ErrorCode::InviteNotForWallet
+        );
+        require!(
+            anchor_lang::solana_program::hash::hash(code).to_bytes() == self.code_hash,
+            ErrorCode::InvalidInviteCode
+        );
+        require!(self.remaining_uses > 0, ErrorCode::InviteExhausted);
+        self.remaining_uses -= 1;
+        Ok(())
+    }
+}
+
+#[account]
+pub struct BlacklistEntry {
+    pub wallet: Pubkey,              // Blocked wallet
+    pub added_at: i64,               // Unix timestamp
+    pub bump: u8,                    // PDA bump
+}
+
+#[account]
+pub struct FeeExempt {
+    pub wallet: Pubkey,              // Wallet settled without the performance fee
+    pub added_at: i64,               // Unix timestamp
+    pub bump: u8,                    // PDA bump
+}
+
+impl FeeExempt {
+    pub const LEN: usize = 8 + 32 + 8 + 1;
+
+    /// Flag each (participation, fee_exempt) pair's participation as exempt and return their total deposits
+    pub fn mark_participants<'info>(
+        accounts: &'info [AccountInfo<'info>],
+        platform: &Pubkey,
+        run_id: u64,
+    ) -> Result<u64> {
+        require!(accounts.len().is_multiple_of(2), ErrorCode::InvalidFeeExemption);
+        let run_id_bytes = run_id.to_le_bytes();
+        let mut exempt_deposits: u64 = 0;
+
+        for pair in accounts.chunks(2) {
+            let mut participation = Account::<UserParticipation>::try_from(&pair[0])?;
+            let exemption = Account::<FeeExempt>::try_from(&pair[1])?;
+
+            let participation_key = Pubkey::create_program_address(
+                &[
+                    b"participation",
+                    platform.as_ref(),
+                    run_id_bytes.as_ref(),
+                    participation.user.as_ref(),
+                    &[participation.bump],
+                ],
+                &crate::ID,
+            )
+            .map_err(|_| ErrorCode::InvalidFeeExemption)?;
+            let exemption_key = Pubkey::create_program_address(
+                &[b"fee_exempt", platform.as_ref(), exemption.wallet.as_ref(), &[exemption.bump]],
+                &crate::ID,
+            )
+            .map_err(|_| ErrorCode::InvalidFeeExemption)?;
+            require_keys_eq!(pair[0].key(), participation_key, ErrorCode::InvalidFeeExemption);
+            require_keys_eq!(pair[1].key(), exemption_key, ErrorCode::InvalidFeeExemption);
+            require_keys_eq!(exemption.wallet, participation.user, ErrorCode::InvalidFeeExemption);
+            // Listing a participant twice would count their deposit twice
+            require!(!participation.fee_exempt, ErrorCode::InvalidFeeExemption);
+
+            participation.fee_exempt = true;
+            exempt_deposits = exempt_deposits
+                .checked_add(participation.deposit_amount)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+            participation.exit(&crate::ID)?;
+        }
+        Ok(exempt_deposits)
+    }
+}
+
+impl BlacklistEntry {
+    pub const LEN: usize = 8 + 32 + 8 + 1;
+
+    /// The wallet's blacklist PDA must not exist
+    pub fn check_not_listed(entry: &AccountInfo) -> Result<()> {
+        require!(entry.data_is_empty(), ErrorCode::WalletBlacklisted);
+        Ok(())
+    }
+}
+
+impl KycAttestation {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 1 + 1;
+
+    /// Require a live attestation from the platform's current compliance authority
+    pub fn check(attestation: Option<&Self>, compliance_authority: Pubkey, now: i64) -> Result<()> {
+        let attestation = attestation.ok_or(ErrorCode::KycRequired)?;
+        require!(compliance_authority != Pubkey::default(), ErrorCode::KycRequired);
+        require!(attestation.issuer == compliance_authority, ErrorCode::KycRequired);
+        require!(!attestation.revoked, ErrorCode::KycRequired);
+        require!(now < attestation.expires_at, ErrorCode::KycRequired);
+        Ok(())
+    }
+}
+
+#[account]
+pub struct ScheduledAction {
+    pub platform: Pubkey,            // Platform the action applies to
+    pub action_id: u64,              // Identifier chosen when queueing
+    pub kind: ScheduledActionKind,   // What executing the action does
+    pub params_hash: [u8; 32],       // SHA-256 of the Borsh-encoded ScheduledActionParams
+    pub queued_at: i64,              // Unix timestamp the action was queued
+    pub execute_after: i64,          // Earliest execution time
+    pub bump: u8,                    // PDA bump
+}
+
+/// Automation thread allowed to start a run on schedule
+#[account]
+pub struct RunAutomation {
+    pub run_id: u64,                 // Associated run
+    pub thread: Pubkey,              // Signer of the automation thread
+    pub start_at: i64,               // Earliest time the thread may start the run
+    pub bump: u8,                    // PDA bump
+}
+
+impl RunAutomation {
+    pub const LEN: usize = 8 + 8 + 32 + 8 + 1;
+}
+
+impl ScheduledAction {
+    pub const LEN: usize = 8 + 32 + 8 + 1 + 32 + 8 + 8 + 1;
+
+    /// Parameters revealed at execution must match what was queued
+    pub fn check_params(&self, params: &ScheduledActionParams) -> Result<()> {
+        require!(params.kind() == self.kind, ErrorCode::ActionParamsMismatch);
+        require!(params.hash()? == self.params_hash, ErrorCode::ActionParamsMismatch);
+        Ok(())
+    }
+}
+
+#[account]
+pub struct AcceptedMint {
+    pub run_id: u64,                 // Run accepting the mint
+    pub mint: Pubkey,                // Stablecoin mint
+    pub price_feed_id: [u8; 32],     // Pyth feed pricing the mint in USD
+    pub total_deposited: u64,        // Tokens deposited into the sub-vault
+    pub bump: u8,                    // PDA bump
+    pub decimals: u8,                // Decimals of the mint
+    pub unconverted_notional: u64,   // USD notional credited for tokens still in the sub-vault
+}
+
+impl AcceptedMint {
+    pub const LEN: usize = 8 + 8 + 32 + 32 + 8 + 1 + 1 + 8;
+}
+
+#[account]
+pub struct EvmLink {
+    pub evm_address: [u8; 20],       // Linked EVM address
+    pub wallet: Pubkey,              // Solana wallet credited with its bridged deposits
+    pub bump: u8,                    // PDA bump
+}
+
+impl EvmLink {
+    pub const LEN: usize = 8 + 20 + 32 + 1;
+
+    /// Byte layout of the secp256k1 program's instruction data
+    const SECP256K1_HEADER_LEN: usize = 1;
+    const SECP256K1_OFFSETS_LEN: usize = 11;
+
+    /// Require that the preceding instruction is a secp256k1 program check of `evm_address`'s
+    /// signature over `message`, with all data inline in that instruction
+    pub fn verify_signature(instructions: &AccountInfo, evm_address: &[u8; 20], message: &[u8]) -> Result<()> {
+        let current = sysvar_instructions::load_current_index_checked(instructions)? as usize;
+        require!(current > 0, ErrorCode::InvalidEvmSignature);
+        let ix = sysvar_instructions::load_instruction_at_checked(current - 1, instructions)?;
+        require_keys_eq!(ix.program_id, secp256k1_program::ID, ErrorCode::InvalidEvmSignature);
+
+        let data = &ix.data;
+        require!(
+            data.len() >= Self::SECP256K1_HEADER_LEN + Self::SECP256K1_OFFSETS_LEN && data[0] == 1,
+            ErrorCode::InvalidEvmSignature
+        );
+        let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]) as usize;
+        let offsets = Self::SECP256K1_HEADER_LEN;
+        let address_offset = read_u16(offsets + 3);
+        let message_offset = read_u16(offsets + 6);
+        let message_size = read_u16(offsets + 8);
+        // Signature, address and message must all come from the secp256k1 instruction itself
+        for index_at in [offsets + 2, offsets + 5, offsets + 10] {
+            require!(data[index_at] as usize == current - 1, ErrorCode::InvalidEvmSignature);
+        }
+
+        let signed_address = data
+            .get(address_offset..address_offset + 20)
+            .ok_or(ErrorCode::InvalidEvmSignature)?;
+        let signed_message = data
+            .get(message_offset..message_offset + message_size)
+            .ok_or(ErrorCode::InvalidEvmSignature)?;
+        require!(signed_address == evm_address.as_slice(), ErrorCode::InvalidEvmSignature);
+        require!(signed_message == message, ErrorCode::InvalidEvmSignature);
+        Ok(())
+    }
+}
+
+#[account]
+pub struct ProcessedBridgeMessage {
+    pub source_domain: u32,          // Bridge domain / chain id of the origin
+    pub nonce: u64,                  // Message nonce within that domain
+    pub processed_at: i64,           // Unix timestamp the deposit was credited
+    pub bump: u8,                    // PDA bump
+}
+
+impl ProcessedBridgeMessage {
+    pub const LEN: usize = 8 + 4 + 8 + 8 + 1;
+}
+
+#[account]
+pub struct IntentNonce {
+    pub user: Pubkey,                // Intent signer
+    pub next_nonce: u64,             // Nonce the next intent must carry
+    pub bump: u8,                    // PDA bump
+}
+
+impl IntentNonce {
+    pub const LEN: usize = 8 + 32 + 8 + 1;
+}
+
+#[account]
+pub struct Subscription {
+    pub user: Pubkey,                // Subscriber wallet
+    pub manager: Pubkey,             // Manager whose runs are auto-joined
+    pub max_per_run: u64,            // USDC deposited into each run (capped by the run's max deposit)
+    pub active: bool,                // Paused subscriptions are skipped
+    pub bump: u8,                    // PDA bump
+}
+
+impl Subscription {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 1 + 1;
+}
+
+#[account]
+pub struct DcaPlan {
+    pub user: Pubkey,                // Depositor
+    pub series_id: u64,              // Run series the plan deposits into
+    pub amount: u64,                 // USDC deposited per execution
+    pub cadence_secs: i64,           // Minimum time between executions
+    pub funding_account: Pubkey,     // Token account with an approval for the plan PDA
+    pub next_execution: i64,         // Earliest time of the next execution
+    pub executions: u32,             // Deposits made so far
+    pub bump: u8,                    // PDA bump
+}
+
+impl DcaPlan {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 8 + 32 + 8 + 4 + 1;
+}
+
+#[account]
+pub struct Manager {
+    pub wallet: Pubkey,              // Manager wallet
+    pub registered_at: i64,          // Unix timestamp of registration
+    pub runs_settled: u32,           // Managed runs settled so far
+    pub total_deposited: u64,        // Deposits across settled runs
+    pub total_pnl: i64,              // Net P/L to participants across settled runs, after fees
+    pub equity_index: u64,           // Compounded net return, EQUITY_INDEX_BASE = 1.0
+    pub peak_equity_index: u64,      // Highest equity_index reached
+    pub max_drawdown_bps: u16,       // Largest fall of equity_index from its peak
+    pub worst_run_return_bps: i64,   // Lowest single-run net return
+    pub bump: u8,                    // PDA bump
+    pub fees_earned: u64,            // Manager fees paid into the fee vault
+}
+
+impl Manager {
+    pub const LEN: usize = 8 + 32 + 8 + 4 + 8 + 8 + 8 + 8 + 2 + 8 + 1 + 8;
+
+    /// Fold a settled run's net result into the track record
+    pub fn record_settlement(&mut self, deposited: u64, final_balance: u64) -> Result<()> {
+        if deposited == 0 {
+            return Ok(());
+        }
+        let pnl = final_balance as i64 - deposited as i64;
+        let return_bps = (pnl as i128 * 10000 / deposited as i128) as i64;
+
+        self.total_deposited = self.total_deposited
+            .checked_add(deposited)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        self.total_pnl = self.total_pnl
+            .checked_add(pnl)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        self.worst_run_return_bps = if self.runs_settled == 0 {
+            return_bps
+        } else {
+            self.worst_run_return_bps.min(return_bps)
+        };
+        self.runs_settled += 1;
+
+        self.equity_index = u64::try_from(
+            self.equity_index as u128 * final_balance as u128 / deposited as u128
+        ).map_err(|_| ErrorCode::ArithmeticOverflow)?;
+        self.peak_equity_index = self.peak_equity_index.max(self.equity_index);
+        let drawdown_bps = (self.peak_equity_index - self.equity_index) as u128 * 10000
+            / self.peak_equity_index.max(1) as u128;
+        self.max_drawdown_bps = self.max_drawdown_bps.max(drawdown_bps as u16);
+        Ok(())
+    }
+}
+
+#[account]
+pub struct Agent {
+    pub wallet: Pubkey,              // Agent signing wallet
+    pub registered_at: i64,          // Unix timestamp of registration
+    pub bond: u64,                   // Bonded stake held in the agent bond vault
+    pub slashed_total: u64,          // Bond slashed into faulted runs
+    pub unbonding_at: i64,           // When unbonding started (0 = bonded)
+    pub bump: u8,                    // PDA bump
+}
+
+impl Agent {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 8 + 8 + 1;
+
+    pub fn is_bonded(&self) -> bool {
+        self.unbonding_at == 0
+    }
+}
+
+#[account]
+pub struct StrategyVault {
+    pub run_id: u64,                 // Associated run
+    pub index: u8,                   // Strategy index, matching a vote option
+    pub agent: Pubkey,               // Agent trading the strategy's capital
+    pub token_account: Pubkey,       // Vault holding the strategy's capital
+    pub allocated: u64,              // Strategy balance after the last capital move
+    pub bump: u8,                    // PDA bump
+    pub capital_in: u64,             // Total moved from the run vault into the strategy
+    pub capital_out: u64,            // Total returned from the strategy to the run vault
+    pub realized_pnl: i64,           // capital_out - capital_in, set once the run settles
+    pub settled: bool,               // P/L has been attributed
+}
+
+impl StrategyVault {
+    pub const LEN: usize = 8 + 8 + 1 + 32 + 32 + 8 + 1 + 8 + 8 + 8 + 1;
+
+    /// Load every strategy of a run, with its vault balance, from (strategy, strategy vault) pairs
+    /// in index order
+    pub fn load_all<'info>(
+        accounts: &'info [AccountInfo<'info>],
+        platform: &Pubkey,
+        run_id: u64,
+        strategy_count: usize,
+    ) -> Result<Vec<(Account<'info, StrategyVault>, u64)>> {
+        require!(accounts.len() == strategy_count * 2, ErrorCode::InvalidStrategyAccounts);
+        let run_id_bytes = run_id.to_le_bytes();
+        let mut strategies = Vec::with_capacity(strategy_count);
+        for (index, pair) in accounts.chunks(2).enumerate() {
+            let strategy: Account<StrategyVault> = Account::try_from(&pair[0])?;
+            let expected = Pubkey::create_program_address(
+                &[
+                    b"strategy",
+                    platform.as_ref(),
+                    run_id_bytes.as_ref(),
+                    &[index as u8],
+                    &[strategy.bump],
+                ],
+                &crate::ID,
+            )
+            .map_err(|_| ErrorCode::InvalidStrategyAccounts)?;
+            require_keys_eq!(pair[0].key(), expected, ErrorCode::InvalidStrategyAccounts);
+            require_keys_eq!(pair[1].key(), strategy.token_account, ErrorCode::InvalidStrategyAccounts);
+            let vault: Account<TokenAccount> = Account::try_from(&pair[1])?;
+            strategies.push((strategy, vault.amount));
+        }
+        Ok(strategies)
+    }
+
+    /// Book a move of the strategy's balance from `balance` to `target`
+    pub fn record_allocation(&mut self, balance: u64, target: u64) -> Result<()> {
+        if target > balance {
+            self.capital_in = self.capital_in
+                .checked_add(target - balance)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+        } else {
+            self.capital_out = self.capital_out
+                .checked_add(balance - target)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+        }
+        self.allocated = target;
+        Ok(())
+    }
+}
+
+#[account]
+pub struct RunTier {
+    pub platform: Pubkey,            // Platform instance the preset belongs to
+    pub tier_id: u8,                 // Preset identifier (e.g. 0 = bronze, 1 = silver, 2 = gold)
+    pub name: [u8; 16],              // Display name, zero-padded UTF-8
+    pub min_deposit: u64,            // Minimum deposit per user
+    pub max_deposit: u64,            // Maximum deposit per user
+    pub min_participants: u16,       // Participants required to start
+    pub max_participants: u16,       // Participant cap
+    pub max_run_tvl: u64,            // Cap on total deposits (0 = no cap)
+    pub min_total_deposit: u64,      // Total deposits required to start
+    pub max_duration: i64,           // Seconds a run may stay active
+    pub management_fee_bps_per_year: u16, // Annualized management fee on deposits
+    pub bonus_bps_per_correct_vote: u16, // Vote-accuracy bonus per correct vote
+    pub max_bonus_bps: u16,          // Cap on the vote-accuracy bonus (0 = uncapped)
+    pub is_active: bool,             // New runs may be created from this preset
+    pub bump: u8,                    // PDA bump
+}
+
+impl RunTier {
+    pub const LEN: usize = 8 + 32 + 1 + 16 + 8 + 8 + 2 + 2 + 8 + 8 + 8 + 2 + 2 + 2 + 1 + 1;
+
+    /// Overwrite the preset fields of a run's creation parameters
+    pub fn apply(&self, params: &mut CreateRunParams) {
+        params.min_deposit = self.min_deposit;
+        params.max_deposit = self.max_deposit;
+        params.min_participants = self.min_participants;
+        params.max_participants = self.max_participants;
+        params.max_run_tvl = self.max_run_tvl;
+        params.min_total_deposit = self.min_total_deposit;
+        params.max_duration = self.max_duration;
+        params.management_fee_bps_per_year = self.management_fee_bps_per_year;
+        params.bonus_bps_per_correct_vote = self.bonus_bps_per_correct_vote;
+    }
+}
+
+// ============================================================================
+// Enums
+// ============================================================================
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum RunStatus {
+    Waiting,            // Accepting deposits
+    Active,             // Trading in progress
+    Settled,            // Trading ended, ready for withdrawals
+    Cancelled,          // Called off before starting, depositors reclaim their deposits
+    Expired,            // Missed quorum by the deposit deadline, depositors reclaim their deposits
+    SettlementPending,  // Trading ended, settlement submitted but not yet final
+    Disputed,           // Settlement challenged, withdrawals frozen until resolved
+    Refunding,          // Frozen after starting, depositors claim pro-rata refunds
+}
+
+/// How a run funds vote-accuracy bonuses
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BonusMode {
+    #[default]
+    Additive,           // Bonuses are paid on top of pro-rata shares, out of the vault's remainder
+    ZeroSum,            // Bonuses are funded by cutting incorrect voters' profit shares
+}
+
+impl RunStatus {
+    /// Allowed edges of the run lifecycle
+    pub fn can_transition_to(self, next: RunStatus) -> bool {
+        use RunStatus::*;
+        matches!(
+            (self, next),
+            (Waiting, Active)
+                | (Waiting, Cancelled)
+                | (Waiting, Expired)
+                | (Active, SettlementPending)
+                | (Active, Settled)
+                | (Active, Refunding)
+                | (SettlementPending, Settled)
+                | (SettlementPending, Refunding)
+                | (Settled, Disputed)
+                | (Disputed, Settled)
+        )
+    }
+
+    /// Statuses in which depositors reclaim funds through `claim_refund`
+    pub fn is_refundable(self) -> bool {
+        matches!(self, RunStatus::Cancelled | RunStatus::Expired | RunStatus::Refunding)
+    }
+}
+
+/// Privileged instruction recorded in the audit log, stored as its u8 discriminant
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum AuditAction {
+    SetBridgeAuthority,
+    SetComplianceAuthority,
+    SetMinPriorityStake,
+    SetVeBoostWeight,
+    SetStakeTiers,
+    SetRevenueShareBps,
+    PausePlatform,
+    UnpausePlatform,
+    SetGovernance,
+    GovernanceSetPlatformFee,
+    GovernancePausePlatform,
+    GovernanceUnpausePlatform,
+    QueueAction,
+    CancelAction,
+    ExecuteAction,
+    PauseRun,
+    SetGuardian,
+    ResumeRun,
+    UpdateRunConfig,
+    ExtendDepositDeadline,
+    SetPlatformTvlCap,
+    SetFeatureFlag,
+    SetExitPenalty,
+    SetSettlementBond,
+    WithdrawPlatformFees,
+    EnableEmergencyRefunds,
+    AddToBlacklist,
+    RemoveFromBlacklist,
+    AddFeeExemption,
+    RemoveFeeExemption,
+    ResolveDispute,
+    SweepUnclaimed,
+    WithdrawRentTreasury,
+    SetRunTier,
+    SlashAgent,
+    SetSettlementEscrow,
+    SetAttestor,
+    SetRiskOracle,
+    SetPlatformLimits,
+    SetKeeperBounty,
+    WithdrawKeeperIncentive,
+    SetSettlementCommittee,
+    SetAuthorityRotation,
+    ProposeAuthority,
+    AcceptAuthority,
+    FreezeParticipation,
+    UnfreezeParticipation,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ScheduledActionKind {
+    SetPlatformFee,     // Change the performance fee
+    TransferAuthority,  // Hand the platform to a new authority key
+    TreasurySpend,      // Pay out of the platform fee vault
+    SetSettlementCommittee, // Change or remove the settlement committee
+}
+
+// ============================================================================
+// Events
+// ============================================================================
+// Emitted with emit_cpi! so indexers read them from inner instructions, which
+// unlike program logs are never truncated
+
+#[event]
+pub struct RunSettled {
+    pub run_id: u64,
+    pub total_deposited: u64,        // Principal at settlement
+    pub final_balance: u64,          // Reported vault balance before fees
+    pub performance_fee: u64,        // Platform fee on profit
+    pub management_fee: u64,         // Time-based fee on deposits
+    pub manager_fee: u64,            // Run manager's cut of profit
+    pub available: u64,              // Balance left for participants
+    pub ended_at: i64,
+}
+
+#[event]
+pub struct RoundDecisionFinalized {
+    pub run_id: u64,
+    pub round: u8,
+    pub decision: u8,                // Majority option
+    pub decision_weight: u64,        // Vote weight behind the decision
+    pub total_weight: u64,           // Vote weight cast in the round
+}
+
+#[event]
+pub struct StrategySettled {
+    pub run_id: u64,
+    pub index: u8,
+    pub agent: Pubkey,
+    pub capital_in: u64,             // Capital the strategy was given
+    pub capital_out: u64,            // Capital the strategy returned
+    pub pnl: i64,                    // capital_out - capital_in
+}
+
+#[event]
+pub struct Rebalanced {
+    pub run_id: u64,
+    pub from_subvault: u8,
+    pub to_subvault: u8,
+    pub amount: u64,
+    pub moved_this_round: u64,       // Capital moved between strategies in the round so far
+}
+
+#[event]
+pub struct TradeRecorded {
+    pub run_id: u64,
+    pub round: u8,
+    pub option: u8,                  // Community decision the trade follows
+    pub market_index: u16,
+    pub size: u64,
+    pub trades_today: u16,           // Trades counted against max_daily_trades so far today
+}
+
+#[event]
+pub struct WithdrawnAndClosed {
+    pub run_id: u64,
+    pub user: Pubkey,
+    pub share: u64,                  // Settled share paid out
+    pub rent_refunded: u64,          // Lamports returned from the closed participation
+}
+
+// ============================================================================
+// Context Structs
+// ============================================================================
+
+#[derive(Accounts)]
+#[instruction(platform_id: u64)]
+pub struct InitializePlatform<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = Platform::LEN,
+        seeds = [b"platform", platform_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        init,
+        payer = authority,
+        token::mint = usdc_mint,
+        token::authority = platform,
+        seeds = [b"platform_fee_vault", platform.key().as_ref()],
+        bump
+    )]
+    pub platform_fee_vault: Account<'info, TokenAccount>,
+    
+    pub usdc_mint: Account<'info, token::Mint>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = AuditLog::LEN,
+        seeds = [b"audit_log", platform.key().as_ref()],
+        bump
+    )]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateRun<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()],
+        bump = platform.bump
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = Run::LEN,
+        seeds = [b"run", platform.key().as_ref(), platform.next_run_id()?.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        init,
+        payer = authority,
+        token::mint = mint,
+        token::authority = run,
+        seeds = [b"vault", platform.key().as_ref(), platform.next_run_id()?.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub run_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = RiskConfig::LEN,
+        seeds = [b"risk_config", platform.key().as_ref(), platform.next_run_id()?.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub risk_config: Account<'info, RiskConfig>,
+    
+    /// Deposit mint of the run, normally USDC
+    pub mint: Account<'info, token::Mint>,
+    
+    /// Preset supplying the run's limits, fees and bonus schedule; omit for custom parameters
+    #[account(seeds = [b"run_tier", platform.key().as_ref(), &[tier.tier_id]], bump = tier.bump)]
+    pub tier: Option<Account<'info, RunTier>>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(tier_id: u8)]
+pub struct SetRunTier<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump, has_one = authority, constraint = platform.is_authority_current() @ ErrorCode::AuthorityExpired)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(mut, seeds = [b"audit_log", platform.key().as_ref()], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = RunTier::LEN,
+        seeds = [b"run_tier", platform.key().as_ref(), &[tier_id]],
+        bump
+    )]
+    pub tier: Account<'info, RunTier>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64)]
+pub struct Deposit<'info> {
+    #[account(mut, seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        init,
+        payer = user,
+        space = UserParticipation::LEN,
+        seeds = [b"participation", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_participation: Account<'info, UserParticipation>,
+    
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = ParticipantPage::LEN,
+        seeds = [b"participant_page", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), run.participant_page_index().to_le_bytes().as_ref()],
+        bump
+    )]
+    pub participant_page: AccountLoader<'info, ParticipantPage>,
+    
+    /// CHECK: checked by Run::check_vault, which reports a missing vault as VaultNotInitialized
+    #[account(
+        mut,
+        seeds = [b"vault", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub run_vault: UncheckedAccount<'info>,
+    
+    #[account(mut, token::mint = run.mint, token::authority = user)]
+    pub user_token_account: Account<'info, TokenAccount>,
+    
+    pub usdc_mint: Account<'info, token::Mint>,
+    
+    /// Required only during a run's priority window
+    #[account(seeds = [b"stake", platform.key().as_ref(), user.key().as_ref()], bump = stake.bump)]
+    pub stake: Option<Account<'info, Stake>>,
+    
+    /// Required only for runs that gate deposits on KYC
+    #[account(seeds = [b"kyc", platform.key().as_ref(), user.key().as_ref()], bump = kyc_attestation.bump)]
+    pub kyc_attestation: Option<Account<'info, KycAttestation>>,
+    
+    /// Required only for private runs; checked against the presented code
+    #[account(
+        mut,
+        seeds = [b"invite", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), invite.code_hash.as_ref()],
+        bump = invite.bump,
+        has_one = platform
+    )]
+    pub invite: Option<Account<'info, Invite>>,
+    
+    /// CHECK: must be uninitialized - the wallet is blacklisted if this PDA exists
+    #[account(seeds = [b"blacklist", platform.key().as_ref(), user.key().as_ref()], bump)]
+    pub blacklist_entry: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    /// Reimburses the participation's rent when provided
+    #[account(mut, seeds = [b"rent_treasury", platform.key().as_ref()], bump = rent_treasury.bump)]
+    pub rent_treasury: Option<Account<'info, RentTreasury>>,
+    
+    /// Tags the transfer with an SPL Memo for reconciliation when provided
+    pub memo_program: Option<Program<'info, Memo>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64)]
+pub struct DepositWithDelegate<'info> {
+    #[account(mut, seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        init,
+        payer = relayer,
+        space = UserParticipation::LEN,
+        seeds = [b"participation", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_participation: Account<'info, UserParticipation>,
+    
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = ParticipantPage::LEN,
+        seeds = [b"participant_page", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), run.participant_page_index().to_le_bytes().as_ref()],
+        bump
+    )]
+    pub participant_page: AccountLoader<'info, ParticipantPage>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub run_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = run.mint, token::authority = user)]
+    pub user_token_account: Account<'info, TokenAccount>,
+    
+    #[account(seeds = [b"stake", platform.key().as_ref(), user.key().as_ref()], bump = stake.bump)]
+    pub stake: Option<Account<'info, Stake>>,
+    
+    #[account(seeds = [b"kyc", platform.key().as_ref(), user.key().as_ref()], bump = kyc_attestation.bump)]
+    pub kyc_attestation: Option<Account<'info, KycAttestation>>,
+    
+    /// CHECK: must be uninitialized - the wallet is blacklisted if this PDA exists
+    #[account(seeds = [b"blacklist", platform.key().as_ref(), user.key().as_ref()], bump)]
+    pub blacklist_entry: UncheckedAccount<'info>,
+    
+    /// CHECK: wallet that owns the token account and is credited the participation
+    pub user: UncheckedAccount<'info>,
+    
+    /// Approved delegate of the user's token account; pays for the participation account
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    
+    /// Tags the transfer with an SPL Memo for reconciliation when provided
+    pub memo_program: Option<Program<'info, Memo>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64)]
+pub struct DepositByIntent<'info> {
+    #[account(mut, seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        init,
+        payer = relayer,
+        space = UserParticipation::LEN,
+        seeds = [b"participation", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_participation: Account<'info, UserParticipation>,
+    
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = ParticipantPage::LEN,
+        seeds = [b"participant_page", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), run.participant_page_index().to_le_bytes().as_ref()],
+        bump
+    )]
+    pub participant_page: AccountLoader<'info, ParticipantPage>,
+    
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = IntentNonce::LEN,
+        seeds = [b"intent_nonce", platform.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub intent_nonce: Account<'info, IntentNonce>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub run_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = run.mint, token::authority = user)]
+    pub user_token_account: Account<'info, TokenAccount>,
+    
+    /// CHECK: PDA the user approved as delegate on their token account; signs only for verified intents
+    #[account(seeds = [b"intent_delegate", platform.key().as_ref()], bump)]
+    pub intent_delegate: UncheckedAccount<'info>,
+    
+    #[account(seeds = [b"stake", platform.key().as_ref(), user.key().as_ref()], bump = stake.bump)]
+    pub stake: Option<Account<'info, Stake>>,
+    
+    #[account(seeds = [b"kyc", platform.key().as_ref(), user.key().as_ref()], bump = kyc_attestation.bump)]
+    pub kyc_attestation: Option<Account<'info, KycAttestation>>,
+    
+    /// CHECK: must be uninitialized - the wallet is blacklisted if this PDA exists
+    #[account(seeds = [b"blacklist", platform.key().as_ref(), user.key().as_ref()], bump)]
+    pub blacklist_entry: UncheckedAccount<'info>,
+    
+    /// CHECK: the intent's signer, checked against the ed25519 instruction
+    pub user: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    
+    /// CHECK: instructions sysvar, used to read the ed25519 signature check
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    
+    /// Tags the transfer with an SPL Memo for reconciliation when provided
+    pub memo_program: Option<Program<'info, Memo>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeAuditLog<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump, has_one = authority, constraint = platform.is_authority_current() @ ErrorCode::AuthorityExpired)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = AuditLog::LEN,
+        seeds = [b"audit_log", platform.key().as_ref()],
+        bump
+    )]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeRentTreasury<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump, has_one = authority, constraint = platform.is_authority_current() @ ErrorCode::AuthorityExpired)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = RentTreasury::LEN,
+        seeds = [b"rent_treasury", platform.key().as_ref()],
+        bump
+    )]
+    pub rent_treasury: Account<'info, RentTreasury>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FundRentTreasury<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(mut, seeds = [b"rent_treasury", platform.key().as_ref()], bump = rent_treasury.bump)]
+    pub rent_treasury: Account<'info, RentTreasury>,
+    
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawRentTreasury<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump, has_one = authority, constraint = platform.is_authority_current() @ ErrorCode::AuthorityExpired)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(mut, seeds = [b"audit_log", platform.key().as_ref()], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    
+    #[account(mut, seeds = [b"rent_treasury", platform.key().as_ref()], bump = rent_treasury.bump)]
+    pub rent_treasury: Account<'info, RentTreasury>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeKeeperIncentive<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump, has_one = authority, constraint = platform.is_authority_current() @ ErrorCode::AuthorityExpired)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = KeeperIncentive::LEN,
+        seeds = [b"keeper_incentive", platform.key().as_ref()],
+        bump
+    )]
+    pub keeper_incentive: Account<'info, KeeperIncentive>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FundKeeperIncentive<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(mut, seeds = [b"keeper_incentive", platform.key().as_ref()], bump = keeper_incentive.bump)]
+    pub keeper_incentive: Account<'info, KeeperIncentive>,
+    
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ManageKeeperIncentive<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump, has_one = authority, constraint = platform.is_authority_current() @ ErrorCode::AuthorityExpired)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(mut, seeds = [b"audit_log", platform.key().as_ref()], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    
+    #[account(mut, seeds = [b"keeper_incentive", platform.key().as_ref()], bump = keeper_incentive.bump)]
+    pub keeper_incentive: Account<'info, KeeperIncentive>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeBridge<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()],
+        bump = platform.bump,
+        has_one = authority,
+        constraint = platform.is_authority_current() @ ErrorCode::AuthorityExpired,
+        has_one = platform_fee_vault
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    pub platform_fee_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        init,
+        payer = authority,
+        token::mint = usdc_mint,
+        token::authority = platform,
+        seeds = [b"bridge_inbox", platform.key().as_ref()],
+        bump
+    )]
+    pub bridge_inbox: Account<'info, TokenAccount>,
+    
+    #[account(address = platform_fee_vault.mint)]
+    pub usdc_mint: Account<'info, token::Mint>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(evm_address: [u8; 20])]
+pub struct LinkEvmAddress<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        init,
+        payer = wallet,
+        space = EvmLink::LEN,
+        seeds = [b"evm_link", platform.key().as_ref(), evm_address.as_ref()],
+        bump
+    )]
+    pub evm_link: Account<'info, EvmLink>,
+    
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+    
+    /// CHECK: instructions sysvar, used to read the secp256k1 signature check
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64, message: BridgedDeposit)]
+pub struct ReceiveBridgedDeposit<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()],
+        bump = platform.bump,
+        has_one = bridge_authority
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump
+    )]
+    pub run: Account<'info, Run>,
+    
+    /// Replay guard for the bridge message
+    #[account(
+        init,
+        payer = payer,
+        space = ProcessedBridgeMessage::LEN,
+        seeds = [
+            b"bridge_msg",
+            platform.key().as_ref(),
+            message.source_domain.to_le_bytes().as_ref(),
+            message.nonce.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub processed_message: Account<'info, ProcessedBridgeMessage>,
+    
+    #[account(
+        seeds = [b"evm_link", platform.key().as_ref(), message.evm_sender.as_ref()],
+        bump = evm_link.bump
+    )]
+    pub evm_link: Account<'info, EvmLink>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = UserParticipation::LEN,
+        seeds = [b"participation", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), evm_link.wallet.as_ref()],
+        bump
+    )]
+    pub user_participation: Account<'info, UserParticipation>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = ParticipantPage::LEN,
+        seeds = [b"participant_page", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), run.participant_page_index().to_le_bytes().as_ref()],
+        bump
+    )]
+    pub participant_page: AccountLoader<'info, ParticipantPage>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub run_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"bridge_inbox", platform.key().as_ref()],
+        bump
+    )]
+    pub bridge_inbox: Account<'info, TokenAccount>,
+    
+    #[account(seeds = [b"stake", platform.key().as_ref(), evm_link.wallet.as_ref()], bump = stake.bump)]
+    pub stake: Option<Account<'info, Stake>>,
+    
+    #[account(seeds = [b"kyc", platform.key().as_ref(), evm_link.wallet.as_ref()], bump = kyc_attestation.bump)]
+    pub kyc_attestation: Option<Account<'info, KycAttestation>>,
+    
+    /// CHECK: must be uninitialized - the wallet is blacklisted if this PDA exists
+    #[account(seeds = [b"blacklist", platform.key().as_ref(), evm_link.wallet.as_ref()], bump)]
+    pub blacklist_entry: UncheckedAccount<'info>,
+    
+    pub bridge_authority: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    /// Tags the transfer with an SPL Memo for reconciliation when provided
+    pub memo_program: Option<Program<'info, Memo>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64)]
+pub struct AddAcceptedMint<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        seeds = [b"run", run.platform.as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump,
+        has_one = platform,
+        constraint = run.is_operator(&platform, authority.key()) @ ErrorCode::NotRunOperator
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = AcceptedMint::LEN,
+        seeds = [b"accepted_mint", run.platform.as_ref(), run_id.to_le_bytes().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub accepted_mint: Account<'info, AcceptedMint>,
+    
+    #[account(
+        init,
+        payer = authority,
+        token::mint = mint,
+        token::authority = run,
+        seeds = [b"sub_vault", run.platform.as_ref(), run_id.to_le_bytes().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub sub_vault: Account<'info, TokenAccount>,
+    
+    pub mint: Account<'info, token::Mint>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64)]
+pub struct DepositStablecoin<'info> {
+    #[account(mut, seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        mut,
+        seeds = [b"accepted_mint", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), accepted_mint.mint.as_ref()],
+        bump = accepted_mint.bump
+    )]
+    pub accepted_mint: Account<'info, AcceptedMint>,
+    
+    #[account(
+        init,
+        payer = user,
+        space = UserParticipation::LEN,
+        seeds = [b"participation", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_participation: Account<'info, UserParticipation>,
+    
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = ParticipantPage::LEN,
+        seeds = [b"participant_page", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), run.participant_page_index().to_le_bytes().as_ref()],
+        bump
+    )]
+    pub participant_page: AccountLoader<'info, ParticipantPage>,
+    
+    #[account(
+        mut,
+        seeds = [b"sub_vault", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), accepted_mint.mint.as_ref()],
+        bump
+    )]
+    pub sub_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = accepted_mint.mint)]
+    pub user_token_account: Account<'info, TokenAccount>,
+    
+    /// CHECK: Pyth PriceUpdateV2 account; owner, feed id and freshness are checked when parsed
+    pub price_update: UncheckedAccount<'info>,
+    
+    #[account(seeds = [b"stake", platform.key().as_ref(), user.key().as_ref()], bump = stake.bump)]
+    pub stake: Option<Account<'info, Stake>>,
+    
+    #[account(seeds = [b"kyc", platform.key().as_ref(), user.key().as_ref()], bump = kyc_attestation.bump)]
+    pub kyc_attestation: Option<Account<'info, KycAttestation>>,
+    
+    /// CHECK: must be uninitialized - the wallet is blacklisted if this PDA exists
+    #[account(seeds = [b"blacklist", platform.key().as_ref(), user.key().as_ref()], bump)]
+    pub blacklist_entry: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    /// Tags the transfer with an SPL Memo for reconciliation when provided
+    pub memo_program: Option<Program<'info, Memo>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64)]
+pub struct ConvertSubVault<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"run", run.platform.as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump,
+        has_one = platform,
+        constraint = run.is_operator(&platform, authority.key()) @ ErrorCode::NotRunOperator
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        mut,
+        seeds = [b"accepted_mint", run.platform.as_ref(), run_id.to_le_bytes().as_ref(), accepted_mint.mint.as_ref()],
+        bump = accepted_mint.bump
+    )]
+    pub accepted_mint: Account<'info, AcceptedMint>,
+    
+    #[account(
+        mut,
+        seeds = [b"sub_vault", run.platform.as_ref(), run_id.to_le_bytes().as_ref(), accepted_mint.mint.as_ref()],
+        bump
+    )]
+    pub sub_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", run.platform.as_ref(), run_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub run_vault: Account<'info, TokenAccount>,
+    
+    /// CHECK: Pyth PriceUpdateV2 account; owner, feed id and freshness are checked when parsed
+    pub price_update: UncheckedAccount<'info>,
+    
+    /// Pays the USDC for the converted stablecoins
+    #[account(mut, token::mint = run.mint)]
+    pub authority_token_account: Account<'info, TokenAccount>,
+    
+    /// Receives the converted stablecoins
+    #[account(mut, token::mint = accepted_mint.mint)]
+    pub destination_token_account: Account<'info, TokenAccount>,
+    
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64)]
+pub struct ClaimSubVaultRefund<'info> {
+    #[account(mut, seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        mut,
+        seeds = [b"participation", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), user.key().as_ref()],
+        bump = user_participation.bump
+    )]
+    pub user_participation: Account<'info, UserParticipation>,
+    
+    #[account(
+        mut,
+        seeds = [b"accepted_mint", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), user_participation.deposit_mint.as_ref()],
+        bump = accepted_mint.bump
+    )]
+    pub accepted_mint: Account<'info, AcceptedMint>,
+    
+    #[account(
+        mut,
+        seeds = [b"sub_vault", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), accepted_mint.mint.as_ref()],
+        bump
+    )]
+    pub sub_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = accepted_mint.mint)]
+    pub user_token_account: Account<'info, TokenAccount>,
+    
+    /// CHECK: must be uninitialized - the wallet is blacklisted if this PDA exists
+    #[account(seeds = [b"blacklist", platform.key().as_ref(), user.key().as_ref()], bump)]
+    pub blacklist_entry: UncheckedAccount<'info>,
+    
+    pub user: Signer<'info>,
+    
+    /// Tags the transfer with an SPL Memo for reconciliation when provided
+    pub memo_program: Option<Program<'info, Memo>>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64)]
+pub struct InitShareMint<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"run", run.platform.as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump,
+        has_one = platform,
+        constraint = run.is_operator(&platform, authority.key()) @ ErrorCode::NotRunOperator
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        init,
+        payer = authority,
+        mint::decimals = run.mint_decimals,
+        mint::authority = run,
+        seeds = [b"share_mint", run.platform.as_ref(), run_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub share_mint: Account<'info, token::Mint>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64)]
+pub struct DepositShares<'info> {
+    #[account(mut, seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub run_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, address = run.share_mint)]
+    pub share_mint: Account<'info, token::Mint>,
+    
+    #[account(mut, token::mint = run.mint, token::authority = user)]
+    pub user_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = share_mint,
+        associated_token::authority = user
+    )]
+    pub user_share_account: Account<'info, TokenAccount>,
+    
+    /// Required only during a run's priority window
+    #[account(seeds = [b"stake", platform.key().as_ref(), user.key().as_ref()], bump = stake.bump)]
+    pub stake: Option<Account<'info, Stake>>,
+    
+    /// Required only for runs that gate deposits on KYC
+    #[account(seeds = [b"kyc", platform.key().as_ref(), user.key().as_ref()], bump = kyc_attestation.bump)]
+    pub kyc_attestation: Option<Account<'info, KycAttestation>>,
+    
+    /// CHECK: must be uninitialized - the wallet is blacklisted if this PDA exists
+    #[account(seeds = [b"blacklist", platform.key().as_ref(), user.key().as_ref()], bump)]
+    pub blacklist_entry: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    /// Tags the transfer with an SPL Memo for reconciliation when provided
+    pub memo_program: Option<Program<'info, Memo>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64)]
+pub struct RedeemShares<'info> {
+    #[account(mut, seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub run_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, address = run.share_mint)]
+    pub share_mint: Account<'info, token::Mint>,
+    
+    #[account(mut, token::mint = run.share_mint, token::authority = user)]
+    pub user_share_account: Account<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = run.mint, token::authority = user)]
+    pub user_token_account: Account<'info, TokenAccount>,
+    
+    /// CHECK: must be uninitialized - the wallet is blacklisted if this PDA exists
+    #[account(seeds = [b"blacklist", platform.key().as_ref(), user.key().as_ref()], bump)]
+    pub blacklist_entry: UncheckedAccount<'info>,
+    
+    pub user: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(manager: Pubkey)]
+pub struct Subscribe<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        init,
+        payer = user,
+        space = Subscription::LEN,
+        seeds = [b"subscription", platform.key().as_ref(), user.key().as_ref(), manager.as_ref()],
+        bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(manager: Pubkey)]
+pub struct UpdateSubscription<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"subscription", platform.key().as_ref(), user.key().as_ref(), manager.as_ref()],
+        bump = subscription.bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+    
+    pub user: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(manager: Pubkey)]
+pub struct Unsubscribe<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"subscription", platform.key().as_ref(), user.key().as_ref(), manager.as_ref()],
+        bump = subscription.bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64)]
+pub struct AutoEnroll<'info> {
+    #[account(mut, seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        seeds = [b"subscription", platform.key().as_ref(), subscription.user.as_ref(), subscription.manager.as_ref()],
+        bump = subscription.bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+    
+    #[account(
+        init,
+        payer = cranker,
+        space = UserParticipation::LEN,
+        seeds = [b"participation", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), subscription.user.as_ref()],
+        bump
+    )]
+    pub user_participation: Account<'info, UserParticipation>,
+    
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = ParticipantPage::LEN,
+        seeds = [b"participant_page", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), run.participant_page_index().to_le_bytes().as_ref()],
+        bump
+    )]
+    pub participant_page: AccountLoader<'info, ParticipantPage>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub run_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = run.mint, token::authority = subscription.user)]
+    pub user_token_account: Account<'info, TokenAccount>,
+    
+    #[account(seeds = [b"stake", platform.key().as_ref(), subscription.user.as_ref()], bump = stake.bump)]
+    pub stake: Option<Account<'info, Stake>>,
+    
+    #[account(seeds = [b"kyc", platform.key().as_ref(), subscription.user.as_ref()], bump = kyc_attestation.bump)]
+    pub kyc_attestation: Option<Account<'info, KycAttestation>>,
+    
+    /// CHECK: must be uninitialized - the wallet is blacklisted if this PDA exists
+    #[account(seeds = [b"blacklist", platform.key().as_ref(), subscription.user.as_ref()], bump)]
+    pub blacklist_entry: UncheckedAccount<'info>,
+    
+    #[account(mut, seeds = [b"keeper_incentive", platform.key().as_ref()], bump = keeper_incentive.bump)]
+    pub keeper_incentive: Option<Account<'info, KeeperIncentive>>,
+    
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    
+    /// Tags the transfer with an SPL Memo for reconciliation when provided
+    pub memo_program: Option<Program<'info, Memo>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(series_id: u64)]
+pub struct CreateDcaPlan<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        init,
+        payer = user,
+        space = DcaPlan::LEN,
+        seeds = [b"dca_plan", platform.key().as_ref(), user.key().as_ref(), series_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub dca_plan: Account<'info, DcaPlan>,
+    
+    #[account(token::authority = user)]
+    pub funding_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(series_id: u64)]
+pub struct CancelDcaPlan<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"dca_plan", platform.key().as_ref(), user.key().as_ref(), series_id.to_le_bytes().as_ref()],
+        bump = dca_plan.bump
+    )]
+    pub dca_plan: Account<'info, DcaPlan>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64)]
+pub struct ExecuteDca<'info> {
+    #[account(mut, seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        mut,
+        seeds = [b"dca_plan", platform.key().as_ref(), dca_plan.user.as_ref(), dca_plan.series_id.to_le_bytes().as_ref()],
+        bump = dca_plan.bump
+    )]
+    pub dca_plan: Account<'info, DcaPlan>,
+    
+    #[account(
+        init,
+        payer = cranker,
+        space = UserParticipation::LEN,
+        seeds = [b"participation", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), dca_plan.user.as_ref()],
+        bump
+    )]
+    pub user_participation: Account<'info, UserParticipation>,
+    
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = ParticipantPage::LEN,
+        seeds = [b"participant_page", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), run.participant_page_index().to_le_bytes().as_ref()],
+        bump
+    )]
+    pub participant_page: AccountLoader<'info, ParticipantPage>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub run_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, address = dca_plan.funding_account, token::mint = run.mint)]
+    pub funding_account: Account<'info, TokenAccount>,
+    
+    #[account(seeds = [b"stake", platform.key().as_ref(), dca_plan.user.as_ref()], bump = stake.bump)]
+    pub stake: Option<Account<'info, Stake>>,
+    
+    #[account(seeds = [b"kyc", platform.key().as_ref(), dca_plan.user.as_ref()], bump = kyc_attestation.bump)]
+    pub kyc_attestation: Option<Account<'info, KycAttestation>>,
+    
+    /// CHECK: must be uninitialized - the wallet is blacklisted if this PDA exists
+    #[account(seeds = [b"blacklist", platform.key().as_ref(), dca_plan.user.as_ref()], bump)]
+    pub blacklist_entry: UncheckedAccount<'info>,
+    
+    #[account(mut, seeds = [b"keeper_incentive", platform.key().as_ref()], bump = keeper_incentive.bump)]
+    pub keeper_incentive: Option<Account<'info, KeeperIncentive>>,
+    
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    
+    /// Tags the transfer with an SPL Memo for reconciliation when provided
+    pub memo_program: Option<Program<'info, Memo>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64, code_hash: [u8; 32])]
+pub struct CreateInvite<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        seeds = [b"run", run.platform.as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump,
+        has_one = platform,
+        constraint = run.is_operator(&platform, authority.key()) @ ErrorCode::NotRunOperator
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = Invite::LEN,
+        seeds = [b"invite", run.platform.as_ref(), run_id.to_le_bytes().as_ref(), code_hash.as_ref()],
+        bump
+    )]
+    pub invite: Account<'info, Invite>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64)]
+pub struct LeaveRun<'info> {
+    #[account(mut, seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"participation", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), user.key().as_ref()],
+        bump = user_participation.bump
+    )]
+    pub user_participation: Account<'info, UserParticipation>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub run_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = run.mint, token::authority = user)]
+    pub user_token_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    /// Required when closing a participation whose rent it sponsored
+    #[account(mut, seeds = [b"rent_treasury", platform.key().as_ref()], bump = rent_treasury.bump)]
+    pub rent_treasury: Option<Account<'info, RentTreasury>>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64)]
+pub struct EmergencyExit<'info> {
+    #[account(mut, seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"participation", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), user.key().as_ref()],
+        bump = user_participation.bump
+    )]
+    pub user_participation: Account<'info, UserParticipation>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub run_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"platform_fee_vault", platform.key().as_ref()],
+        bump
+    )]
+    pub platform_fee_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = run.mint, token::authority = user)]
+    pub user_token_account: Account<'info, TokenAccount>,
+    
+    /// CHECK: must be uninitialized - the wallet is blacklisted if this PDA exists
+    #[account(seeds = [b"blacklist", platform.key().as_ref(), user.key().as_ref()], bump)]
+    pub blacklist_entry: UncheckedAccount<'info>,
+    
+    /// Only needed for a stake-tier penalty discount
+    #[account(seeds = [b"stake", platform.key().as_ref(), user.key().as_ref()], bump = stake.bump)]
+    pub stake: Option<Account<'info, Stake>>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    /// Required when closing a participation whose rent it sponsored
+    #[account(mut, seeds = [b"rent_treasury", platform.key().as_ref()], bump = rent_treasury.bump)]
+    pub rent_treasury: Option<Account<'info, RentTreasury>>,
+    
+    /// Tags the transfer with an SPL Memo for reconciliation when provided
+    pub memo_program: Option<Program<'info, Memo>>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64)]
+pub struct CreateWaitlistVault<'info> {
+    #[account(
+        seeds = [b"run", run.platform.as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        init,
+        payer = payer,
+        token::mint = usdc_mint,
+        token::authority = run,
+        seeds = [b"waitlist_vault", run.platform.as_ref(), run_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub waitlist_vault: Account<'info, TokenAccount>,
+    
+    pub usdc_mint: Account<'info, token::Mint>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64)]
+pub struct JoinWaitlist<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        init,
+        payer = user,
+        space = WaitlistEntry::LEN,
+        seeds = [b"waitlist", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), run.waitlist_tail.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub waitlist_entry: Account<'info, WaitlistEntry>,
+    
+    #[account(
+        init,
+        payer = user,
+        space = WaitlistMarker::LEN,
+        seeds = [b"waitlist_user", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub waitlist_marker: Account<'info, WaitlistMarker>,
+    
+    /// CHECK: only checked to be uninitialized; it is created on promotion
+    #[account(
+        seeds = [b"participation", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_participation: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"waitlist_vault", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub waitlist_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = run.mint, token::authority = user)]
+    pub user_token_account: Account<'info, TokenAccount>,
+    
+    /// Required only for runs that gate deposits on KYC
+    #[account(seeds = [b"kyc", platform.key().as_ref(), user.key().as_ref()], bump = kyc_attestation.bump)]
+    pub kyc_attestation: Option<Account<'info, KycAttestation>>,
+    
+    /// CHECK: must be uninitialized - the wallet is blacklisted if this PDA exists
+    #[account(seeds = [b"blacklist", platform.key().as_ref(), user.key().as_ref()], bump)]
+    pub blacklist_entry: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    /// Tags the transfer with an SPL Memo for reconciliation when provided
+    pub memo_program: Option<Program<'info, Memo>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64)]
+pub struct PromoteFromWaitlist<'info> {
+    #[account(mut, seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"waitlist", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), run.waitlist_head.to_le_bytes().as_ref()],
+        bump = waitlist_entry.bump
+    )]
+    pub waitlist_entry: Account<'info, WaitlistEntry>,
+    
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"waitlist_user", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), waitlist_entry.user.as_ref()],
+        bump = waitlist_marker.bump
+    )]
+    pub waitlist_marker: Account<'info, WaitlistMarker>,
+    
+    #[account(
+        init,
+        payer = cranker,
+        space = UserParticipation::LEN,
+        seeds = [b"participation", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), waitlist_entry.user.as_ref()],
+        bump
+    )]
+    pub user_participation: Account<'info, UserParticipation>,
+    
+    /// CHECK: must be uninitialized - the queued wallet is blacklisted if this PDA exists
+    #[account(seeds = [b"blacklist", platform.key().as_ref(), waitlist_entry.user.as_ref()], bump)]
+    pub blacklist_entry: UncheckedAccount<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = ParticipantPage::LEN,
+        seeds = [b"participant_page", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), run.participant_page_index().to_le_bytes().as_ref()],
+        bump
+    )]
+    pub participant_page: AccountLoader<'info, ParticipantPage>,
+    
+    #[account(
+        mut,
+        seeds = [b"waitlist_vault", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub waitlist_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub run_vault: Account<'info, TokenAccount>,
+    
+    /// CHECK: receives the waitlist entry's rent; must be the queued user
+    #[account(mut, address = waitlist_entry.user)]
+    pub user: UncheckedAccount<'info>,
+    
+    #[account(mut, seeds = [b"keeper_incentive", platform.key().as_ref()], bump = keeper_incentive.bump)]
+    pub keeper_incentive: Option<Account<'info, KeeperIncentive>>,
+    
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    
+    /// Tags the transfer with an SPL Memo for reconciliation when provided
+    pub memo_program: Option<Program<'info, Memo>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64)]
+pub struct SkipWaitlistHead<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        seeds = [b"waitlist", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), run.waitlist_head.to_le_bytes().as_ref()],
+        bump = waitlist_entry.bump
+    )]
+    pub waitlist_entry: Account<'info, WaitlistEntry>,
+    
+    /// CHECK: the queued wallet already participates if this PDA exists
+    #[account(
+        seeds = [b"participation", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), waitlist_entry.user.as_ref()],
+        bump
+    )]
+    pub user_participation: UncheckedAccount<'info>,
+    
+    /// CHECK: the queued wallet is blacklisted if this PDA exists
+    #[account(seeds = [b"blacklist", platform.key().as_ref(), waitlist_entry.user.as_ref()], bump)]
+    pub blacklist_entry: UncheckedAccount<'info>,
+    
+    #[account(mut, seeds = [b"keeper_incentive", platform.key().as_ref()], bump = keeper_incentive.bump)]
+    pub keeper_incentive: Option<Account<'info, KeeperIncentive>>,
+    
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64, position: u64)]
+pub struct LeaveWaitlist<'info> {
+    #[account(
+        seeds = [b"run", run.platform.as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"waitlist", run.platform.as_ref(), run_id.to_le_bytes().as_ref(), position.to_le_bytes().as_ref()],
+        bump = waitlist_entry.bump,
+        has_one = user
+    )]
+    pub waitlist_entry: Account<'info, WaitlistEntry>,
+    
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"waitlist_user", run.platform.as_ref(), run_id.to_le_bytes().as_ref(), user.key().as_ref()],
+        bump = waitlist_marker.bump
+    )]
+    pub waitlist_marker: Account<'info, WaitlistMarker>,
+    
+    #[account(
+        mut,
+        seeds = [b"waitlist_vault", run.platform.as_ref(), run_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub waitlist_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = run.mint, token::authority = user)]
+    pub user_token_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64)]
+pub struct CreateSquad<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()], bump = run.bump)]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        init,
+        payer = leader,
+        space = Squad::LEN,
+        seeds = [b"squad", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), leader.key().as_ref()],
+        bump
+    )]
+    pub squad: Account<'info, Squad>,
+    
+    /// Escrows contributions until commit and receives the squad's payout
+    #[account(
+        init,
+        payer = leader,
+        associated_token::mint = mint,
+        associated_token::authority = squad
+    )]
+    pub squad_vault: Account<'info, TokenAccount>,
+    
+    #[account(address = run.mint)]
+    pub mint: Account<'info, token::Mint>,
+    
+    #[account(mut)]
+    pub leader: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64, leader: Pubkey)]
+pub struct JoinSquad<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()], bump = run.bump)]
+    pub run: Account<'info, Run>,
+    
+    #[account(mut, seeds = [b"squad", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), leader.as_ref()], bump = squad.bump)]
+    pub squad: Account<'info, Squad>,
+    
+    #[account(
+        init_if_needed,
+        payer = member,
+        space = SquadMember::LEN,
+        seeds = [b"squad_member", squad.key().as_ref(), member.key().as_ref()],
+        bump
+    )]
+    pub squad_member: Account<'info, SquadMember>,
+    
+    #[account(mut, associated_token::mint = run.mint, associated_token::authority = squad)]
+    pub squad_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = run.mint, token::authority = member)]
+    pub member_token_account: Account<'info, TokenAccount>,
+    
+    /// Required only for runs that gate deposits on KYC
+    #[account(seeds = [b"kyc", platform.key().as_ref(), member.key().as_ref()], bump = kyc_attestation.bump)]
+    pub kyc_attestation: Option<Account<'info, KycAttestation>>,
+    
+    /// CHECK: must be uninitialized - the wallet is blacklisted if this PDA exists
+    #[account(seeds = [b"blacklist", platform.key().as_ref(), member.key().as_ref()], bump)]
+    pub blacklist_entry: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub member: Signer<'info>,
+    
+    /// Tags the transfer with an SPL Memo for reconciliation when provided
+    pub memo_program: Option<Program<'info, Memo>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64, leader: Pubkey)]
+pub struct LeaveSquad<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()], bump = run.bump)]
+    pub run: Account<'info, Run>,
+    
+    #[account(mut, seeds = [b"squad", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), leader.as_ref()], bump = squad.bump)]
+    pub squad: Account<'info, Squad>,
+    
+    #[account(
+        mut,
+        close = member,
+        seeds = [b"squad_member", squad.key().as_ref(), member.key().as_ref()],
+        bump = squad_member.bump,
+        has_one = member
+    )]
+    pub squad_member: Account<'info, SquadMember>,
+    
+    #[account(mut, associated_token::mint = run.mint, associated_token::authority = squad)]
+    pub squad_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = run.mint, token::authority = member)]
+    pub member_token_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub member: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64)]
+pub struct CommitSquad<'info> {
+    #[account(mut, seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        mut,
+        seeds = [b"squad", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), leader.key().as_ref()],
+        bump = squad.bump
+    )]
+    pub squad: Account<'info, Squad>,
+    
+    /// The squad's participation, keyed by the squad PDA in place of a wallet
+    #[account(
+        init,
+        payer = leader,
+        space = UserParticipation::LEN,
+        seeds = [b"participation", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), squad.key().as_ref()],
+        bump
+    )]
+    pub user_participation: Account<'info, UserParticipation>,
+    
+    #[account(
+        init_if_needed,
+        payer = leader,
+        space = ParticipantPage::LEN,
+        seeds = [b"participant_page", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), run.participant_page_index().to_le_bytes().as_ref()],
+        bump
+    )]
+    pub participant_page: AccountLoader<'info, ParticipantPage>,
+    
+    #[account(mut, associated_token::mint = run.mint, associated_token::authority = squad)]
+    pub squad_vault: Account<'info, TokenAccount>,
+    
+    /// CHECK: checked by Run::check_vault, which reports a missing vault as VaultNotInitialized
+    #[account(
+        mut,
+        seeds = [b"vault", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub run_vault: UncheckedAccount<'info>,
+    
+    /// Required only during a run's priority window
+    #[account(seeds = [b"stake", platform.key().as_ref(), leader.key().as_ref()], bump = stake.bump)]
+    pub stake: Option<Account<'info, Stake>>,
+    
+    /// Required only for runs that gate deposits on KYC
+    #[account(seeds = [b"kyc", platform.key().as_ref(), leader.key().as_ref()], bump = kyc_attestation.bump)]
+    pub kyc_attestation: Option<Account<'info, KycAttestation>>,
+    
+    /// CHECK: must be uninitialized - the wallet is blacklisted if this PDA exists
+    #[account(seeds = [b"blacklist", platform.key().as_ref(), leader.key().as_ref()], bump)]
+    pub blacklist_entry: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub leader: Signer<'info>,
+    
+    /// Tags the transfer with an SPL Memo for reconciliation when provided
+    pub memo_program: Option<Program<'info, Memo>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64, leader: Pubkey)]
+pub struct WithdrawSquad<'info> {
+    #[account(mut, seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(seeds = [b"squad", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), leader.as_ref()], bump = squad.bump)]
+    pub squad: Account<'info, Squad>,
+    
+    #[account(
+        mut,
+        seeds = [b"participation", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), squad.key().as_ref()],
+        bump = user_participation.bump
+    )]
+    pub user_participation: Account<'info, UserParticipation>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub run_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, associated_token::mint = run.mint, associated_token::authority = squad)]
+    pub squad_vault: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64, leader: Pubkey)]
+pub struct ClaimMemberShare<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()], bump = run.bump)]
+    pub run: Account<'info, Run>,
+    
+    #[account(mut, seeds = [b"squad", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), leader.as_ref()], bump = squad.bump)]
+    pub squad: Account<'info, Squad>,
+    
+    #[account(
+        seeds = [b"participation", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), squad.key().as_ref()],
+        bump = user_participation.bump
+    )]
+    pub user_participation: Account<'info, UserParticipation>,
+    
+    #[account(
+        mut,
+        close = member,
+        seeds = [b"squad_member", squad.key().as_ref(), member.key().as_ref()],
+        bump = squad_member.bump,
+        has_one = member
+    )]
+    pub squad_member: Account<'info, SquadMember>,
+    
+    #[account(mut, associated_token::mint = mint, associated_token::authority = squad)]
+    pub squad_vault: Account<'info, TokenAccount>,
+    
+    /// Created if the member closed or never had an associated token account for the run mint
+    #[account(
+        init_if_needed,
+        payer = member,
+        associated_token::mint = mint,
+        associated_token::authority = member
+    )]
+    pub member_token_account: Account<'info, TokenAccount>,
+    
+    #[account(address = run.mint)]
+    pub mint: Account<'info, token::Mint>,
+    
+    /// CHECK: must be uninitialized - the wallet is blacklisted if this PDA exists
+    #[account(seeds = [b"blacklist", platform.key().as_ref(), member.key().as_ref()], bump)]
+    pub blacklist_entry: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub member: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64, leader: Pubkey)]
+pub struct DistributeSquadBonus<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()], bump = run.bump)]
+    pub run: Account<'info, Run>,
+    
+    #[account(seeds = [b"squad", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), leader.as_ref()], bump = squad.bump)]
+    pub squad: Account<'info, Squad>,
+    
+    #[account(mut, token::mint = run.mint, token::authority = funder)]
+    pub funder_token_account: Account<'info, TokenAccount>,
+    
+    pub funder: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64)]
+pub struct ManageRun<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump,
+        constraint = run.is_operator(&platform, authority.key()) @ ErrorCode::NotRunOperator
+    )]
+    pub run: Account<'info, Run>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64, user: Pubkey)]
+pub struct SnapshotVoteWeight<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()], bump = run.bump)]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        mut,
+        seeds = [b"participation", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), user.as_ref()],
+        bump = user_participation.bump
+    )]
+    pub user_participation: Account<'info, UserParticipation>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64)]
+pub struct StartRun<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()],
+        bump = platform.bump
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump,
+        constraint = run.is_operator(&platform, authority.key())
+            || automation.as_ref().is_some_and(|a| a.thread == authority.key()) @ ErrorCode::NotRunOperator
+    )]
+    pub run: Account<'info, Run>,
+    
+    /// CHECK: checked by Run::check_vault, which reports a missing vault as VaultNotInitialized
+    #[account(seeds = [b"vault", platform.key().as_ref(), run_id.to_le_bytes().as_ref()], bump)]
+    pub run_vault: UncheckedAccount<'info>,
+    
+    #[account(seeds = [b"automation", platform.key().as_ref(), run_id.to_le_bytes().as_ref()], bump = automation.bump)]
+    pub automation: Option<Account<'info, RunAutomation>>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64)]
+pub struct RegisterRunAutomation<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump,
+        constraint = run.is_operator(&platform, authority.key()) @ ErrorCode::NotRunOperator
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = RunAutomation::LEN,
+        seeds = [b"automation", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub automation: Account<'info, RunAutomation>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64)]
+pub struct CancelRunAutomation<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump,
+        constraint = run.is_operator(&platform, authority.key()) @ ErrorCode::NotRunOperator
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"automation", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = automation.bump
+    )]
+    pub automation: Account<'info, RunAutomation>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(run_id: u64)]
+pub struct SettleRun<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()],
+        bump = platform.bump
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump,
+        constraint = run.is_operator(&platform, authority.key()) @ ErrorCode::NotRunOperator
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub run_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"platform_fee_vault", platform.key().as_ref()],
+        bump
+    )]
+    pub platform_fee_vault: Account<'info, TokenAccount>,
+    
+    /// Required once revenue sharing is on
+    #[account(
+        mut,
+        seeds = [b"revenue_vault", platform.key().as_ref()],
+        bump
+    )]
+    pub revenue_vault: Option<Account<'info, TokenAccount>>,
+    
+    /// Required when the run has a manager
+    #[account(
+        mut,
+        seeds = [b"manager", platform.key().as_ref(), run.manager.as_ref()],
+        bump = manager_profile.bump
+    )]
+    pub manager_profile: Option<Account<'info, Manager>>,
+    
+    /// Required when the run charges a manager fee
+    #[account(
+        mut,
+        seeds = [b"manager_fee_vault", platform.key().as_ref(), run.manager.as_ref()],
+        bump
+    )]
+    pub manager_fee_vault: Option<Account<'info, TokenAccount>>,
+    
+    #[account(
+        init,
+        payer = authority,
+        token::mint = usdc_mint,
+        token::authority = run,
+        seeds = [b"bond_vault", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub bond_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = authority
+    )]
+    pub settler_token_account: Account<'info, TokenAccount>,
+    
+    #[account(address = run_vault.mint)]
+    pub usdc_mint: Account<'info, token::Mint>,
+    
+    #[account(
+        mut,
+        seeds = [b"share_table", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub share_table: AccountLoader<'info, ShareTable>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    /// Required when the platform has an attestor
+    pub attestor: Option<Signer<'info>>,
+    
+    /// Required when the platform has a settlement committee
+    #[account(seeds = [b"settlement_committee", platform.key().as_ref()], bump = settlement_committee.bump)]
+    pub settlement_committee: Option<Account<'info, SettlementCommittee>>,
+    
+    /// Required when the platform has a settlement committee: the approvals of the figures being settled
+    #[account(
+        seeds = [
+            b"settlement_approval",
+            platform.key().as_ref(),
+            run_id.to_le_bytes().as_ref(),
+            settlement_approval.final_balance.to_le_bytes().as_ref(),
+            settlement_approval.shares_hash.as_ref(),
+        ],
+        bump = settlement_approval.bump
+    )]
+    pub settlement_approval: Option<Account<'info, SettlementApproval>>,
+    
+    /// CHECK: instructions sysvar, required when the platform has a risk oracle
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+    
+    /// Tags the transfer with an SPL Memo for reconciliation when provided
+    pub memo_program: Option<Program<'info, Memo>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Admin context that stays usable after the authority key expires
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()],
+        bump = platform.bump,
+        has_one = authority
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(mut, seeds = [b"audit_log", platform.key().as_ref()], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()],
+        bump = platform.bump,
+        constraint = platform.pending_authority == new_authority.key() @ ErrorCode::NotPendingAuthority
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(mut, seeds = [b"audit_log", platform.key().as_ref()], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    
+    pub new_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetSettlementCommittee<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()],
+        bump = platform.bump,
+        has_one = authority,
+        constraint = platform.is_authority_current() @ ErrorCode::AuthorityExpired
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(mut, seeds = [b"audit_log", platform.key().as_ref()], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = SettlementCommittee::LEN,
+        seeds = [b"settlement_committee", platform.key().as_ref()],
+        bump
+    )]
+    pub settlement_committee: Account<'info, SettlementCommittee>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64, final_balance: u64, shares_hash: [u8; 32])]
+pub struct ApproveSettlement<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()], bump = run.bump)]
+    pub run: Account<'info, Run>,
+    
+    #[account(seeds = [b"settlement_committee", platform.key().as_ref()], bump = settlement_committee.bump)]
+    pub settlement_committee: Account<'info, SettlementCommittee>,
+    
+    #[account(
+        init_if_needed,
+        payer = approver,
+        space = SettlementApproval::LEN,
+        seeds = [
+            b"settlement_approval",
+            platform.key().as_ref(),
+            run_id.to_le_bytes().as_ref(),
+            final_balance.to_le_bytes().as_ref(),
+            shares_hash.as_ref(),
+        ],
+        bump
+    )]
+    pub settlement_approval: Account<'info, SettlementApproval>,
+    
+    #[account(mut)]
+    pub approver: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64)]
+pub struct StageSettlement<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump,
+        constraint = run.is_operator(&platform, authority.key()) @ ErrorCode::NotRunOperator
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        mut,
+        seeds = [b"share_table", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub share_table: AccountLoader<'info, ShareTable>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64)]
+pub struct ManageRunLookupTable<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump,
+        constraint = run.is_operator(&platform, authority.key()) @ ErrorCode::NotRunOperator
+    )]
+    pub run: Account<'info, Run>,
+    
+    /// CHECK: derived from the run and recent slot on creation, matched against run.lookup_table after
+    #[account(mut)]
+    pub lookup_table: UncheckedAccount<'info>,
+    
+    /// Pays the lookup table's rent
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    /// CHECK: address lookup table program
+    #[account(address = ADDRESS_LOOKUP_TABLE_PROGRAM_ID)]
+    pub address_lookup_table_program: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ManageRunLookupTable<'info> {
+    fn invoke_with_run_seeds(&self, data: Vec<u8>) -> Result<()> {
+        let platform_key = self.platform.key();
+        let run_id_bytes = self.run.run_id.to_le_bytes();
+        let run_seeds = &[
+            b"run".as_ref(),
+            platform_key.as_ref(),
+            run_id_bytes.as_ref(),
+            &[self.run.bump],
+        ];
+        let instruction = Instruction {
+            program_id: ADDRESS_LOOKUP_TABLE_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(self.lookup_table.key(), false),
+                AccountMeta::new_readonly(self.run.key(), true),
+                AccountMeta::new(self.authority.key(), true),
+                AccountMeta::new_readonly(self.system_program.key(), false),
+            ],
+            data,
+        };
+        invoke_signed(
+            &instruction,
+            &[
+                self.lookup_table.to_account_info(),
+                self.run.to_account_info(),
+                self.authority.to_account_info(),
+                self.system_program.to_account_info(),
+                self.address_lookup_table_program.to_account_info(),
+            ],
+            &[&run_seeds[..]],
+        )?;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64)]
+pub struct InitShareTable<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        seeds = [b"run", run.platform.as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump,
+        has_one = platform,
+        constraint = run.is_operator(&platform, authority.key()) @ ErrorCode::NotRunOperator
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = ShareTable::space_for(0),
+        seeds = [b"share_table", run.platform.as_ref(), run_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub share_table: AccountLoader<'info, ShareTable>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64)]
+pub struct AllocateShareTable<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        seeds = [b"run", run.platform.as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump,
+        has_one = platform,
+        constraint = run.is_operator(&platform, authority.key()) @ ErrorCode::NotRunOperator
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        mut,
+        seeds = [b"share_table", run.platform.as_ref(), run_id.to_le_bytes().as_ref()],
+        bump,
+        realloc = ShareTable::next_len(share_table.to_account_info().data_len(), run.participant_count),
+        realloc::payer = authority,
+        realloc::zero = false
+    )]
+    pub share_table: AccountLoader<'info, ShareTable>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64, recipient: Pubkey)]
+pub struct Tip<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()], bump = run.bump)]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        mut,
+        seeds = [b"participation", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), tipper.key().as_ref()],
+        bump = sender_participation.bump
+    )]
+    pub sender_participation: Account<'info, UserParticipation>,
+    
+    #[account(
+        mut,
+        seeds = [b"participation", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), recipient.as_ref()],
+        bump = recipient_participation.bump
+    )]
+    pub recipient_participation: Account<'info, UserParticipation>,
+    
+    #[account(seeds = [b"vault", platform.key().as_ref(), run_id.to_le_bytes().as_ref()], bump)]
+    pub run_vault: Account<'info, TokenAccount>,
+    
+    /// CHECK: must be uninitialized - the wallet is blacklisted if this PDA exists
+    #[account(seeds = [b"blacklist", platform.key().as_ref(), tipper.key().as_ref()], bump)]
+    pub blacklist_entry: UncheckedAccount<'info>,
+    
+    pub tipper: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(run_id: u64)]
+pub struct Withdraw<'info> {
+    #[account(mut, seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        mut,
+        seeds = [b"participation", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), user.key().as_ref()],
+        bump = user_participation.bump
+    )]
+    pub user_participation: Account<'info, UserParticipation>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub run_vault: Account<'info, TokenAccount>,
+    
+    /// Created if the user closed or never had an associated token account for the run mint
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = mint,
+        associated_token::authority = user
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+    
+    #[account(address = run.mint)]
+    pub mint: Account<'info, token::Mint>,
+    
+    /// CHECK: must be uninitialized - the wallet is blacklisted if this PDA exists
+    #[account(seeds = [b"blacklist", platform.key().as_ref(), user.key().as_ref()], bump)]
+    pub blacklist_entry: UncheckedAccount<'info>,
+    
+    /// Only needed for a vote-escrow bonus boost
+    #[account(seeds = [b"stake", platform.key().as_ref(), user.key().as_ref()], bump = stake.bump)]
+    pub stake: Option<Account<'info, Stake>>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    /// Required when closing a participation whose rent it sponsored
+    #[account(mut, seeds = [b"rent_treasury", platform.key().as_ref()], bump = rent_treasury.bump)]
+    pub rent_treasury: Option<Account<'info, RentTreasury>>,
+    
+    /// Only needed to refund a deposit made in another stablecoin
+    #[account(
+        seeds = [b"accepted_mint", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), user_participation.deposit_mint.as_ref()],
+        bump = accepted_mint.bump
+    )]
+    pub accepted_mint: Option<Account<'info, AcceptedMint>>,
+    
+    /// Tags the transfer with an SPL Memo for reconciliation when provided
+    pub memo_program: Option<Program<'info, Memo>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Withdraw<'info> {
+    /// Pay the user's settled share, returning (share, paid now, profit left vesting)
+    pub fn pay_share(&mut self) -> Result<(u64, u64, u64)> {
+        require!(self.run.status != RunStatus::Disputed, ErrorCode::SettlementDisputed);
+        require!(self.run.status == RunStatus::Settled, ErrorCode::RunNotSettled);
+        require!(!self.user_participation.withdrawn, ErrorCode::AlreadyWithdrawn);
+        BlacklistEntry::check_not_listed(&self.blacklist_entry)?;
+        self.user_participation.check_not_frozen()?;
+        self.run.check_solvency(self.run_vault.amount)?;
+        let now = Clock::get()?.unix_timestamp;
+        self.run.check_claim_open(now)?;
+
+        let vote_boost_bps = Stake::vote_boost_bps(
+            self.stake.as_deref(),
+            self.platform.ve_boost_weight,
+            now,
+        );
+        let user_share = self.run.settled_share(
+            &self.user_participation,
+            self.run_vault.amount,
+            self.platform.is_feature_enabled(FEATURE_BONUSES),
+            vote_boost_bps,
+        )?;
+        if self.run.withdrawn_count + 1 == self.run.participant_count {
+            msg!(
+                "Last withdrawal - user {} gets remaining vault balance: {}",
+                self.user.key(),
+                user_share
+            );
+        }
+        let (paid_now, vesting_profit) = self.run.split_vesting(&self.user_participation, user_share);
+
+        write_memo(
+            &self.memo_program,
+            &format!("instinct:withdraw run_id={} participation={}", self.run.run_id, self.user_participation.key()),
+        )?;
+        // Transfer USDC from vault to user
+        let run_id_bytes = self.run.run_id.to_le_bytes();
+        let run_seeds = &[
+            b"run".as_ref(),
+            self.run.platform.as_ref(),
+            run_id_bytes.as_ref(),
+            &[self.run.bump],
+        ];
+        let signer = &[&run_seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: self.run_vault.to_account_info(),
+            to: self.user_token_account.to_account_info(),
+            authority: self.run.to_account_info(),
+        };
+        let cpi_program = self.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, paid_now)?;
+
+        self.run.record_payout(&mut self.user_participation, user_share, vesting_profit)?;
+
+        // Principal leaves the platform's TVL regardless of the P/L paid out
+        let platform = &mut self.platform;
+        platform.total_value_locked = platform.total_value_locked
+            .saturating_sub(self.user_participation.deposit_amount);
+        platform.record_withdrawal(paid_now)?;
+
+        Ok((user_share, paid_now, vesting_profit))
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64, user: Pubkey)]
+pub struct PreviewWithdraw<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        seeds = [b"participation", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), user.as_ref()],
+        bump = user_participation.bump
+    )]
+    pub user_participation: Account<'info, UserParticipation>,
+    
+    #[account(
+        seeds = [b"vault", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub run_vault: Account<'info, TokenAccount>,
+    
+    /// Only needed for a vote-escrow bonus boost
+    #[account(seeds = [b"stake", platform.key().as_ref(), user.as_ref()], bump = stake.bump)]
+    pub stake: Option<Account<'info, Stake>>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64)]
+pub struct PushPayouts<'info> {
+    #[account(mut, seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump, has_one = authority, constraint = platform.is_authority_current() @ ErrorCode::AuthorityExpired)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub run_vault: Account<'info, TokenAccount>,
+    
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64)]
+pub struct FinalizeSettlement<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(mut, seeds = [b"keeper_incentive", platform.key().as_ref()], bump = keeper_incentive.bump)]
+    pub keeper_incentive: Option<Account<'info, KeeperIncentive>>,
+    
+    #[account(mut)]
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64)]
+pub struct SetCapitalMigration<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"participation", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), user.key().as_ref()],
+        bump = user_participation.bump
+    )]
+    pub user_participation: Account<'info, UserParticipation>,
+    
+    pub user: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(old_run_id: u64, new_run_id: u64)]
+pub struct MigrateCapital<'info> {
+    #[account(mut, seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump, has_one = authority, constraint = platform.is_authority_current() @ ErrorCode::AuthorityExpired)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"run", platform.key().as_ref(), old_run_id.to_le_bytes().as_ref()],
+        bump = old_run.bump
+    )]
+    pub old_run: Account<'info, Run>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", platform.key().as_ref(), old_run_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub old_run_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"participation", platform.key().as_ref(), old_run_id.to_le_bytes().as_ref(), user.key().as_ref()],
+        bump = old_participation.bump
+    )]
+    pub old_participation: Account<'info, UserParticipation>,
+    
+    #[account(
+        mut,
+        seeds = [b"run", platform.key().as_ref(), new_run_id.to_le_bytes().as_ref()],
+        bump = new_run.bump,
+        constraint = new_run.mint == old_run.mint @ ErrorCode::InvalidMigrationRun
+    )]
+    pub new_run: Account<'info, Run>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", platform.key().as_ref(), new_run_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub new_run_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = UserParticipation::LEN,
+        seeds = [b"participation", platform.key().as_ref(), new_run_id.to_le_bytes().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub new_participation: Account<'info, UserParticipation>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = ParticipantPage::LEN,
+        seeds = [b"participant_page", platform.key().as_ref(), new_run_id.to_le_bytes().as_ref(), new_run.participant_page_index().to_le_bytes().as_ref()],
+        bump
+    )]
+    pub participant_page: AccountLoader<'info, ParticipantPage>,
+    
+    /// CHECK: the migrating participant; bound by the participation seeds
+    pub user: UncheckedAccount<'info>,
+    
+    /// CHECK: must be uninitialized - the wallet is blacklisted if this PDA exists
+    #[account(seeds = [b"blacklist", platform.key().as_ref(), user.key().as_ref()], bump)]
+    pub blacklist_entry: UncheckedAccount<'info>,
+    
+    /// CHECK: the user's stake, uninitialized if they never staked; required so a staker's boost
+    /// cannot be left out
+    #[account(seeds = [b"stake", platform.key().as_ref(), user.key().as_ref()], bump)]
+    pub stake: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    /// Tags the transfer with an SPL Memo for reconciliation when provided
+    pub memo_program: Option<Program<'info, Memo>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64)]
+pub struct SweepUnclaimed<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump, has_one = authority, constraint = platform.is_authority_current() @ ErrorCode::AuthorityExpired)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(mut, seeds = [b"audit_log", platform.key().as_ref()], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    
+    #[account(
+        mut,
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub run_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"platform_fee_vault", platform.key().as_ref()],
+        bump
+    )]
+    pub platform_fee_vault: Account<'info, TokenAccount>,
+    
+    pub authority: Signer<'info>,
+    
+    /// Tags the transfer with an SPL Memo for reconciliation when provided
+    pub memo_program: Option<Program<'info, Memo>>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64)]
+pub struct DisputeSettlement<'info> {
+    #[account(
+        mut,
+        seeds = [b"run", run.platform.as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        seeds = [b"participation", run.platform.as_ref(), run_id.to_le_bytes().as_ref(), user.key().as_ref()],
+        bump = user_participation.bump
+    )]
+    pub user_participation: Account<'info, UserParticipation>,
+    
+    pub user: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64)]
+pub struct ResolveDispute<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump, has_one = authority, constraint = platform.is_authority_current() @ ErrorCode::AuthorityExpired)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(mut, seeds = [b"audit_log", platform.key().as_ref()], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    
+    #[account(
+        mut,
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        mut,
+        seeds = [b"bond_vault", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub bond_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = bond_vault.mint,
+        token::authority = run.settler
+    )]
+    pub settler_token_account: Account<'info, TokenAccount>,
+    
+    pub authority: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64)]
+pub struct ReleaseSettlementBond<'info> {
+    #[account(
+        mut,
+        seeds = [b"run", run.platform.as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        mut,
+        seeds = [b"bond_vault", run.platform.as_ref(), run_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub bond_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = bond_vault.mint,
+        token::authority = run.settler
+    )]
+    pub settler_token_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64, user_pubkey: Pubkey)]
+pub struct UpdateVoteStats<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump,
+        constraint = run.is_operator(&platform, authority.key()) @ ErrorCode::NotRunOperator
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        mut,
+        seeds = [b"participation", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), user_pubkey.as_ref()],
+        bump = user_participation.bump
+    )]
+    pub user_participation: Account<'info, UserParticipation>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64, delegate: Pubkey)]
+pub struct DelegateVotes<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()], bump = run.bump)]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        mut,
+        seeds = [b"participation", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), user.key().as_ref()],
+        bump = user_participation.bump
+    )]
+    pub user_participation: Account<'info, UserParticipation>,
+    
+    #[account(
+        mut,
+        seeds = [b"participation", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), delegate.as_ref()],
+        bump = delegate_participation.bump
+    )]
+    pub delegate_participation: Account<'info, UserParticipation>,
+    
+    pub user: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64)]
+pub struct UndelegateVotes<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()], bump = run.bump)]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        mut,
+        seeds = [b"participation", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), user.key().as_ref()],
+        bump = user_participation.bump,
+        constraint = user_participation.vote_delegate != Pubkey::default() @ ErrorCode::NotDelegated
+    )]
+    pub user_participation: Account<'info, UserParticipation>,
+    
+    #[account(
+        mut,
+        seeds = [b"participation", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), user_participation.vote_delegate.as_ref()],
+        bump = delegate_participation.bump
+    )]
+    pub delegate_participation: Account<'info, UserParticipation>,
+    
+    pub user: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64, round: u8)]
+pub struct PostTradeProposal<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump,
+        constraint = run.is_operator(&platform, authority.key()) @ ErrorCode::NotRunOperator
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = TradeProposal::LEN,
+        seeds = [b"proposal", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), &[round]],
+        bump
+    )]
+    pub trade_proposal: Account<'info, TradeProposal>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64, round: u8)]
+pub struct OpenVoteRound<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump,
+        constraint = run.is_operator(&platform, authority.key()) @ ErrorCode::NotRunOperator
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = VoteRound::LEN,
+        seeds = [b"vote_round", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), &[round]],
+        bump
+    )]
+    pub vote_round: Account<'info, VoteRound>,
+    
+    #[account(
+        seeds = [b"proposal", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), &[round]],
+        bump = trade_proposal.bump
+    )]
+    pub trade_proposal: Account<'info, TradeProposal>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        token::mint = mint,
+        token::authority = run,
+        seeds = [b"round_rewards", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub rewards_vault: Account<'info, TokenAccount>,
+    
+    #[account(address = run.mint)]
+    pub mint: Account<'info, token::Mint>,
+    
+    #[account(mut, token::mint = run.mint, token::authority = authority)]
+    pub authority_token_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64, round: u8)]
+pub struct CastVote<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()], bump = run.bump)]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        mut,
+        seeds = [b"vote_round", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), &[round]],
+        bump = vote_round.bump
+    )]
+    pub vote_round: Account<'info, VoteRound>,
+    
+    #[account(
+        seeds = [b"participation", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), user.key().as_ref()],
+        bump = user_participation.bump
+    )]
+    pub user_participation: Account<'info, UserParticipation>,
+    
+    #[account(
+        init,
+        payer = user,
+        space = VoteReceipt::LEN,
+        seeds = [b"vote", vote_round.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub vote_receipt: Account<'info, VoteReceipt>,
+    
+    /// CHECK: must be uninitialized - the wallet is blacklisted if this PDA exists
+    #[account(seeds = [b"blacklist", platform.key().as_ref(), user.key().as_ref()], bump)]
+    pub blacklist_entry: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(run_id: u64, round: u8)]
+pub struct FinalizeRoundDecision<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"vote_round", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), &[round]],
+        bump = vote_round.bump
+    )]
+    pub vote_round: Account<'info, VoteRound>,
+    
+    #[account(mut, seeds = [b"keeper_incentive", platform.key().as_ref()], bump = keeper_incentive.bump)]
+    pub keeper_incentive: Option<Account<'info, KeeperIncentive>>,
+    
+    #[account(mut)]
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64, round: u8)]
+pub struct ResolveVoteRound<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump,
+        constraint = run.is_operator(&platform, authority.key()) @ ErrorCode::NotRunOperator
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        mut,
+        seeds = [b"vote_round", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), &[round]],
+        bump = vote_round.bump
+    )]
+    pub vote_round: Account<'info, VoteRound>,
+    
+    #[account(
+        mut,
+        seeds = [b"round_rewards", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub rewards_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = run.mint, token::authority = authority)]
+    pub authority_token_account: Account<'info, TokenAccount>,
+    
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64, round: u8)]
+pub struct ClaimRoundReward<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()], bump = run.bump)]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        mut,
+        seeds = [b"vote_round", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), &[round]],
+        bump = vote_round.bump
+    )]
+    pub vote_round: Account<'info, VoteRound>,
+    
+    #[account(
+        mut,
+        seeds = [b"vote", vote_round.key().as_ref(), user.key().as_ref()],
+        bump = vote_receipt.bump
+    )]
+    pub vote_receipt: Account<'info, VoteReceipt>,
+    
+    #[account(
+        mut,
+        seeds = [b"round_rewards", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub rewards_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = run.mint, token::authority = user)]
+    pub user_token_account: Account<'info, TokenAccount>,
+    
+    /// CHECK: must be uninitialized - the wallet is blacklisted if this PDA exists
+    #[account(seeds = [b"blacklist", platform.key().as_ref(), user.key().as_ref()], bump)]
+    pub blacklist_entry: UncheckedAccount<'info>,
+    
+    pub user: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64, round: u8)]
+pub struct DistributeRoundRewards<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump,
+        constraint = run.is_operator(&platform, authority.key()) @ ErrorCode::NotRunOperator
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        mut,
+        seeds = [b"vote_round", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), &[round]],
+        bump = vote_round.bump
+    )]
+    pub vote_round: Account<'info, VoteRound>,
+    
+    #[account(
+        mut,
+        seeds = [b"round_rewards", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub rewards_vault: Account<'info, TokenAccount>,
+    
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(action_id: u64)]
+pub struct QueueAction<'info> {
+    #[account(
+        seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()],
+        bump = platform.bump,
+        has_one = authority,
+        constraint = platform.is_authority_current() @ ErrorCode::AuthorityExpired
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(mut, seeds = [b"audit_log", platform.key().as_ref()], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = ScheduledAction::LEN,
+        seeds = [b"scheduled_action", platform.key().as_ref(), action_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub scheduled_action: Account<'info, ScheduledAction>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(action_id: u64)]
+pub struct CancelAction<'info> {
+    #[account(
+        seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()],
+        bump = platform.bump,
+        has_one = authority,
+        constraint = platform.is_authority_current() @ ErrorCode::AuthorityExpired
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(mut, seeds = [b"audit_log", platform.key().as_ref()], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"scheduled_action", platform.key().as_ref(), action_id.to_le_bytes().as_ref()],
+        bump = scheduled_action.bump
+    )]
+    pub scheduled_action: Account<'info, ScheduledAction>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(action_id: u64)]
+pub struct ExecuteAction<'info> {
+    #[account(mut, seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(mut, seeds = [b"audit_log", platform.key().as_ref()], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"scheduled_action", platform.key().as_ref(), action_id.to_le_bytes().as_ref()],
+        bump = scheduled_action.bump
+    )]
+    pub scheduled_action: Account<'info, ScheduledAction>,
+    
+    /// CHECK: current platform authority, refunded the action's rent
+    #[account(mut, address = platform.authority)]
+    pub authority: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"platform_fee_vault", platform.key().as_ref()],
+        bump
+    )]
+    pub platform_fee_vault: Account<'info, TokenAccount>,
+    
+    /// Required for treasury spends
+    #[account(mut)]
+    pub destination_token_account: Option<Account<'info, TokenAccount>>,
+    
+    /// Required for settlement committee changes
+    #[account(mut, seeds = [b"settlement_committee", platform.key().as_ref()], bump = settlement_committee.bump)]
+    pub settlement_committee: Option<Account<'info, SettlementCommittee>>,
+    
+    pub executor: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct SetGovernance<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()],
+        bump = platform.bump,
+        has_one = authority,
+        constraint = platform.is_authority_current() @ ErrorCode::AuthorityExpired
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(mut, seeds = [b"audit_log", platform.key().as_ref()], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    
+    /// CHECK: governance account of the realm; it signs executed proposals and becomes the authority
+    #[account(owner = governance_program.key())]
+    pub governance: UncheckedAccount<'info>,
+    
+    /// CHECK: the SPL Governance program deployment hosting the realm
+    #[account(executable)]
+    pub governance_program: UncheckedAccount<'info>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GovernanceAction<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()],
+        bump = platform.bump,
+        has_one = authority,
+        constraint = platform.is_authority_current() @ ErrorCode::AuthorityExpired
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(mut, seeds = [b"audit_log", platform.key().as_ref()], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    
+    #[account(owner = platform.governance_program @ ErrorCode::NotCalledByGovernance)]
+    pub authority: Signer<'info>,
+    
+    /// CHECK: instructions sysvar, used to identify the calling program
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterManager<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        init,
+        payer = wallet,
+        space = Manager::LEN,
+        seeds = [b"manager", platform.key().as_ref(), wallet.key().as_ref()],
+        bump
+    )]
+    pub manager_profile: Account<'info, Manager>,
+    
+    #[account(
+        init,
+        payer = wallet,
+        token::mint = usdc_mint,
+        token::authority = manager_profile,
+        seeds = [b"manager_fee_vault", platform.key().as_ref(), wallet.key().as_ref()],
+        bump
+    )]
+    pub manager_fee_vault: Account<'info, TokenAccount>,
+    
+    #[account(address = platform.platform_fee_vault)]
+    pub platform_fee_vault: Account<'info, TokenAccount>,
+    
+    #[account(address = platform_fee_vault.mint)]
+    pub usdc_mint: Account<'info, token::Mint>,
+    
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterAgent<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        init,
+        payer = wallet,
+        space = Agent::LEN,
+        seeds = [b"agent", platform.key().as_ref(), wallet.key().as_ref()],
+        bump
+    )]
+    pub agent: Account<'info, Agent>,
+    
+    #[account(
+        init,
+        payer = wallet,
+        token::mint = usdc_mint,
+        token::authority = agent,
+        seeds = [b"agent_bond", platform.key().as_ref(), wallet.key().as_ref()],
+        bump
+    )]
+    pub agent_bond_vault: Account<'info, TokenAccount>,
+    
+    #[account(address = platform.platform_fee_vault)]
+    pub platform_fee_vault: Account<'info, TokenAccount>,
+    
+    #[account(address = platform_fee_vault.mint)]
+    pub usdc_mint: Account<'info, token::Mint>,
+    
+    #[account(mut, token::mint = usdc_mint, token::authority = wallet)]
+    pub wallet_token_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AddAgentBond<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"agent", platform.key().as_ref(), wallet.key().as_ref()],
+        bump = agent.bump
+    )]
+    pub agent: Account<'info, Agent>,
+    
+    #[account(
+        mut,
+        seeds = [b"agent_bond", platform.key().as_ref(), wallet.key().as_ref()],
+        bump
+    )]
+    pub agent_bond_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = agent_bond_vault.mint, token::authority = wallet)]
+    pub wallet_token_account: Account<'info, TokenAccount>,
+    
+    pub wallet: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
 }
 
-// ============================================================================
-// Account Structures
-// ============================================================================
+#[derive(Accounts)]
+pub struct BeginAgentUnbonding<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"agent", platform.key().as_ref(), wallet.key().as_ref()],
+        bump = agent.bump
+    )]
+    pub agent: Account<'info, Agent>,
+    
+    pub wallet: Signer<'info>,
+}
 
-#[account]
-pub struct Platform {
-    pub authority: Pubkey,           // Platform admin
-    pub platform_fee_bps: u16,       // Fee in basis points (1500 = 15%)
-    pub total_runs: u64,             // Total runs created
-    pub is_paused: bool,             // Emergency pause flag
-    pub bump: u8,                    // PDA bump
-    pub total_fees_collected: u64,   // Total fees collected across all runs
-    pub platform_fee_vault: Pubkey,  // Platform fee vault address
+#[derive(Accounts)]
+pub struct WithdrawAgentBond<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"agent", platform.key().as_ref(), wallet.key().as_ref()],
+        bump = agent.bump
+    )]
+    pub agent: Account<'info, Agent>,
+    
+    #[account(
+        mut,
+        seeds = [b"agent_bond", platform.key().as_ref(), wallet.key().as_ref()],
+        bump
+    )]
+    pub agent_bond_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = agent_bond_vault.mint)]
+    pub destination_token_account: Account<'info, TokenAccount>,
+    
+    pub wallet: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64, agent: Pubkey)]
+pub struct SetRunAgent<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump, has_one = authority, constraint = platform.is_authority_current() @ ErrorCode::AuthorityExpired)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump
+    )]
+    pub run: Account<'info, Run>,
+    
+    /// Required unless the agent is being removed
+    #[account(seeds = [b"agent", platform.key().as_ref(), agent.as_ref()], bump = agent_profile.bump)]
+    pub agent_profile: Option<Account<'info, Agent>>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64, index: u8, agent: Pubkey)]
+pub struct AddRunAgent<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump, has_one = authority, constraint = platform.is_authority_current() @ ErrorCode::AuthorityExpired)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(seeds = [b"agent", platform.key().as_ref(), agent.as_ref()], bump = agent_profile.bump)]
+    pub agent_profile: Account<'info, Agent>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = StrategyVault::LEN,
+        seeds = [b"strategy", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), &[index]],
+        bump
+    )]
+    pub strategy: Account<'info, StrategyVault>,
+    
+    #[account(
+        init,
+        payer = authority,
+        token::mint = mint,
+        token::authority = run,
+        seeds = [b"strategy_vault", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), &[index]],
+        bump
+    )]
+    pub strategy_vault: Account<'info, TokenAccount>,
+    
+    #[account(address = run.mint)]
+    pub mint: Account<'info, token::Mint>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64, round: u8)]
+pub struct AllocateCapital<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump,
+        constraint = run.is_operator(&platform, authority.key()) @ ErrorCode::NotRunOperator
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub run_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"vote_round", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), &[round]],
+        bump = vote_round.bump
+    )]
+    pub vote_round: Account<'info, VoteRound>,
+    
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64, index: u8)]
+pub struct MoveStrategyCapital<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump,
+        constraint = run.is_operator(&platform, authority.key()) @ ErrorCode::NotRunOperator
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub run_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"strategy", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), &[index]],
+        bump = strategy.bump
+    )]
+    pub strategy: Account<'info, StrategyVault>,
+    
+    #[account(mut, address = strategy.token_account)]
+    pub strategy_vault: Account<'info, TokenAccount>,
+    
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> MoveStrategyCapital<'info> {
+    fn transfer_with_run_seeds(&self, from: AccountInfo<'info>, to: AccountInfo<'info>, amount: u64) -> Result<()> {
+        let platform_key = self.platform.key();
+        let run_id_bytes = self.run.run_id.to_le_bytes();
+        let run_seeds = &[
+            b"run".as_ref(),
+            platform_key.as_ref(),
+            run_id_bytes.as_ref(),
+            &[self.run.bump],
+        ];
+        let signer = &[&run_seeds[..]];
+        let cpi_accounts = Transfer {
+            from,
+            to,
+            authority: self.run.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), cpi_accounts, signer);
+        token::transfer(cpi_ctx, amount)
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64)]
+pub struct InitPositionLedger<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        seeds = [b"run", run.platform.as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump,
+        has_one = platform,
+        constraint = run.is_operator(&platform, authority.key()) @ ErrorCode::NotRunOperator
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = PositionLedger::LEN,
+        seeds = [b"position_ledger", run.platform.as_ref(), run_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub position_ledger: AccountLoader<'info, PositionLedger>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(run_id: u64, from_subvault: u8, to_subvault: u8)]
+pub struct Rebalance<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump,
+        constraint = run.is_operator(&platform, authority.key()) @ ErrorCode::NotRunOperator
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        mut,
+        seeds = [b"strategy", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), &[from_subvault]],
+        bump = from_strategy.bump
+    )]
+    pub from_strategy: Account<'info, StrategyVault>,
+    
+    #[account(mut, address = from_strategy.token_account)]
+    pub from_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"strategy", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), &[to_subvault]],
+        bump = to_strategy.bump
+    )]
+    pub to_strategy: Account<'info, StrategyVault>,
+    
+    #[account(mut, address = to_strategy.token_account)]
+    pub to_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"position_ledger", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = position_ledger.load()?.bump
+    )]
+    pub position_ledger: AccountLoader<'info, PositionLedger>,
+    
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(run_id: u64, round: u8)]
+pub struct RecordTrade<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump,
+        constraint = run.is_operator(&platform, authority.key()) @ ErrorCode::NotRunOperator
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        mut,
+        seeds = [b"risk_config", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = risk_config.bump
+    )]
+    pub risk_config: Account<'info, RiskConfig>,
+    
+    #[account(
+        seeds = [b"vote_round", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), &[round]],
+        bump = vote_round.bump
+    )]
+    pub vote_round: Account<'info, VoteRound>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(run_id: u64, index: u8)]
+pub struct SettleStrategy<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()], bump = run.bump)]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        mut,
+        seeds = [b"strategy", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), &[index]],
+        bump = strategy.bump
+    )]
+    pub strategy: Account<'info, StrategyVault>,
+    
+    #[account(address = strategy.token_account)]
+    pub strategy_vault: Account<'info, TokenAccount>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64)]
+pub struct SlashAgent<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump, has_one = authority, constraint = platform.is_authority_current() @ ErrorCode::AuthorityExpired)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(mut, seeds = [b"audit_log", platform.key().as_ref()], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    
+    #[account(
+        mut,
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump
+    )]
+    pub run: Account<'info, Run>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = run_vault.mint == agent_bond_vault.mint @ ErrorCode::VaultNotInitialized
+    )]
+    pub run_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"agent", platform.key().as_ref(), run.agent.as_ref()],
+        bump = agent.bump
+    )]
+    pub agent: Account<'info, Agent>,
+    
+    #[account(
+        mut,
+        seeds = [b"agent_bond", platform.key().as_ref(), run.agent.as_ref()],
+        bump
+    )]
+    pub agent_bond_vault: Account<'info, TokenAccount>,
+    
+    pub authority: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimManagerFees<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        seeds = [b"manager", platform.key().as_ref(), wallet.key().as_ref()],
+        bump = manager_profile.bump
+    )]
+    pub manager_profile: Account<'info, Manager>,
+    
+    #[account(
+        mut,
+        seeds = [b"manager_fee_vault", platform.key().as_ref(), wallet.key().as_ref()],
+        bump
+    )]
+    pub manager_fee_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = manager_fee_vault.mint)]
+    pub destination_token_account: Account<'info, TokenAccount>,
+    
+    pub wallet: Signer<'info>,
+    
+    /// Tags the transfer with an SPL Memo for reconciliation when provided
+    pub memo_program: Option<Program<'info, Memo>>,
+    
+    pub token_program: Program<'info, Token>,
 }
 
-impl Platform {
-    pub const LEN: usize = 8 + 32 + 2 + 8 + 1 + 1 + 8 + 32;
+#[derive(Accounts)]
+#[instruction(run_id: u64, manager: Pubkey)]
+pub struct SetRunManager<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump, has_one = authority, constraint = platform.is_authority_current() @ ErrorCode::AuthorityExpired)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump
+    )]
+    pub run: Account<'info, Run>,
+    
+    /// Required unless the manager is being removed
+    #[account(seeds = [b"manager", platform.key().as_ref(), manager.as_ref()], bump = manager_profile.bump)]
+    pub manager_profile: Option<Account<'info, Manager>>,
+    
+    pub authority: Signer<'info>,
 }
 
-#[account]
-pub struct Run {
-    pub run_id: u64,                 // Unique run identifier
-    pub authority: Pubkey,           // Platform authority
-    pub status: RunStatus,           // Current status
-    pub total_deposited: u64,        // Total USDC deposited
-    pub final_balance: u64,          // Final balance after trading (after fee deduction)
-    pub platform_fee_amount: u64,    // Platform fee collected for this run
-    pub total_withdrawn: u64,        // Total amount withdrawn by users
-    pub withdrawn_count: u16,        // Number of users who have withdrawn
-    pub participant_count: u16,      // Number of participants
-    pub min_deposit: u64,            // Minimum deposit (e.g., 10 USDC)
-    pub max_deposit: u64,            // Maximum deposit (e.g., 100 USDC)
-    pub max_participants: u16,       // Max participants (e.g., 100)
-    pub created_at: i64,             // Unix timestamp
-    pub started_at: i64,             // Unix timestamp
-    pub ended_at: i64,               // Unix timestamp
-    pub bump: u8,                    // PDA bump
+#[derive(Accounts)]
+pub struct PausePlatform<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()],
+        bump = platform.bump,
+        constraint = platform.can_pause(pauser.key()) @ ErrorCode::NotAuthorityOrGuardian
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(mut, seeds = [b"audit_log", platform.key().as_ref()], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    
+    pub pauser: Signer<'info>,
 }
 
-impl Run {
-    pub const LEN: usize = 8 + 8 + 32 + 1 + 8 + 8 + 8 + 8 + 2 + 2 + 8 + 8 + 2 + 8 + 8 + 8 + 1;
+#[derive(Accounts)]
+#[instruction(run_id: u64)]
+pub struct PauseRun<'info> {
+    #[account(
+        seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()],
+        bump = platform.bump,
+        constraint = platform.can_pause(pauser.key()) @ ErrorCode::NotAuthorityOrGuardian
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(mut, seeds = [b"audit_log", platform.key().as_ref()], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    
+    #[account(
+        mut,
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump
+    )]
+    pub run: Account<'info, Run>,
+    
+    pub pauser: Signer<'info>,
 }
 
-#[account]
-pub struct UserParticipation {
-    pub user: Pubkey,                // User wallet
-    pub run_id: u64,                 // Associated run
-    pub deposit_amount: u64,         // Amount deposited
-    pub final_share: u64,            // Final share received
-    pub withdrawn: bool,             // Withdrawal status
-    pub correct_votes: u8,           // Number of correct votes
-    pub total_votes: u8,             // Total votes cast
-    pub bump: u8,                    // PDA bump
+#[derive(Accounts)]
+pub struct AdminAction<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()],
+        bump = platform.bump,
+        has_one = authority,
+        constraint = platform.is_authority_current() @ ErrorCode::AuthorityExpired
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(mut, seeds = [b"audit_log", platform.key().as_ref()], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    
+    pub authority: Signer<'info>,
 }
 
-impl UserParticipation {
-    pub const LEN: usize = 8 + 32 + 8 + 8 + 8 + 1 + 1 + 1 + 1;
+#[derive(Accounts)]
+#[instruction(run_id: u64)]
+pub struct RunAdminAction<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump, has_one = authority, constraint = platform.is_authority_current() @ ErrorCode::AuthorityExpired)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(mut, seeds = [b"audit_log", platform.key().as_ref()], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    
+    #[account(
+        mut,
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump
+    )]
+    pub run: Account<'info, Run>,
+    
+    pub authority: Signer<'info>,
 }
 
-// ============================================================================
-// Enums
-// ============================================================================
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct AddToBlacklist<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump, has_one = authority, constraint = platform.is_authority_current() @ ErrorCode::AuthorityExpired)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(mut, seeds = [b"audit_log", platform.key().as_ref()], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = BlacklistEntry::LEN,
+        seeds = [b"blacklist", platform.key().as_ref(), wallet.as_ref()],
+        bump
+    )]
+    pub blacklist_entry: Account<'info, BlacklistEntry>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
-pub enum RunStatus {
-    Waiting,   // Accepting deposits
-    Active,    // Trading in progress
-    Settled,   // Trading ended, ready for withdrawals
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct RemoveFromBlacklist<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump, has_one = authority, constraint = platform.is_authority_current() @ ErrorCode::AuthorityExpired)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(mut, seeds = [b"audit_log", platform.key().as_ref()], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"blacklist", platform.key().as_ref(), wallet.as_ref()],
+        bump = blacklist_entry.bump
+    )]
+    pub blacklist_entry: Account<'info, BlacklistEntry>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
 }
 
-// ============================================================================
-// Context Structs
-// ============================================================================
+#[derive(Accounts)]
+#[instruction(run_id: u64, user: Pubkey)]
+pub struct SetParticipationFrozen<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump, has_one = authority, constraint = platform.is_authority_current() @ ErrorCode::AuthorityExpired)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(mut, seeds = [b"audit_log", platform.key().as_ref()], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    
+    #[account(
+        mut,
+        seeds = [b"participation", platform.key().as_ref(), run_id.to_le_bytes().as_ref(), user.as_ref()],
+        bump = user_participation.bump
+    )]
+    pub user_participation: Account<'info, UserParticipation>,
+    
+    pub authority: Signer<'info>,
+}
 
 #[derive(Accounts)]
-pub struct InitializePlatform<'info> {
+#[instruction(wallet: Pubkey)]
+pub struct AddFeeExemption<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump, has_one = authority, constraint = platform.is_authority_current() @ ErrorCode::AuthorityExpired)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(mut, seeds = [b"audit_log", platform.key().as_ref()], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    
     #[account(
         init,
         payer = authority,
-        space = Platform::LEN,
-        seeds = [b"platform"],
+        space = FeeExempt::LEN,
+        seeds = [b"fee_exempt", platform.key().as_ref(), wallet.as_ref()],
+        bump
+    )]
+    pub fee_exempt: Account<'info, FeeExempt>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct RemoveFeeExemption<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump, has_one = authority, constraint = platform.is_authority_current() @ ErrorCode::AuthorityExpired)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(mut, seeds = [b"audit_log", platform.key().as_ref()], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"fee_exempt", platform.key().as_ref(), wallet.as_ref()],
+        bump = fee_exempt.bump
+    )]
+    pub fee_exempt: Account<'info, FeeExempt>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct IssueKycAttestation<'info> {
+    #[account(
+        seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()],
+        bump = platform.bump,
+        has_one = compliance_authority
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        init_if_needed,
+        payer = compliance_authority,
+        space = KycAttestation::LEN,
+        seeds = [b"kyc", platform.key().as_ref(), user.as_ref()],
         bump
     )]
+    pub kyc_attestation: Account<'info, KycAttestation>,
+    
+    #[account(mut)]
+    pub compliance_authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct RevokeKycAttestation<'info> {
+    #[account(
+        seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()],
+        bump = platform.bump,
+        has_one = compliance_authority
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [b"kyc", platform.key().as_ref(), user.as_ref()],
+        bump = kyc_attestation.bump
+    )]
+    pub kyc_attestation: Account<'info, KycAttestation>,
+    
+    pub compliance_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeStaking<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()],
+        bump = platform.bump,
+        has_one = authority,
+        constraint = platform.is_authority_current() @ ErrorCode::AuthorityExpired
+    )]
     pub platform: Account<'info, Platform>,
     
     #[account(
         init,
         payer = authority,
-        token::mint = usdc_mint,
+        token::mint = stake_mint,
         token::authority = platform,
-        seeds = [b"platform_fee_vault"],
+        seeds = [b"stake_vault", platform.key().as_ref()],
         bump
     )]
-    pub platform_fee_vault: Account<'info, TokenAccount>,
+    pub stake_vault: Account<'info, TokenAccount>,
     
-    pub usdc_mint: Account<'info, token::Mint>,
+    pub stake_mint: Account<'info, token::Mint>,
     
     #[account(mut)]
     pub authority: Signer<'info>,
@@ -543,277 +11562,990 @@ pub struct InitializePlatform<'info> {
 }
 
 #[derive(Accounts)]
-#[instruction(run_id: u64)]
-pub struct CreateRun<'info> {
+pub struct StakeTokens<'info> {
+    #[account(mut, seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
     #[account(
-        mut,
-        seeds = [b"platform"],
-        bump = platform.bump
+        init_if_needed,
+        payer = user,
+        space = Stake::LEN,
+        seeds = [b"stake", platform.key().as_ref(), user.key().as_ref()],
+        bump
     )]
-    pub platform: Account<'info, Platform>,
+    pub stake: Account<'info, Stake>,
     
     #[account(
-        init,
-        payer = authority,
-        space = Run::LEN,
-        seeds = [b"run", run_id.to_le_bytes().as_ref()],
+        mut,
+        seeds = [b"stake_vault", platform.key().as_ref()],
         bump
     )]
-    pub run: Account<'info, Run>,
+    pub stake_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = platform.stake_mint)]
+    pub user_token_account: Account<'info, TokenAccount>,
     
     #[account(mut)]
-    pub authority: Signer<'info>,
+    pub user: Signer<'info>,
     
+    pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-#[instruction(run_id: u64)]
-pub struct CreateRunVault<'info> {
+pub struct UnstakeTokens<'info> {
+    #[account(mut, seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
     #[account(
-        seeds = [b"run", run_id.to_le_bytes().as_ref()],
-        bump = run.bump
+        mut,
+        seeds = [b"stake", platform.key().as_ref(), user.key().as_ref()],
+        bump = stake.bump,
+        constraint = stake.owner == user.key()
     )]
-    pub run: Account<'info, Run>,
+    pub stake: Account<'info, Stake>,
+    
+    #[account(
+        mut,
+        seeds = [b"stake_vault", platform.key().as_ref()],
+        bump
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = platform.stake_mint)]
+    pub user_token_account: Account<'info, TokenAccount>,
+    
+    pub user: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeRevenueShare<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()],
+        bump = platform.bump,
+        has_one = authority,
+        constraint = platform.is_authority_current() @ ErrorCode::AuthorityExpired,
+        has_one = platform_fee_vault
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    pub platform_fee_vault: Account<'info, TokenAccount>,
     
     #[account(
         init,
-        payer = payer,
+        payer = authority,
         token::mint = usdc_mint,
-        token::authority = run,
-        seeds = [b"vault", run_id.to_le_bytes().as_ref()],
+        token::authority = platform,
+        seeds = [b"revenue_vault", platform.key().as_ref()],
         bump
     )]
-    pub run_vault: Account<'info, TokenAccount>,
+    pub revenue_vault: Account<'info, TokenAccount>,
     
+    #[account(address = platform_fee_vault.mint)]
     pub usdc_mint: Account<'info, token::Mint>,
     
     #[account(mut)]
-    pub payer: Signer<'info>,
+    pub authority: Signer<'info>,
     
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-#[instruction(run_id: u64)]
-pub struct Deposit<'info> {
-    #[account(seeds = [b"platform"], bump = platform.bump)]
+pub struct DistributeRevenue<'info> {
+    #[account(mut, seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(mut, seeds = [b"keeper_incentive", platform.key().as_ref()], bump = keeper_incentive.bump)]
+    pub keeper_incentive: Option<Account<'info, KeeperIncentive>>,
+    
+    /// Receives the keeper bounty, if any
+    #[account(mut)]
+    pub keeper: Option<Signer<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRevenue<'info> {
+    #[account(seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump)]
     pub platform: Account<'info, Platform>,
     
     #[account(
         mut,
-        seeds = [b"run", run_id.to_le_bytes().as_ref()],
-        bump = run.bump
-    )]
-    pub run: Account<'info, Run>,
-    
-    #[account(
-        init,
-        payer = user,
-        space = UserParticipation::LEN,
-        seeds = [b"participation", run_id.to_le_bytes().as_ref(), user.key().as_ref()],
-        bump
+        seeds = [b"stake", platform.key().as_ref(), user.key().as_ref()],
+        bump = stake.bump,
+        constraint = stake.owner == user.key()
     )]
-    pub user_participation: Account<'info, UserParticipation>,
+    pub stake: Account<'info, Stake>,
     
     #[account(
         mut,
-        seeds = [b"vault", run_id.to_le_bytes().as_ref()],
+        seeds = [b"revenue_vault", platform.key().as_ref()],
         bump
     )]
-    pub run_vault: Account<'info, TokenAccount>,
+    pub revenue_vault: Account<'info, TokenAccount>,
     
-    #[account(mut)]
+    #[account(mut, token::mint = revenue_vault.mint)]
     pub user_token_account: Account<'info, TokenAccount>,
     
-    pub usdc_mint: Account<'info, token::Mint>,
-    
-    #[account(mut)]
     pub user: Signer<'info>,
     
     pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-#[instruction(run_id: u64)]
-pub struct ManageRun<'info> {
-    #[account(seeds = [b"platform"], bump = platform.bump)]
+pub struct WithdrawPlatformFees<'info> {
+    #[account(
+        seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()],
+        bump = platform.bump,
+        has_one = authority,
+        constraint = platform.is_authority_current() @ ErrorCode::AuthorityExpired
+    )]
     pub platform: Account<'info, Platform>,
     
+    #[account(mut, seeds = [b"audit_log", platform.key().as_ref()], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    
     #[account(
         mut,
-        seeds = [b"run", run_id.to_le_bytes().as_ref()],
-        bump = run.bump,
-        has_one = authority
+        seeds = [b"platform_fee_vault", platform.key().as_ref()],
+        bump
     )]
-    pub run: Account<'info, Run>,
+    pub platform_fee_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub destination_token_account: Account<'info, TokenAccount>,
     
     pub authority: Signer<'info>,
+    
+    /// Tags the transfer with an SPL Memo for reconciliation when provided
+    pub memo_program: Option<Program<'info, Memo>>,
+    pub token_program: Program<'info, Token>,
 }
 
 #[derive(Accounts)]
 #[instruction(run_id: u64)]
-pub struct SettleRun<'info> {
+pub struct ForceRefundMode<'info> {
     #[account(
         mut,
-        seeds = [b"platform"],
-        bump = platform.bump
+        seeds = [b"run", run.platform.as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump
     )]
+    pub run: Account<'info, Run>,
+    
+    #[account(mut, address = run.platform)]
     pub platform: Account<'info, Platform>,
     
     #[account(
-        mut,
-        seeds = [b"run", run_id.to_le_bytes().as_ref()],
-        bump = run.bump,
-        has_one = authority
+        seeds = [b"vault", run.platform.as_ref(), run_id.to_le_bytes().as_ref()],
+        bump
     )]
-    pub run: Account<'info, Run>,
+    pub run_vault: Account<'info, TokenAccount>,
     
+    #[account(mut, seeds = [b"keeper_incentive", platform.key().as_ref()], bump = keeper_incentive.bump)]
+    pub keeper_incentive: Option<Account<'info, KeeperIncentive>>,
+    
+    /// Receives the keeper bounty, if any
+    #[account(mut)]
+    pub keeper: Option<Signer<'info>>,
+}
+
+#[derive(Accounts)]
+#[instruction(run_id: u64)]
+pub struct TriggerStopLoss<'info> {
     #[account(
         mut,
-        seeds = [b"vault", run_id.to_le_bytes().as_ref()],
-        bump
+        seeds = [b"run", run.platform.as_ref(), run_id.to_le_bytes().as_ref()],
+        bump = run.bump
     )]
-    pub run_vault: Account<'info, TokenAccount>,
+    pub run: Account<'info, Run>,
+    
+    #[account(mut, address = run.platform)]
+    pub platform: Account<'info, Platform>,
     
     #[account(
         mut,
-        seeds = [b"platform_fee_vault"],
+        seeds = [b"vault", run.platform.as_ref(), run_id.to_le_bytes().as_ref()],
         bump
     )]
-    pub platform_fee_vault: Account<'info, TokenAccount>,
+    pub run_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, seeds = [b"keeper_incentive", platform.key().as_ref()], bump = keeper_incentive.bump)]
+    pub keeper_incentive: Option<Account<'info, KeeperIncentive>>,
+    
+    #[account(mut)]
+    pub caller: Signer<'info>,
     
-    pub authority: Signer<'info>,
     pub token_program: Program<'info, Token>,
 }
 
 #[derive(Accounts)]
 #[instruction(run_id: u64)]
-pub struct Withdraw<'info> {
+pub struct EnableEmergencyRefunds<'info> {
+    #[account(mut, seeds = [b"platform", platform.platform_id.to_le_bytes().as_ref()], bump = platform.bump, has_one = authority, constraint = platform.is_authority_current() @ ErrorCode::AuthorityExpired)]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(mut, seeds = [b"audit_log", platform.key().as_ref()], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    
     #[account(
         mut,
-        seeds = [b"run", run_id.to_le_bytes().as_ref()],
+        seeds = [b"run", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
         bump = run.bump
     )]
     pub run: Account<'info, Run>,
     
     #[account(
-        mut,
-        seeds = [b"participation", run_id.to_le_bytes().as_ref(), user.key().as_ref()],
-        bump = user_participation.bump
-    )]
-    pub user_participation: Account<'info, UserParticipation>,
-    
-    #[account(
-        mut,
-        seeds = [b"vault", run_id.to_le_bytes().as_ref()],
+        seeds = [b"vault", platform.key().as_ref(), run_id.to_le_bytes().as_ref()],
         bump
     )]
     pub run_vault: Account<'info, TokenAccount>,
     
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateAccount<'info> {
+    /// CHECK: owner and discriminator are verified in the handler, since older layouts
+    /// cannot be deserialized as the current account types
     #[account(mut)]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub target: UncheckedAccount<'info>,
     
-    pub user: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
     
-    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
 }
 
+#[cfg(feature = "devnet")]
 #[derive(Accounts)]
-#[instruction(run_id: u64, user_pubkey: Pubkey)]
-pub struct UpdateVoteStats<'info> {
-    #[account(seeds = [b"platform"], bump = platform.bump)]
-    pub platform: Account<'info, Platform>,
-    
+pub struct FaucetMintTestUsdc<'info> {
     #[account(
-        seeds = [b"run", run_id.to_le_bytes().as_ref()],
-        bump = run.bump,
-        has_one = authority
+        init_if_needed,
+        payer = caller,
+        mint::decimals = USDC_DECIMALS,
+        mint::authority = faucet_authority,
+        seeds = [b"test_usdc_mint"],
+        bump
     )]
-    pub run: Account<'info, Run>,
+    pub test_usdc_mint: Account<'info, token::Mint>,
+    
+    /// CHECK: PDA that signs for the test mint; holds no data
+    #[account(seeds = [b"faucet_authority"], bump)]
+    pub faucet_authority: UncheckedAccount<'info>,
     
     #[account(
-        mut,
-        seeds = [b"participation", run_id.to_le_bytes().as_ref(), user_pubkey.as_ref()],
-        bump = user_participation.bump
+        init_if_needed,
+        payer = caller,
+        associated_token::mint = test_usdc_mint,
+        associated_token::authority = caller
     )]
-    pub user_participation: Account<'info, UserParticipation>,
+    pub caller_token_account: Account<'info, TokenAccount>,
     
-    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub caller: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+// ============================================================================
+// Helper Structs
+// ============================================================================
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ParticipantShare {
+    pub user: Pubkey,
+    pub share_amount: u64,
+}
+
+/// Corrected vote record of a participant whose share an upheld dispute found misreported
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct VoteCorrection {
+    pub user: Pubkey,
+    pub correct_votes: u8,
+    pub total_votes: u8,
+}
+
+/// Token amount shown in whole units, e.g. 12500000 at 6 decimals displays as 12.500000
+pub struct UiAmount {
+    pub amount: u64,
+    pub decimals: u8,
+}
+
+impl fmt::Display for UiAmount {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.decimals == 0 {
+            return write!(f, "{}", self.amount);
+        }
+        let unit = 10u128.pow(self.decimals as u32);
+        let amount = self.amount as u128;
+        write!(f, "{}.{:0width$}", amount / unit, amount % unit, width = self.decimals as usize)
+    }
+}
+
+/// Fees, bond, oracle check and state changes shared by settle_run and complete_settlement, once
+/// the share table holds every participant's share
+fn settle_recorded_shares<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SettleRun<'info>>,
+    run_id: u64,
+    final_balance: u64,
+    report: Option<SettlementReport>,
+) -> Result<()> {
+    ctx.accounts.platform.check_attestor(ctx.accounts.attestor.as_ref().map(|attestor| attestor.key()))?;
+
+    // Verify current vault balance matches reported final_balance
+    let vault_balance = ctx.accounts.run_vault.amount;
+    require!(vault_balance == final_balance, ErrorCode::VaultBalanceMismatch);
+    // Shares are paid from the run vault alone, so stablecoin deposits must be converted into it
+    require!(ctx.accounts.run.unconverted_deposits == 0, ErrorCode::UnconvertedDeposits);
+    
+    // Read values we need from run before any mutable borrows
+    let total_deposited = ctx.accounts.run.total_deposited;
+    let run_bump = ctx.accounts.run.bump;
+    let platform_key = ctx.accounts.run.platform;
+    let run_id_bytes = run_id.to_le_bytes();
+    let started_at = ctx.accounts.run.started_at;
+    let management_fee_bps = ctx.accounts.run.management_fee_bps_per_year;
+    let manager_fee_bps = ctx.accounts.run.manager_fee_bps;
+    let now = Clock::get()?.unix_timestamp;
+
+    // Calculate platform fee ONLY on profit (not on principal)
+    let profit = if final_balance > total_deposited {
+        final_balance
+            .checked_sub(total_deposited)
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+    } else {
+        0
+    };
+
+    // Fee-exempt participants arrive as (participation, fee_exempt) pairs in remaining accounts;
+    // only the profit earned on everyone else's deposits is charged
+    let exempt_deposits = FeeExempt::mark_participants(ctx.remaining_accounts, &platform_key, run_id)?;
+    let charged_profit = (profit as u128)
+        .checked_mul(total_deposited.saturating_sub(exempt_deposits) as u128)
+        .ok_or(ErrorCode::ArithmeticOverflow)?
+        .checked_div(total_deposited as u128)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    let platform_fee = charged_profit
+        .checked_mul(ctx.accounts.platform.platform_fee_bps as u128)
+        .ok_or(ErrorCode::ArithmeticOverflow)?
+        .checked_div(10000)
+        .ok_or(ErrorCode::ArithmeticOverflow)?
+        as u64;
+
+    // The manager's cut is taken from all profit and paid separately from the platform fee
+    let manager_fee = (profit as u128)
+        .checked_mul(manager_fee_bps as u128)
+        .ok_or(ErrorCode::ArithmeticOverflow)?
+        .checked_div(10000)
+        .ok_or(ErrorCode::ArithmeticOverflow)?
+        .min((profit - platform_fee) as u128) as u64;
+
+    // Management fee accrues on deposits for the time the run was active, whatever the P/L
+    let active_secs = now.saturating_sub(started_at).max(0);
+    let management_fee = (total_deposited as u128)
+        .checked_mul(management_fee_bps as u128)
+        .and_then(|v| v.checked_mul(active_secs as u128))
+        .ok_or(ErrorCode::ArithmeticOverflow)?
+        .checked_div(10000 * SECONDS_PER_YEAR as u128)
+        .ok_or(ErrorCode::ArithmeticOverflow)?
+        .min((final_balance - platform_fee - manager_fee) as u128) as u64;
+    let total_fee = platform_fee
+        .checked_add(management_fee)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    // Stakers' cut of the fees goes to the revenue vault, the rest to the treasury
+    let revenue_share = (total_fee as u128)
+        .checked_mul(ctx.accounts.platform.revenue_share_bps as u128)
+        .ok_or(ErrorCode::ArithmeticOverflow)?
+        .checked_div(10000)
+        .ok_or(ErrorCode::ArithmeticOverflow)? as u64;
+    let treasury_fee = total_fee - revenue_share;
+
+    let run_seeds = &[
+        b"run".as_ref(),
+        platform_key.as_ref(),
+        run_id_bytes.as_ref(),
+        &[run_bump],
+    ];
+    let signer = &[&run_seeds[..]];
+
+    write_memo(
+        &ctx.accounts.memo_program,
+        &format!("instinct:settlement_fees run_id={}", run_id),
+    )?;
+    // Transfer platform fees to platform vault
+    if treasury_fee > 0 {
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.run_vault.to_account_info(),
+            to: ctx.accounts.platform_fee_vault.to_account_info(),
+            authority: ctx.accounts.run.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, treasury_fee)?;
+    }
+
+    if revenue_share > 0 {
+        let revenue_vault = ctx.accounts.revenue_vault
+            .as_ref()
+            .ok_or(ErrorCode::RevenueShareNotInitialized)?;
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.run_vault.to_account_info(),
+            to: revenue_vault.to_account_info(),
+            authority: ctx.accounts.run.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, revenue_share)?;
+    }
+
+    if manager_fee > 0 {
+        let manager_fee_vault = ctx.accounts.manager_fee_vault
+            .as_ref()
+            .ok_or(ErrorCode::ManagerNotRegistered)?;
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.run_vault.to_account_info(),
+            to: manager_fee_vault.to_account_info(),
+            authority: ctx.accounts.run.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, manager_fee)?;
+    }
+
+    // Settler posts a bond that backs the reported shares until the dispute window closes
+    let settlement_bond = ctx.accounts.platform.settlement_bond;
+    if settlement_bond > 0 {
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.settler_token_account.to_account_info(),
+            to: ctx.accounts.bond_vault.to_account_info(),
+            authority: ctx.accounts.authority.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::transfer(cpi_ctx, settlement_bond)?;
+    }
+
+    // Commit to the recorded share table so a dispute refers to exactly what was submitted
+    let shares_hash = ShareTable::shares_hash(&ctx.accounts.share_table)?;
+    let risk_oracle = ctx.accounts.platform.risk_oracle;
+    if risk_oracle != Pubkey::default() {
+        let report = report.as_ref().ok_or(ErrorCode::InvalidSettlementReport)?;
+        let instructions = ctx.accounts.instructions.as_ref().ok_or(ErrorCode::InvalidSettlementReport)?;
+        report.check(platform_key, run_id, final_balance, shares_hash, Clock::get()?.slot)?;
+        report.verify_signature(instructions, &risk_oracle)?;
+    }
+
+    if ctx.accounts.platform.settlement_committee != Pubkey::default() {
+        SettlementApproval::check(
+            ctx.accounts.settlement_approval.as_deref(),
+            ctx.accounts.settlement_committee.as_deref(),
+            final_balance,
+            shares_hash,
+        )?;
+    }
+
+    // Without a dispute window the settlement cannot be disputed and withdrawals open at once
+    let dispute_deadline = if ctx.accounts.platform.dispute_window_secs > 0 {
+        now.checked_add(ctx.accounts.platform.dispute_window_secs)
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+    } else {
+        0
+    };
+
+    // Now update run state (mutable borrow)
+    let settler = ctx.accounts.authority.key();
+    ctx.accounts.platform.end_run(&ctx.accounts.run);
+    let run = &mut ctx.accounts.run;
+    run.transition_to(RunStatus::Settled)?;
+    run.final_balance = final_balance
+        .checked_sub(total_fee)
+        .and_then(|v| v.checked_sub(manager_fee))
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    run.platform_fee_amount = platform_fee;
+    run.management_fee_amount = management_fee;
+    run.manager_fee_amount = manager_fee;
+    run.exempt_deposits = exempt_deposits;
+    run.ended_at = now;
+    run.settlement_bond = settlement_bond;
+    run.settler = settler;
+    run.shares_hash = shares_hash;
+    run.dispute_deadline = dispute_deadline;
+    run.bond_resolved = false;
+    run.escrow_until = now
+        .checked_add(ctx.accounts.platform.settlement_escrow_secs)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    run.in_escrow = ctx.accounts.platform.settlement_escrow_secs > 0;
+
+    // Update platform totals
+    let platform = &mut ctx.accounts.platform;
+    platform.total_fees_collected = platform.total_fees_collected
+        .checked_add(total_fee)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    platform.pending_revenue = platform.pending_revenue
+        .checked_add(revenue_share)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    // Third-party managers carry a public track record of net returns
+    if run.manager != Pubkey::default() {
+        let manager_profile = ctx.accounts.manager_profile
+            .as_mut()
+            .ok_or(ErrorCode::ManagerNotRegistered)?;
+        manager_profile.record_settlement(total_deposited, run.final_balance)?;
+        manager_profile.fees_earned = manager_profile.fees_earned
+            .checked_add(manager_fee)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+    }
+
+    emit_cpi!(RunSettled {
+        run_id,
+        total_deposited,
+        final_balance,
+        performance_fee: platform_fee,
+        management_fee,
+        manager_fee,
+        available: run.final_balance,
+        ended_at: now,
+    });
+
+    msg!("Run #{} settled - Initial: {} Final: {} Performance fee: {} Management fee: {} Manager fee: {} Available: {}", 
+        run_id, 
+        run.ui(total_deposited), 
+        run.ui(final_balance),
+        run.ui(platform_fee),
+        run.ui(management_fee),
+        run.ui(manager_fee),
+        run.ui(run.final_balance)
+    );
+    
+    Ok(())
+}
+
+/// Byte layout of the ed25519 program's instruction data
+const ED25519_HEADER_LEN: usize = 2;
+const ED25519_OFFSETS_LEN: usize = 14;
+
+/// Require that the instruction before the current one is an ed25519 program check of `signer`'s
+/// signature over `expected`, with signature, key and message all inline in that instruction
+pub fn verify_ed25519_signature(instructions: &AccountInfo, signer: &Pubkey, expected: &[u8]) -> Result<()> {
+    let current = sysvar_instructions::load_current_index_checked(instructions)? as usize;
+    require!(current > 0, ErrorCode::InvalidIntentSignature);
+    let ix = sysvar_instructions::load_instruction_at_checked(current - 1, instructions)?;
+    require_keys_eq!(ix.program_id, ed25519_program::ID, ErrorCode::InvalidIntentSignature);
+
+    let data = &ix.data;
+    require!(
+        data.len() >= ED25519_HEADER_LEN + ED25519_OFFSETS_LEN && data[0] == 1,
+        ErrorCode::InvalidIntentSignature
+    );
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    let offsets = ED25519_HEADER_LEN;
+    let public_key_offset = read_u16(offsets + 4) as usize;
+    let message_offset = read_u16(offsets + 8) as usize;
+    let message_size = read_u16(offsets + 10) as usize;
+    // Signature, key and message must all come from the ed25519 instruction itself
+    for index_at in [offsets + 2, offsets + 6, offsets + 12] {
+        require!(read_u16(index_at) == u16::MAX, ErrorCode::InvalidIntentSignature);
+    }
+
+    let public_key = data
+        .get(public_key_offset..public_key_offset + 32)
+        .ok_or(ErrorCode::InvalidIntentSignature)?;
+    let message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(ErrorCode::InvalidIntentSignature)?;
+    require!(public_key == signer.as_ref(), ErrorCode::InvalidIntentSignature);
+    require!(message == expected, ErrorCode::InvalidIntentSignature);
+    Ok(())
+}
+
+/// Log `memo` through the SPL Memo program so indexers see it in the transaction next to the
+/// transfer it describes; callers that pass no memo program skip it
+pub fn write_memo<'info>(memo_program: &Option<Program<'info, Memo>>, memo: &str) -> Result<()> {
+    let Some(memo_program) = memo_program else {
+        return Ok(());
+    };
+    memo::build_memo(CpiContext::new(memo_program.to_account_info(), BuildMemo {}), memo.as_bytes())
+}
+
+/// Convert an amount between mints with different decimals, rounding down
+pub fn rescale_amount(amount: u64, from_decimals: u8, to_decimals: u8) -> Result<u64> {
+    let scaled = if to_decimals >= from_decimals {
+        let factor = 10u128
+            .checked_pow((to_decimals - from_decimals) as u32)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        (amount as u128).checked_mul(factor).ok_or(ErrorCode::ArithmeticOverflow)?
+    } else {
+        let factor = 10u128
+            .checked_pow((from_decimals - to_decimals) as u32)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        amount as u128 / factor
+    };
+    Ok(u64::try_from(scaled).map_err(|_| ErrorCode::ArithmeticOverflow)?)
+}
+
+/// Return data of `preview_withdraw`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct WithdrawPreview {
+    pub share: u64,                  // Total share, including the vote bonus
+    pub base_share: u64,             // Pro-rata share after fees, before the bonus
+    pub bonus: u64,                  // Vote-accuracy bonus (including any ve boost)
+    pub paid_now: u64,               // Paid by `withdraw`
+    pub vesting: u64,                // Released later through `claim_vested`
+}
+
+/// Fields of a Pyth PriceUpdateV2 account needed for conversion
+pub struct PythPrice {
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+}
+
+impl PythPrice {
+    /// Parse a fully verified PriceUpdateV2 account for `feed_id`
+    pub fn load(account: &AccountInfo, feed_id: &[u8; 32]) -> Result<Self> {
+        require_keys_eq!(*account.owner, PYTH_RECEIVER_PROGRAM_ID, ErrorCode::InvalidPriceFeed);
+        let data = account.try_borrow_data()?;
+        // discriminator (8) + write_authority (32), then the verification level; only Full (tag 1) is accepted
+        let level_at = 8 + 32;
+        require!(data.get(level_at) == Some(&1), ErrorCode::InvalidPriceFeed);
+        let message = data
+            .get(level_at + 1..level_at + 1 + 32 + 8 + 8 + 4 + 8)
+            .ok_or(ErrorCode::InvalidPriceFeed)?;
+        require!(&message[..32] == feed_id.as_ref(), ErrorCode::InvalidPriceFeed);
+
+        let read_i64 = |at: usize| i64::from_le_bytes(message[at..at + 8].try_into().unwrap());
+        Ok(Self {
+            price: read_i64(32),
+            conf: u64::from_le_bytes(message[40..48].try_into().unwrap()),
+            exponent: i32::from_le_bytes(message[48..52].try_into().unwrap()),
+            publish_time: read_i64(52),
+        })
+    }
+
+    /// Convert a stablecoin amount to its USD notional, rejecting stale, uncertain or depegged prices
+    pub fn usd_notional(&self, amount: u64, now: i64) -> Result<u64> {
+        require!(now.saturating_sub(self.publish_time) <= MAX_PRICE_AGE_SECS, ErrorCode::StalePrice);
+        require!(self.price > 0, ErrorCode::InvalidPriceFeed);
+        let price = self.price as u128;
+        require!(
+            (self.conf as u128) * 10000 <= price * MAX_PRICE_CONF_BPS as u128,
+            ErrorCode::PriceTooUncertain
+        );
+
+        let scale = 10u128
+            .checked_pow(self.exponent.unsigned_abs())
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        let (one_dollar, notional) = if self.exponent < 0 {
+            (scale, (amount as u128) * price / scale)
+        } else {
+            (1, (amount as u128) * price * scale)
+        };
+        let price_in_units = if self.exponent < 0 { price } else { price * scale };
+        require!(
+            price_in_units.abs_diff(one_dollar) * 10000 <= one_dollar * MAX_DEPEG_BPS as u128,
+            ErrorCode::StablecoinDepegged
+        );
+        Ok(u64::try_from(notional).map_err(|_| ErrorCode::ArithmeticOverflow)?)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BridgedDeposit {
+    pub source_domain: u32,          // CCTP domain / Wormhole chain id of the origin
+    pub nonce: u64,                  // Message nonce, unique per domain
+    pub evm_sender: [u8; 20],        // Depositor on the origin chain
+    pub run_id: u64,                 // Run to join
+    pub amount: u64,                 // USDC delivered to the bridge inbox
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct DepositIntent {
+    pub platform: Pubkey,            // Platform instance, so intents cannot be replayed across instances
+    pub user: Pubkey,                // Signing wallet
+    pub run_id: u64,
+    pub amount: u64,
+    pub expiry: i64,                 // Unix timestamp after which the intent is void
+    pub nonce: u64,                  // Must equal the user's IntentNonce.next_nonce
+}
+
+impl DepositIntent {
+    /// Require that the preceding instruction is an ed25519 program check of the user's
+    /// signature over this intent's Borsh encoding, with all data inline in that instruction
+    pub fn verify_signature(&self, instructions: &AccountInfo) -> Result<()> {
+        let expected = self.try_to_vec().map_err(|_| ErrorCode::InvalidIntentSignature)?;
+        verify_ed25519_signature(instructions, &self.user, &expected)
+            .map_err(|_| ErrorCode::InvalidIntentSignature.into())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SettlementReport {
+    pub platform: Pubkey,            // Platform instance, so reports cannot be replayed across instances
+    pub run_id: u64,
+    pub final_balance: u64,          // Vault balance the settlement reports
+    pub shares_root: [u8; 32],       // SHA-256 of the Borsh-encoded participant shares (the run's shares_hash)
+    pub slot: u64,                   // Slot the report was signed at
+}
+
+impl SettlementReport {
+    /// The report must describe exactly this settlement and be recent
+    pub fn check(&self, platform: Pubkey, run_id: u64, final_balance: u64, shares_hash: [u8; 32], slot: u64) -> Result<()> {
+        require!(
+            self.platform == platform
+                && self.run_id == run_id
+                && self.final_balance == final_balance
+                && self.shares_root == shares_hash,
+            ErrorCode::InvalidSettlementReport
+        );
+        require!(
+            self.slot <= slot && slot - self.slot <= MAX_REPORT_AGE_SLOTS,
+            ErrorCode::StaleSettlementReport
+        );
+        Ok(())
+    }
+
+    /// Require that the preceding instruction is an ed25519 program check of the risk oracle's
+    /// signature over this report's Borsh encoding
+    pub fn verify_signature(&self, instructions: &AccountInfo, risk_oracle: &Pubkey) -> Result<()> {
+        let expected = self.try_to_vec().map_err(|_| ErrorCode::InvalidSettlementReport)?;
+        verify_ed25519_signature(instructions, risk_oracle, &expected)
+            .map_err(|_| ErrorCode::InvalidSettlementReport.into())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum ScheduledActionParams {
+    SetPlatformFee { platform_fee_bps: u16 },
+    TransferAuthority { new_authority: Pubkey },
+    TreasurySpend { amount: u64, destination: Pubkey },
+    SetSettlementCommittee { approvers: Vec<Pubkey>, threshold: u8 },
+}
+
+impl ScheduledActionParams {
+    pub fn kind(&self) -> ScheduledActionKind {
+        match self {
+            ScheduledActionParams::SetPlatformFee { .. } => ScheduledActionKind::SetPlatformFee,
+            ScheduledActionParams::TransferAuthority { .. } => ScheduledActionKind::TransferAuthority,
+            ScheduledActionParams::TreasurySpend { .. } => ScheduledActionKind::TreasurySpend,
+            ScheduledActionParams::SetSettlementCommittee { .. } => ScheduledActionKind::SetSettlementCommittee,
+        }
+    }
+
+    /// Commitment stored by `queue_action`
+    pub fn hash(&self) -> Result<[u8; 32]> {
+        let data = self.try_to_vec().map_err(|_| ErrorCode::ActionParamsMismatch)?;
+        Ok(anchor_lang::solana_program::hash::hash(&data).to_bytes())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct StakeTier {
+    pub min_stake: u64,              // Governance tokens staked to reach the tier (0 = unused)
+    pub discount_bps: u16,           // Discount on stake-discountable fees
+}
+
+impl StakeTier {
+    pub const LEN: usize = 8 + 2;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RiskParams {
+    pub max_position_size: u64,      // Largest notional of a single position, in USDC units
+    pub max_leverage_bps: u32,       // Largest position notional over run capital (10000 = 1x)
+    pub allowed_markets: u64,        // Bit i set = market index i may be traded
+    pub max_daily_trades: u16,       // Trades allowed per UTC day
+}
+
+impl RiskParams {
+    pub fn validate(&self) -> Result<()> {
+        require!(self.max_position_size > 0, ErrorCode::InvalidRiskParams);
+        require!(self.max_leverage_bps > 0, ErrorCode::InvalidRiskParams);
+        require!(self.allowed_markets != 0, ErrorCode::InvalidRiskParams);
+        require!(self.max_daily_trades > 0, ErrorCode::InvalidRiskParams);
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreateRunParams {
+    pub min_deposit: u64,            // Minimum deposit per user
+    pub max_deposit: u64,            // Maximum deposit per user
+    pub max_participants: u16,       // Participant cap
+    pub max_duration: i64,           // Seconds the run may stay active
+    pub max_run_tvl: u64,            // Cap on total deposits (0 = no cap)
+    pub min_participants: u16,       // Participants required to start
+    pub min_total_deposit: u64,      // Total deposits required to start
+    pub deposit_deadline: i64,       // Deposits accepted until this timestamp
+    pub auto_start: bool,            // Start on the deposit that fills the run
+    pub early_access_until: i64,     // Stakers-only deposit window end (0 = none)
+    pub requires_kyc: bool,          // Gate deposits on KYC attestations
+    pub is_private: bool,            // Require invite codes to deposit
+    pub profit_vesting_secs: i64,    // Profit vesting period after settlement (0 = immediate)
+    pub management_fee_bps_per_year: u16, // Annualized management fee on deposits (0 = none)
+    pub series_id: u64,              // Recurring series for DCA plans (0 = standalone)
+    pub claim_window_secs: i64,      // Claim window after settlement before shares are swept (0 = none)
+    pub bonus_mode: BonusMode,       // How vote-accuracy bonuses are funded
+    pub bonus_bps_per_correct_vote: u16, // Bonus on the profit share per correct vote (100 = 1%)
+    pub max_vote_rounds: u8,         // Vote rounds held during the run
+    pub min_vote_deposit: u64,       // Deposit needed to vote and earn the bonus (0 = any deposit)
+    pub max_rebalance_bps_per_round: u16, // Capital that may move between strategies per vote round, of total deposits (0 = none)
+    pub liquidity_buffer_bps: u16,   // Share of a strategy's balance a rebalance must leave in place
+    pub max_drawdown_bps: u16,       // Marked loss on total deposits that pauses the run (0 = no breaker)
+    pub stop_loss_nav: u64,          // Marked NAV below which anyone may stop the run into refunds (0 = none)
+}
+
+impl CreateRunParams {
+    pub fn validate(&self, now: i64) -> Result<()> {
+        require!(self.min_deposit > 0, ErrorCode::InvalidDepositAmount);
+        require!(self.max_deposit >= self.min_deposit, ErrorCode::InvalidDepositAmount);
+        require!(self.max_participants > 0, ErrorCode::InvalidParticipantLimit);
+        require!(
+            self.min_participants <= self.max_participants,
+            ErrorCode::InvalidParticipantLimit
+        );
+        require!(self.max_duration > 0, ErrorCode::InvalidDuration);
+        require!(self.profit_vesting_secs >= 0, ErrorCode::InvalidDuration);
+        // Vesting profit must be fully claimable before the deadline can forfeit it
+        require!(
+            self.claim_window_secs == 0 || self.claim_window_secs >= self.profit_vesting_secs.max(1),
+            ErrorCode::InvalidDuration
+        );
+        require!(self.management_fee_bps_per_year <= 10000, ErrorCode::InvalidFee);
+        require!(self.bonus_bps_per_correct_vote <= 10000, ErrorCode::InvalidFee);
+        require!(self.max_vote_rounds > 0, ErrorCode::InvalidVoteStats);
+        require!(self.max_rebalance_bps_per_round <= 10000, ErrorCode::InvalidRebalanceLimits);
+        require!(self.liquidity_buffer_bps <= 10000, ErrorCode::InvalidRebalanceLimits);
+        require!(self.max_drawdown_bps <= 10000, ErrorCode::InvalidDrawdownLimit);
+        require!(
+            self.max_run_tvl == 0 || self.max_run_tvl >= self.min_deposit,
+            ErrorCode::InvalidTvlCap
+        );
+        require!(
+            self.max_run_tvl == 0 || self.min_total_deposit <= self.max_run_tvl,
+            ErrorCode::InvalidTvlCap
+        );
+        require!(self.deposit_deadline > now, ErrorCode::InvalidDeadline);
+        require!(
+            self.early_access_until <= self.deposit_deadline,
+            ErrorCode::InvalidDeadline
+        );
+        Ok(())
+    }
 }
 
-#[derive(Accounts)]
-pub struct AdminAction<'info> {
-    #[account(
-        mut,
-        seeds = [b"platform"],
-        bump = platform.bump,
-        has_one = authority
-    )]
-    pub platform: Account<'info, Platform>,
-    
-    pub authority: Signer<'info>,
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RunTierParams {
+    pub name: [u8; 16],              // Display name, zero-padded UTF-8
+    pub min_deposit: u64,            // Minimum deposit per user
+    pub max_deposit: u64,            // Maximum deposit per user
+    pub min_participants: u16,       // Participants required to start
+    pub max_participants: u16,       // Participant cap
+    pub max_run_tvl: u64,            // Cap on total deposits (0 = no cap)
+    pub min_total_deposit: u64,      // Total deposits required to start
+    pub max_duration: i64,           // Seconds a run may stay active
+    pub management_fee_bps_per_year: u16, // Annualized management fee on deposits
+    pub bonus_bps_per_correct_vote: u16, // Vote-accuracy bonus per correct vote
+    pub max_bonus_bps: u16,          // Cap on the vote-accuracy bonus (0 = uncapped)
+    pub is_active: bool,             // New runs may be created from this preset
 }
 
-#[derive(Accounts)]
-pub struct WithdrawPlatformFees<'info> {
-    #[account(
-        seeds = [b"platform"],
-        bump = platform.bump,
-        has_one = authority
-    )]
-    pub platform: Account<'info, Platform>,
-    
-    #[account(
-        mut,
-        seeds = [b"platform_fee_vault"],
-        bump
-    )]
-    pub platform_fee_vault: Account<'info, TokenAccount>,
-    
-    #[account(mut)]
-    pub destination_token_account: Account<'info, TokenAccount>,
-    
-    pub authority: Signer<'info>,
-    pub token_program: Program<'info, Token>,
+impl RunTierParams {
+    /// Same limits `create_run` enforces, checked up front so a bad preset can't be saved
+    pub fn validate(&self) -> Result<()> {
+        require!(self.min_deposit > 0, ErrorCode::InvalidDepositAmount);
+        require!(self.max_deposit >= self.min_deposit, ErrorCode::InvalidDepositAmount);
+        require!(self.max_participants > 0, ErrorCode::InvalidParticipantLimit);
+        require!(
+            self.min_participants <= self.max_participants,
+            ErrorCode::InvalidParticipantLimit
+        );
+        require!(self.max_duration > 0, ErrorCode::InvalidDuration);
+        require!(self.management_fee_bps_per_year <= 10000, ErrorCode::InvalidFee);
+        require!(
+            self.bonus_bps_per_correct_vote <= 10000 && self.max_bonus_bps <= 10000,
+            ErrorCode::InvalidFee
+        );
+        require!(
+            self.max_run_tvl == 0 || self.max_run_tvl >= self.min_deposit,
+            ErrorCode::InvalidTvlCap
+        );
+        require!(
+            self.max_run_tvl == 0 || self.min_total_deposit <= self.max_run_tvl,
+            ErrorCode::InvalidTvlCap
+        );
+        Ok(())
+    }
 }
 
-#[derive(Accounts)]
-#[instruction(run_id: u64)]
-pub struct EmergencyWithdraw<'info> {
-    #[account(seeds = [b"platform"], bump = platform.bump, has_one = authority)]
-    pub platform: Account<'info, Platform>,
-    
-    #[account(
-        seeds = [b"run", run_id.to_le_bytes().as_ref()],
-        bump = run.bump
-    )]
-    pub run: Account<'info, Run>,
-    
-    #[account(
-        mut,
-        seeds = [b"vault", run_id.to_le_bytes().as_ref()],
-        bump
-    )]
-    pub run_vault: Account<'info, TokenAccount>,
-    
-    #[account(mut)]
-    pub destination_token_account: Account<'info, TokenAccount>,
-    
-    pub authority: Signer<'info>,
-    pub token_program: Program<'info, Token>,
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct UpdateRunConfigParams {
+    pub min_deposit: Option<u64>,          // New minimum deposit per user
+    pub max_deposit: Option<u64>,          // New maximum deposit per user
+    pub max_participants: Option<u16>,     // New participant cap
+    pub deposit_deadline: Option<i64>,     // New end of the deposit window
+    pub early_access_until: Option<i64>,   // New end of the stakers-only window
 }
 
-// ============================================================================
-// Helper Structs
-// ============================================================================
+impl UpdateRunConfigParams {
+    /// Apply the changes to a Waiting run; once anyone has deposited only the participant cap may be raised
+    pub fn apply(&self, run: &mut Run, now: i64) -> Result<()> {
+        if run.participant_count > 0 {
+            require!(
+                self.min_deposit.is_none()
+                    && self.max_deposit.is_none()
+                    && self.deposit_deadline.is_none()
+                    && self.early_access_until.is_none(),
+                ErrorCode::RunHasDeposits
+            );
+            if let Some(max_participants) = self.max_participants {
+                require!(max_participants >= run.max_participants, ErrorCode::InvalidParticipantLimit);
+            }
+        }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct ParticipantShare {
-    pub user: Pubkey,
-    pub share_amount: u64,
+        // The updated run must satisfy the same rules as a freshly created one
+        let params = CreateRunParams {
+            min_deposit: self.min_deposit.unwrap_or(run.min_deposit),
+            max_deposit: self.max_deposit.unwrap_or(run.max_deposit),
+            max_participants: self.max_participants.unwrap_or(run.max_participants),
+            max_duration: run.max_duration,
+            max_run_tvl: run.max_run_tvl,
+            min_participants: run.min_participants,
+            min_total_deposit: run.min_total_deposit,
+            deposit_deadline: self.deposit_deadline.unwrap_or(run.deposit_deadline),
+            auto_start: run.auto_start,
+            early_access_until: self.early_access_until.unwrap_or(run.early_access_until),
+            requires_kyc: run.requires_kyc,
+            is_private: run.is_private,
+            profit_vesting_secs: run.profit_vesting_secs,
+            management_fee_bps_per_year: run.management_fee_bps_per_year,
+            series_id: run.series_id,
+            claim_window_secs: run.claim_window_secs,
+            bonus_mode: run.bonus_mode,
+            bonus_bps_per_correct_vote: run.bonus_bps_per_correct_vote,
+            max_vote_rounds: run.max_vote_rounds,
+            min_vote_deposit: run.min_vote_deposit,
+            max_rebalance_bps_per_round: run.max_rebalance_bps_per_round,
+            liquidity_buffer_bps: run.liquidity_buffer_bps,
+            max_drawdown_bps: run.max_drawdown_bps,
+            stop_loss_nav: run.stop_loss_nav,
+        };
+        params.validate(now)?;
+
+        run.min_deposit = params.min_deposit;
+        run.max_deposit = params.max_deposit;
+        run.max_participants = params.max_participants;
+        run.deposit_deadline = params.deposit_deadline;
+        run.early_access_until = params.early_access_until;
+        Ok(())
+    }
 }
 
 // ============================================================================
@@ -872,4 +12604,511 @@ pub enum ErrorCode {
     
     #[msg("Arithmetic overflow occurred")]
     ArithmeticOverflow,
+    
+    #[msg("Dispute window must not be negative")]
+    InvalidDisputeWindow,
+    
+    #[msg("Settlement is under dispute")]
+    SettlementDisputed,
+    
+    #[msg("Dispute window has closed")]
+    DisputeWindowClosed,
+    
+    #[msg("Dispute window is still open")]
+    DisputeWindowOpen,
+    
+    #[msg("No active dispute for this run")]
+    NoActiveDispute,
+    
+    #[msg("Settlement bond has already been resolved")]
+    BondAlreadyResolved,
+    
+    #[msg("Vault balance does not reconcile with settled balance and payouts")]
+    VaultInsolvent,
+    
+    #[msg("Run is in emergency refund mode")]
+    RunInRefundMode,
+    
+    #[msg("Emergency refunds are not enabled for this run")]
+    RefundsNotEnabled,
+    
+    #[msg("Invalid run duration")]
+    InvalidDuration,
+    
+    #[msg("Run is not yet overdue for settlement")]
+    SettlementNotOverdue,
+    
+    #[msg("Run is paused")]
+    RunPaused,
+    
+    #[msg("Invalid TVL cap")]
+    InvalidTvlCap,
+    
+    #[msg("Deposit would exceed the run's TVL cap")]
+    RunTvlCapExceeded,
+    
+    #[msg("Deposit would exceed the platform's TVL cap")]
+    PlatformTvlCapExceeded,
+    
+    #[msg("Invalid deadline")]
+    InvalidDeadline,
+    
+    #[msg("Deposit window has closed")]
+    DepositWindowClosed,
+    
+    #[msg("Deposit window is still open")]
+    DepositWindowOpen,
+    
+    #[msg("Run has not reached its minimum participants or deposits")]
+    QuorumNotReached,
+    
+    #[msg("Run has already reached its quorum")]
+    QuorumAlreadyMet,
+    
+    #[msg("Waitlisted users must be promoted before new deposits")]
+    WaitlistNotEmpty,
+    
+    #[msg("Run is not full")]
+    RunNotFull,
+    
+    #[msg("User already participates in this run")]
+    AlreadyParticipating,
+    
+    #[msg("Only stake locked through the priority window may deposit during it")]
+    EarlyAccessOnly,
+    
+    #[msg("Staking has already been initialized")]
+    StakingAlreadyInitialized,
+    
+    #[msg("Invalid stake amount")]
+    InvalidStakeAmount,
+    
+    #[msg("Insufficient staked balance")]
+    InsufficientStake,
+    
+    #[msg("A valid KYC attestation is required for this run")]
+    KycRequired,
+    
+    #[msg("Wallet is blacklisted")]
+    WalletBlacklisted,
+    
+    #[msg("An invite code is required for this run")]
+    InviteRequired,
+    
+    #[msg("Invalid invite code")]
+    InvalidInviteCode,
+    
+    #[msg("Invite code has no uses left")]
+    InviteExhausted,
+    
+    #[msg("Invite must allow at least one use")]
+    InvalidInviteUses,
+    
+    #[msg("Share table is not allocated for every participant")]
+    ShareTableTooSmall,
+    
+    #[msg("Account cannot be migrated")]
+    InvalidMigrationTarget,
+    
+    #[msg("Feature is disabled on this platform")]
+    FeatureDisabled,
+    
+    #[msg("Unknown feature flag")]
+    UnknownFeature,
+    
+    #[msg("Run cannot move to the requested status")]
+    InvalidStatusTransition,
+    
+    #[msg("Run already has deposits")]
+    RunHasDeposits,
+    
+    #[msg("Deposit deadline cannot be extended that far")]
+    DeadlineExtensionTooLong,
+    
+    #[msg("The last participant cannot exit a run early")]
+    LastParticipantCannotExit,
+    
+    #[msg("No vested profit to claim")]
+    NothingVested,
+    
+    #[msg("Invalid fee exemption accounts")]
+    InvalidFeeExemption,
+    
+    #[msg("Revenue sharing has already been initialized")]
+    RevenueShareAlreadyInitialized,
+    
+    #[msg("Revenue sharing is not initialized")]
+    RevenueShareNotInitialized,
+    
+    #[msg("Revenue epoch has not ended yet")]
+    RevenueEpochNotOver,
+    
+    #[msg("No revenue or no stakers to distribute to")]
+    NothingToDistribute,
+    
+    #[msg("No revenue to claim")]
+    NoRevenueToClaim,
+    
+    #[msg("Stake tiers must be ascending with discounts of at most 100%")]
+    InvalidStakeTiers,
+    
+    #[msg("Staked tokens are still locked")]
+    StakeLocked,
+    
+    #[msg("Lock end must be in the future")]
+    InvalidLockEnd,
+    
+    #[msg("A lock cannot be shortened")]
+    CannotShortenLock,
+    
+    #[msg("Lock exceeds the maximum lock duration")]
+    LockTooLong,
+    
+    #[msg("No locked stake to withdraw")]
+    NoLockedStake,
+    
+    #[msg("Platform authority is not a governance account")]
+    GovernanceNotConfigured,
+    
+    #[msg("Instruction must be executed by the governance program")]
+    NotCalledByGovernance,
+    
+    #[msg("Scheduled action must wait at least the minimum delay")]
+    ActionDelayTooShort,
+    
+    #[msg("Scheduled action is not executable yet")]
+    ActionNotReady,
+    
+    #[msg("Parameters do not match the scheduled action")]
+    ActionParamsMismatch,
+    
+    #[msg("Only the platform authority or guardian may pause")]
+    NotAuthorityOrGuardian,
+    
+    #[msg("Only the platform authority or run manager may operate this run")]
+    NotRunOperator,
+    
+    #[msg("Manager is not registered")]
+    ManagerNotRegistered,
+    
+    #[msg("No manager fees to claim")]
+    NoManagerFees,
+    
+    #[msg("Subscription is paused")]
+    SubscriptionInactive,
+    
+    #[msg("Run is not operated by the subscribed manager")]
+    SubscriptionMismatch,
+    
+    #[msg("Run does not belong to the plan's series")]
+    InvalidSeries,
+    
+    #[msg("DCA interval has not elapsed")]
+    DcaNotDue,
+    
+    #[msg("Deposit intent signature is missing or does not match")]
+    InvalidIntentSignature,
+    
+    #[msg("Deposit intent has expired")]
+    IntentExpired,
+    
+    #[msg("Deposit intent nonce already used or out of order")]
+    InvalidIntentNonce,
+    
+    #[msg("EVM signature is missing or does not match")]
+    InvalidEvmSignature,
+    
+    #[msg("Bridge message does not match the deposit")]
+    InvalidBridgeMessage,
+    
+    #[msg("Stablecoin deposits can only leave through the run vault after conversion")]
+    AltDepositLocked,
+    
+    #[msg("Price account is not a verified feed for this mint")]
+    InvalidPriceFeed,
+    
+    #[msg("Price update is too old")]
+    StalePrice,
+    
+    #[msg("Price confidence interval is too wide")]
+    PriceTooUncertain,
+    
+    #[msg("Stablecoin is trading too far from $1")]
+    StablecoinDepegged,
+    
+    #[msg("Profit vests on this run, so the participation must stay open")]
+    ProfitVestingActive,
+    
+    #[msg("Payout accounts must be (participation, associated token account) pairs for this run")]
+    InvalidPayoutAccounts,
+    
+    #[msg("The claim deadline for this run has passed")]
+    ClaimDeadlinePassed,
+    
+    #[msg("The run has no claim deadline or it has not passed yet")]
+    ClaimWindowOpen,
+    
+    #[msg("Rent treasury does not have enough spare lamports")]
+    RentTreasuryEmpty,
+    
+    #[msg("Rent treasury account is required to return sponsored rent")]
+    RentTreasuryRequired,
+    
+    #[msg("Share-token runs only accept deposits through deposit_shares")]
+    ShareTokenRun,
+    
+    #[msg("Run does not issue share tokens")]
+    NotShareTokenRun,
+    
+    #[msg("Not enough shares")]
+    InsufficientShares,
+    
+    #[msg("Run is not open-ended")]
+    NotOpenEndedRun,
+    
+    #[msg("NAV mark is too old")]
+    StaleNav,
+    
+    #[msg("Not enough liquidity in the run vault for this redemption")]
+    InsufficientVaultLiquidity,
+    
+    #[msg("Run tier is not active")]
+    RunTierInactive,
+    
+    #[msg("Run vault is not initialized for the run's mint")]
+    VaultNotInitialized,
+    
+    #[msg("Vote counts exceed the run's vote rounds")]
+    InvalidVoteStats,
+    
+    #[msg("Account is not a participation of this run")]
+    InvalidParticipation,
+    
+    #[msg("Vote weight already recorded")]
+    VoteWeightAlreadyRecorded,
+    
+    #[msg("Deposit is below the run's minimum to vote")]
+    BelowMinVoteDeposit,
+    
+    #[msg("Cannot delegate votes to yourself")]
+    CannotDelegateToSelf,
+    
+    #[msg("Votes are already delegated")]
+    VotesAlreadyDelegated,
+    
+    #[msg("Votes are not delegated")]
+    NotDelegated,
+    
+    #[msg("Delegated votes cannot be delegated onward")]
+    DelegationChain,
+    
+    #[msg("Vote weight has not been recorded yet")]
+    VoteWeightNotRecorded,
+    
+    #[msg("Round number exceeds the run's vote rounds")]
+    InvalidVoteRound,
+    
+    #[msg("Invalid vote option")]
+    InvalidVoteOption,
+    
+    #[msg("A vote round is still open")]
+    VotingOpen,
+    
+    #[msg("Voting for this round has closed")]
+    VotingClosed,
+    
+    #[msg("Vote round already resolved")]
+    VoteRoundResolved,
+    
+    #[msg("Vote round not resolved yet")]
+    VoteRoundNotResolved,
+    
+    #[msg("Vote was not for the winning option")]
+    IncorrectVote,
+    
+    #[msg("Community decision already finalized")]
+    DecisionAlreadyFinalized,
+    
+    #[msg("Community decision not finalized yet")]
+    DecisionNotFinalized,
+    
+    #[msg("Trade does not match the community decision")]
+    DecisionMismatch,
+    
+    #[msg("No votes were cast in this round")]
+    NoVotesCast,
+    
+    #[msg("Details URI too long")]
+    DetailsUriTooLong,
+    
+    #[msg("Vote does not match the round's trade proposal")]
+    ProposalMismatch,
+    
+    #[msg("Agent bond below the minimum")]
+    AgentBondTooSmall,
+    
+    #[msg("Agent is not registered")]
+    AgentNotRegistered,
+    
+    #[msg("Agent is unbonding")]
+    AgentUnbonding,
+    
+    #[msg("Agent must start unbonding first")]
+    AgentBonded,
+    
+    #[msg("No fault recorded against the run's agent")]
+    AgentNotAtFault,
+    
+    #[msg("Strategies must be added in order, one per vote option")]
+    InvalidStrategyIndex,
+    
+    #[msg("Strategy accounts must be (strategy, strategy vault) pairs for every strategy of the run")]
+    InvalidStrategyAccounts,
+    
+    #[msg("Capital already allocated for this round")]
+    CapitalAlreadyAllocated,
+    
+    #[msg("Strategy vault still holds capital")]
+    StrategyNotEmpty,
+    
+    #[msg("Strategy already settled")]
+    StrategyAlreadySettled,
+    
+    #[msg("Rebalance limits must be at most 10000 bps")]
+    InvalidRebalanceLimits,
+    
+    #[msg("Rebalance would exceed the run's per-round movement cap")]
+    RebalanceCapExceeded,
+    
+    #[msg("Rebalance would dip into the source strategy's liquidity buffer")]
+    LiquidityBufferBreached,
+    
+    #[msg("Capital cannot be migrated into this run")]
+    InvalidMigrationRun,
+    
+    #[msg("Participant has not opted into migrating to this run")]
+    MigrationNotOptedIn,
+    
+    #[msg("Settled funds are still in escrow")]
+    SettlementInEscrow,
+    
+    #[msg("Settlement is not in escrow")]
+    SettlementNotInEscrow,
+    
+    #[msg("Settlement must be co-signed by the platform attestor")]
+    AttestorSignatureRequired,
+    
+    #[msg("Settlement report missing, mismatched or not signed by the risk oracle")]
+    InvalidSettlementReport,
+    
+    #[msg("Settlement report is too old")]
+    StaleSettlementReport,
+    
+    #[msg("Drawdown limit must be at most 10000 bps")]
+    InvalidDrawdownLimit,
+    
+    #[msg("Run's marked NAV is not below its stop-loss floor")]
+    StopLossNotTriggered,
+    
+    #[msg("Risk limits must all be non-zero")]
+    InvalidRiskParams,
+    
+    #[msg("Market is not allowed by the run's risk config")]
+    MarketNotAllowed,
+    
+    #[msg("Position exceeds the run's max position size")]
+    PositionTooLarge,
+    
+    #[msg("Position exceeds the run's max leverage")]
+    LeverageTooHigh,
+    
+    #[msg("Run reached its daily trade limit")]
+    DailyTradeLimitReached,
+    
+    #[msg("Platform has reached its cap on active runs")]
+    ActiveRunCapReached,
+    
+    #[msg("Starting this run would exceed the platform exposure cap")]
+    PlatformExposureCapExceeded,
+    
+    #[msg("Keeper incentive has too few spare lamports")]
+    KeeperIncentiveEmpty,
+    
+    #[msg("Automation start time is in the past")]
+    InvalidAutomationSchedule,
+    
+    #[msg("Automation thread's scheduled start time has not arrived")]
+    AutomationNotDue,
+    
+    #[msg("Run already has a lookup table")]
+    LookupTableExists,
+    
+    #[msg("Lookup table does not belong to this run")]
+    InvalidLookupTable,
+    
+    #[msg("Faucet mints at most 10,000 test USDC per call")]
+    FaucetLimitExceeded,
+    
+    #[msg("Settlement committee needs 1 to 10 distinct approvers and a threshold within their number")]
+    InvalidSettlementCommittee,
+    
+    #[msg("Platform has no settlement committee")]
+    NoSettlementCommittee,
+    
+    #[msg("Signer is not on the settlement committee")]
+    NotSettlementApprover,
+    
+    #[msg("Settlement figures lack the committee's approval")]
+    SettlementNotApproved,
+    
+    #[msg("Platform authority key has expired; propose and accept a new authority")]
+    AuthorityExpired,
+    
+    #[msg("Proposed authority is already the platform authority")]
+    AuthorityUnchanged,
+    
+    #[msg("Signer is not the proposed platform authority")]
+    NotPendingAuthority,
+    
+    #[msg("Participation is frozen by the platform")]
+    ParticipationFrozen,
+    
+    #[msg("Squad has already committed its pool to the run")]
+    SquadCommitted,
+    
+    #[msg("Squad has no contributions to commit")]
+    EmptySquad,
+    
+    #[msg("Squad has not withdrawn its share from the run yet")]
+    SquadNotWithdrawn,
+    
+    #[msg("Squad has not committed its pool to the run")]
+    SquadNotCommitted,
+    
+    #[msg("Squad members have started claiming their shares")]
+    SquadClaimsStarted,
+    
+    #[msg("Tip must be positive and within your settled share")]
+    TipExceedsShare,
+    
+    #[msg("Cannot tip your own participation")]
+    CannotTipSelf,
+    
+    #[msg("Waitlist head can still be promoted")]
+    WaitlistHeadPromotable,
+    
+    #[msg("Invite is bound to another wallet")]
+    InviteNotForWallet,
+    
+    #[msg("A settlement committee is in force; change it through a scheduled action")]
+    SettlementCommitteeTimelocked,
+    
+    #[msg("Upheld disputes must correct at least one participant, passed with their token accounts; rejected disputes none")]
+    InvalidDisputeCorrections,
+    
+    #[msg("Stablecoin deposits must be converted into the run vault first")]
+    UnconvertedDeposits,
+    
+    #[msg("This stablecoin was converted into the run vault, so its refund is paid from there")]
+    SubVaultConverted,
 }
@@ -1,7 +1,16 @@
 const anchor = require("@coral-xyz/anchor");
 const { SystemProgram, Keypair, LAMPORTS_PER_SOL } = anchor.web3;
-const { TOKEN_PROGRAM_ID, createMint, createAccount, mintTo, getAccount } = require("@solana/spl-token");
+const {
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  createMint,
+  createAccount,
+  mintTo,
+  getAccount,
+  getAssociatedTokenAddressSync,
+} = require("@solana/spl-token");
 const assert = require("assert");
+const { createHash } = require("crypto");
 
 describe("Instinct Trading", () => {
   // Configure the client to use the local cluster
@@ -19,12 +28,209 @@ describe("Instinct Trading", () => {
   let user2;
   let user1TokenAccount;
   let user2TokenAccount;
+  let authorityTokenAccount;
 
   const PLATFORM_FEE_BPS = 1500; // 15%
+  const PLATFORM_ID = new anchor.BN(1);
   const RUN_ID = new anchor.BN(1);
   const MIN_DEPOSIT = new anchor.BN(10_000_000); // 10 USDC (6 decimals)
   const MAX_DEPOSIT = new anchor.BN(100_000_000); // 100 USDC
   const MAX_PARTICIPANTS = 100;
+  const MAX_DURATION = new anchor.BN(7 * 24 * 60 * 60); // 7 days
+  const MAX_RUN_TVL = new anchor.BN(0); // uncapped
+  const riskParams = {
+    maxPositionSize: new anchor.BN(1_000_000_000), // 1,000 USDC
+    maxLeverageBps: 30000, // 3x
+    allowedMarkets: new anchor.BN(0b111), // SOL, BTC, ETH perps
+    maxDailyTrades: 20,
+  };
+  const runParams = () => ({
+    minDeposit: MIN_DEPOSIT,
+    maxDeposit: MAX_DEPOSIT,
+    maxParticipants: MAX_PARTICIPANTS,
+    maxDuration: MAX_DURATION,
+    maxRunTvl: MAX_RUN_TVL,
+    minParticipants: 1,
+    minTotalDeposit: new anchor.BN(0),
+    depositDeadline: new anchor.BN(Math.floor(Date.now() / 1000) + 60 * 60),
+    autoStart: false,
+    earlyAccessUntil: new anchor.BN(0),
+    requiresKyc: false,
+    isPrivate: false,
+    profitVestingSecs: new anchor.BN(0), // profits paid at withdrawal
+    managementFeeBpsPerYear: 0,
+    seriesId: new anchor.BN(0), // standalone run
+    claimWindowSecs: new anchor.BN(0), // no claim deadline
+    bonusMode: { additive: {} },
+    bonusBpsPerCorrectVote: 100, // 1% of the profit share per correct vote
+    maxVoteRounds: 12,
+    minVoteDeposit: new anchor.BN(0), // any deposit may vote
+    maxRebalanceBpsPerRound: 0, // no strategy rebalancing
+    liquidityBufferBps: 0,
+    maxDrawdownBps: 0, // no drawdown breaker
+    stopLossNav: new anchor.BN(0), // no stop-loss
+  });
+
+  const findPda = (...seeds) =>
+    anchor.web3.PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+  const runPdaOf = (prefix, runId) =>
+    findPda(Buffer.from(prefix), platformPda.toBuffer(), runId.toArrayLike(Buffer, "le", 8));
+  const userPdaOf = (prefix, runId, user) =>
+    findPda(Buffer.from(prefix), platformPda.toBuffer(), runId.toArrayLike(Buffer, "le", 8), user.toBuffer());
+  const walletPdaOf = (prefix, user) => findPda(Buffer.from(prefix), platformPda.toBuffer(), user.toBuffer());
+
+  // A fresh wallet with SOL for rent and 1000 USDC
+  const createFundedUser = async () => {
+    const keypair = Keypair.generate();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(keypair.publicKey, 2 * LAMPORTS_PER_SOL)
+    );
+    const tokenAccount = await createAccount(provider.connection, payer.payer, usdcMint, keypair.publicKey);
+    await mintTo(provider.connection, payer.payer, usdcMint, tokenAccount, payer.publicKey, 1000_000_000);
+    return { keypair, tokenAccount };
+  };
+
+  // Create a run under the platform's next run id, returning that id
+  const createTestRun = async (overrides = {}) => {
+    const platform = await program.account.platform.fetch(platformPda);
+    const runId = platform.totalRuns.addn(1);
+    await program.methods
+      .createRun({ ...runParams(), ...overrides }, riskParams)
+      .accounts({
+        platform: platformPda,
+        run: runPdaOf("run", runId),
+        runVault: runPdaOf("vault", runId),
+        riskConfig: runPdaOf("risk_config", runId),
+        mint: usdcMint,
+        tier: null,
+        authority: platformAuthority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([platformAuthority])
+      .rpc();
+    return runId;
+  };
+
+  const depositInto = (runId, user, amount, { invite = null, inviteCode = null } = {}) =>
+    program.methods
+      .deposit(runId, amount, inviteCode)
+      .accounts({
+        platform: platformPda,
+        run: runPdaOf("run", runId),
+        userParticipation: userPdaOf("participation", runId, user.keypair.publicKey),
+        // Test runs stay within their first participant page
+        participantPage: findPda(
+          Buffer.from("participant_page"),
+          platformPda.toBuffer(),
+          runId.toArrayLike(Buffer, "le", 8),
+          new anchor.BN(0).toArrayLike(Buffer, "le", 4)
+        ),
+        runVault: runPdaOf("vault", runId),
+        userTokenAccount: user.tokenAccount,
+        usdcMint: usdcMint,
+        stake: null,
+        kycAttestation: null,
+        invite,
+        blacklistEntry: walletPdaOf("blacklist", user.keypair.publicKey),
+        user: user.keypair.publicKey,
+        rentTreasury: null,
+        memoProgram: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([user.keypair])
+      .rpc();
+
+  // Start a run, snapshotting the vote weights of the given participants
+  const startTestRun = (runId, participants) =>
+    program.methods
+      .startRun(runId)
+      .accounts({
+        platform: platformPda,
+        run: runPdaOf("run", runId),
+        runVault: runPdaOf("vault", runId),
+        automation: null,
+        authority: platformAuthority.publicKey,
+      })
+      .remainingAccounts(
+        participants.map((user) => ({
+          pubkey: userPdaOf("participation", runId, user.keypair.publicKey),
+          isWritable: true,
+          isSigner: false,
+        }))
+      )
+      .signers([platformAuthority])
+      .rpc();
+
+  const prepareShareTable = async (runId) => {
+    for (const method of ["initShareTable", "allocateShareTable"]) {
+      await program.methods[method](runId)
+        .accounts({
+          platform: platformPda,
+          run: runPdaOf("run", runId),
+          shareTable: runPdaOf("share_table", runId),
+          authority: platformAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([platformAuthority])
+        .rpc();
+    }
+  };
+
+  // Accounts of settle_run and complete_settlement
+  const settleAccounts = (runId, extraAccounts = {}) => ({
+    platform: platformPda,
+    run: runPdaOf("run", runId),
+    runVault: runPdaOf("vault", runId),
+    settlerTokenAccount: authorityTokenAccount,
+    revenueVault: null,
+    managerProfile: null,
+    managerFeeVault: null,
+    usdcMint: usdcMint,
+    authority: platformAuthority.publicKey,
+    attestor: null,
+    settlementCommittee: null,
+    settlementApproval: null,
+    instructions: null,
+    memoProgram: null,
+    ...extraAccounts,
+  });
+
+  const vaultBalanceOf = async (runId) =>
+    new anchor.BN((await getAccount(provider.connection, runPdaOf("vault", runId))).amount.toString());
+
+  // Settle a run at its vault balance with the given { user, shareAmount } shares
+  const settleTestRun = async (runId, participantShares, extraAccounts = {}) => {
+    await prepareShareTable(runId);
+    await program.methods
+      .settleRun(runId, await vaultBalanceOf(runId), participantShares, null)
+      .accounts(settleAccounts(runId, extraAccounts))
+      .signers([platformAuthority])
+      .rpc();
+  };
+
+  // Accounts of withdraw and the instructions sharing its context
+  const withdrawAccounts = (runId, user) => ({
+    platform: platformPda,
+    run: runPdaOf("run", runId),
+    userParticipation: userPdaOf("participation", runId, user.keypair.publicKey),
+    runVault: runPdaOf("vault", runId),
+    userTokenAccount: user.tokenAccount,
+    mint: usdcMint,
+    stake: null,
+    user: user.keypair.publicKey,
+    rentTreasury: null,
+    acceptedMint: null,
+    memoProgram: null,
+    tokenProgram: TOKEN_PROGRAM_ID,
+  });
+
+  const adminAccounts = () => ({
+    platform: platformPda,
+    auditLog: findPda(Buffer.from("audit_log"), platformPda.toBuffer()),
+    authority: platformAuthority.publicKey,
+  });
 
   before(async () => {
     // Generate test keypairs
@@ -70,6 +276,13 @@ describe("Instinct Trading", () => {
       user2.publicKey
     );
 
+    authorityTokenAccount = await createAccount(
+      provider.connection,
+      payer.payer,
+      usdcMint,
+      platformAuthority.publicKey
+    );
+
     // Mint USDC to users
     await mintTo(
       provider.connection,
@@ -99,14 +312,15 @@ describe("Instinct Trading", () => {
   describe("Platform Initialization", () => {
     it("Initializes the platform", async () => {
       [platformPda] = anchor.web3.PublicKey.findProgramAddressSync(
-        [Buffer.from("platform")],
+        [Buffer.from("platform"), PLATFORM_ID.toArrayLike(Buffer, "le", 8)],
         program.programId
       );
 
       const tx = await program.methods
-        .initializePlatform(PLATFORM_FEE_BPS)
+        .initializePlatform(PLATFORM_ID, PLATFORM_FEE_BPS)
         .accounts({
           platform: platformPda,
+          usdcMint: usdcMint,
           authority: platformAuthority.publicKey,
           systemProgram: SystemProgram.programId,
         })
@@ -126,9 +340,10 @@ describe("Instinct Trading", () => {
     it("Fails to initialize platform twice", async () => {
       try {
         await program.methods
-          .initializePlatform(PLATFORM_FEE_BPS)
+          .initializePlatform(PLATFORM_ID, PLATFORM_FEE_BPS)
           .accounts({
             platform: platformPda,
+            usdcMint: usdcMint,
             authority: platformAuthority.publicKey,
             systemProgram: SystemProgram.programId,
           })
@@ -145,18 +360,31 @@ describe("Instinct Trading", () => {
     let runPda;
     let runVaultPda;
 
-    it("Creates a new run", async () => {
+    it("Creates a new run with its vault", async () => {
       [runPda] = anchor.web3.PublicKey.findProgramAddressSync(
-        [Buffer.from("run"), RUN_ID.toArrayLike(Buffer, "le", 8)],
+        [Buffer.from("run"), platformPda.toBuffer(), RUN_ID.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      [runVaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), platformPda.toBuffer(), RUN_ID.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const [riskConfigPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("risk_config"), platformPda.toBuffer(), RUN_ID.toArrayLike(Buffer, "le", 8)],
         program.programId
       );
 
       const tx = await program.methods
-        .createRun(RUN_ID, MIN_DEPOSIT, MAX_DEPOSIT, MAX_PARTICIPANTS)
+        .createRun(runParams(), riskParams)
         .accounts({
           platform: platformPda,
           run: runPda,
+          runVault: runVaultPda,
+          riskConfig: riskConfigPda,
+          mint: usdcMint,
+          tier: null,
           authority: platformAuthority.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
         .signers([platformAuthority])
@@ -170,34 +398,13 @@ describe("Instinct Trading", () => {
       assert.equal(run.minDeposit.toNumber(), MIN_DEPOSIT.toNumber());
       assert.equal(run.maxDeposit.toNumber(), MAX_DEPOSIT.toNumber());
       assert.equal(run.maxParticipants, MAX_PARTICIPANTS);
+      assert.equal(run.maxDuration.toNumber(), MAX_DURATION.toNumber());
       assert.equal(run.participantCount, 0);
       assert.equal(run.totalDeposited.toNumber(), 0);
 
       // Verify platform total runs increased
       const platform = await program.account.platform.fetch(platformPda);
       assert.equal(platform.totalRuns.toNumber(), 1);
-    });
-
-    it("Creates vault for the run", async () => {
-      [runVaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
-        [Buffer.from("vault"), RUN_ID.toArrayLike(Buffer, "le", 8)],
-        program.programId
-      );
-
-      const tx = await program.methods
-        .createRunVault(RUN_ID)
-        .accounts({
-          run: runPda,
-          runVault: runVaultPda,
-          usdcMint: usdcMint,
-          payer: platformAuthority.publicKey,
-          tokenProgram: TOKEN_PROGRAM_ID,
-          systemProgram: SystemProgram.programId,
-        })
-        .signers([platformAuthority])
-        .rpc();
-
-      console.log("Vault created:", tx);
 
       // Verify vault exists
       const vaultAccount = await getAccount(provider.connection, runVaultPda);
@@ -209,15 +416,25 @@ describe("Instinct Trading", () => {
   describe("User Deposits", () => {
     let runPda;
     let runVaultPda;
+    let participantPagePda;
     let user1ParticipationPda;
 
     before(() => {
       [runPda] = anchor.web3.PublicKey.findProgramAddressSync(
-        [Buffer.from("run"), RUN_ID.toArrayLike(Buffer, "le", 8)],
+        [Buffer.from("run"), platformPda.toBuffer(), RUN_ID.toArrayLike(Buffer, "le", 8)],
         program.programId
       );
       [runVaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
-        [Buffer.from("vault"), RUN_ID.toArrayLike(Buffer, "le", 8)],
+        [Buffer.from("vault"), platformPda.toBuffer(), RUN_ID.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      [participantPagePda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("participant_page"),
+          platformPda.toBuffer(),
+          RUN_ID.toArrayLike(Buffer, "le", 8),
+          new anchor.BN(0).toArrayLike(Buffer, "le", 4),
+        ],
         program.programId
       );
     });
@@ -228,6 +445,7 @@ describe("Instinct Trading", () => {
       [user1ParticipationPda] = anchor.web3.PublicKey.findProgramAddressSync(
         [
           Buffer.from("participation"),
+          platformPda.toBuffer(),
           RUN_ID.toArrayLike(Buffer, "le", 8),
           user1.publicKey.toBuffer(),
         ],
@@ -235,15 +453,21 @@ describe("Instinct Trading", () => {
       );
 
       const tx = await program.methods
-        .deposit(RUN_ID, depositAmount)
+        .deposit(RUN_ID, depositAmount, null)
         .accounts({
           platform: platformPda,
           run: runPda,
           userParticipation: user1ParticipationPda,
+          participantPage: participantPagePda,
           runVault: runVaultPda,
           userTokenAccount: user1TokenAccount,
           usdcMint: usdcMint,
+          stake: null,
+          kycAttestation: null,
+          invite: null,
           user: user1.publicKey,
+          rentTreasury: null,
+          memoProgram: null,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
@@ -268,6 +492,11 @@ describe("Instinct Trading", () => {
       const run = await program.account.run.fetch(runPda);
       assert.equal(run.participantCount, 1);
       assert.equal(run.totalDeposited.toNumber(), depositAmount.toNumber());
+
+      // Verify the participant was registered on the first page
+      const page = await program.account.participantPage.fetch(participantPagePda);
+      assert.equal(page.len, 1);
+      assert.equal(page.participants[0].toString(), user1.publicKey.toString());
     });
 
     it("Fails deposit below minimum", async () => {
@@ -276,6 +505,7 @@ describe("Instinct Trading", () => {
       const [user2ParticipationPda] = anchor.web3.PublicKey.findProgramAddressSync(
         [
           Buffer.from("participation"),
+          platformPda.toBuffer(),
           RUN_ID.toArrayLike(Buffer, "le", 8),
           user2.publicKey.toBuffer(),
         ],
@@ -284,15 +514,21 @@ describe("Instinct Trading", () => {
 
       try {
         await program.methods
-          .deposit(RUN_ID, lowAmount)
+          .deposit(RUN_ID, lowAmount, null)
           .accounts({
             platform: platformPda,
             run: runPda,
             userParticipation: user2ParticipationPda,
+            participantPage: participantPagePda,
             runVault: runVaultPda,
             userTokenAccount: user2TokenAccount,
             usdcMint: usdcMint,
+            stake: null,
+            kycAttestation: null,
+            invite: null,
             user: user2.publicKey,
+            rentTreasury: null,
+            memoProgram: null,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
           })
@@ -307,22 +543,42 @@ describe("Instinct Trading", () => {
 
   describe("Run Lifecycle", () => {
     let runPda;
+    let runVaultPda;
 
     before(() => {
       [runPda] = anchor.web3.PublicKey.findProgramAddressSync(
-        [Buffer.from("run"), RUN_ID.toArrayLike(Buffer, "le", 8)],
+        [Buffer.from("run"), platformPda.toBuffer(), RUN_ID.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      [runVaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), platformPda.toBuffer(), RUN_ID.toArrayLike(Buffer, "le", 8)],
         program.programId
       );
     });
 
     it("Starts the run", async () => {
+      const [user1ParticipationPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("participation"),
+          platformPda.toBuffer(),
+          RUN_ID.toArrayLike(Buffer, "le", 8),
+          user1.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+
       const tx = await program.methods
         .startRun(RUN_ID)
         .accounts({
           platform: platformPda,
           run: runPda,
+          runVault: runVaultPda,
+          automation: null,
           authority: platformAuthority.publicKey,
         })
+        .remainingAccounts([
+          { pubkey: user1ParticipationPda, isWritable: true, isSigner: false },
+        ])
         .signers([platformAuthority])
         .rpc();
 
@@ -332,11 +588,50 @@ describe("Instinct Trading", () => {
       const run = await program.account.run.fetch(runPda);
       assert.equal(run.status.active !== undefined, true);
       assert.ok(run.startedAt.toNumber() > 0);
+
+      // Verify the participant's vote weight was snapshotted from their deposit
+      const participation = await program.account.userParticipation.fetch(user1ParticipationPda);
+      assert.equal(participation.voteWeight.toString(), participation.depositAmount.toString());
+    });
+
+    it("Allocates the share table", async () => {
+      const [shareTablePda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("share_table"), platformPda.toBuffer(), RUN_ID.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+
+      await program.methods
+        .initShareTable(RUN_ID)
+        .accounts({
+          platform: platformPda,
+          run: runPda,
+          shareTable: shareTablePda,
+          authority: platformAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([platformAuthority])
+        .rpc();
+
+      await program.methods
+        .allocateShareTable(RUN_ID)
+        .accounts({
+          platform: platformPda,
+          run: runPda,
+          shareTable: shareTablePda,
+          authority: platformAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([platformAuthority])
+        .rpc();
+
+      const run = await program.account.run.fetch(runPda);
+      const shareTable = await program.account.shareTable.fetch(shareTablePda);
+      assert.equal(shareTable.capacity, run.participantCount);
     });
 
     it("Settles the run", async () => {
       const [runVaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
-        [Buffer.from("vault"), RUN_ID.toArrayLike(Buffer, "le", 8)],
+        [Buffer.from("vault"), platformPda.toBuffer(), RUN_ID.toArrayLike(Buffer, "le", 8)],
         program.programId
       );
 
@@ -355,12 +650,22 @@ describe("Instinct Trading", () => {
       ];
 
       const tx = await program.methods
-        .settleRun(RUN_ID, finalBalance, participantShares)
+        .settleRun(RUN_ID, finalBalance, participantShares, null)
         .accounts({
           platform: platformPda,
           run: runPda,
           runVault: runVaultPda,
+          settlerTokenAccount: authorityTokenAccount,
+          revenueVault: null,
+          managerProfile: null,
+          managerFeeVault: null,
+          usdcMint: usdcMint,
           authority: platformAuthority.publicKey,
+          attestor: null,
+          settlementCommittee: null,
+          settlementApproval: null,
+          instructions: null,
+          memoProgram: null,
         })
         .signers([platformAuthority])
         .rpc();
@@ -372,6 +677,13 @@ describe("Instinct Trading", () => {
       assert.equal(updatedRun.status.settled !== undefined, true);
       assert.equal(updatedRun.finalBalance.toNumber(), finalBalance.toNumber());
       assert.ok(updatedRun.endedAt.toNumber() > 0);
+
+      const [shareTablePda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("share_table"), platformPda.toBuffer(), RUN_ID.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const shareTable = await program.account.shareTable.fetch(shareTablePda);
+      assert.equal(shareTable.len, participantShares.length);
     });
   });
 
@@ -382,16 +694,17 @@ describe("Instinct Trading", () => {
 
     before(() => {
       [runPda] = anchor.web3.PublicKey.findProgramAddressSync(
-        [Buffer.from("run"), RUN_ID.toArrayLike(Buffer, "le", 8)],
+        [Buffer.from("run"), platformPda.toBuffer(), RUN_ID.toArrayLike(Buffer, "le", 8)],
         program.programId
       );
       [runVaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
-        [Buffer.from("vault"), RUN_ID.toArrayLike(Buffer, "le", 8)],
+        [Buffer.from("vault"), platformPda.toBuffer(), RUN_ID.toArrayLike(Buffer, "le", 8)],
         program.programId
       );
       [user1ParticipationPda] = anchor.web3.PublicKey.findProgramAddressSync(
         [
           Buffer.from("participation"),
+          platformPda.toBuffer(),
           RUN_ID.toArrayLike(Buffer, "le", 8),
           user1.publicKey.toBuffer(),
         ],
@@ -405,11 +718,17 @@ describe("Instinct Trading", () => {
       const tx = await program.methods
         .withdraw(RUN_ID)
         .accounts({
+          platform: platformPda,
           run: runPda,
           userParticipation: user1ParticipationPda,
           runVault: runVaultPda,
           userTokenAccount: user1TokenAccount,
+          mint: usdcMint,
+          stake: null,
           user: user1.publicKey,
+          rentTreasury: null,
+          acceptedMint: null,
+          memoProgram: null,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
         .signers([user1])
@@ -434,11 +753,17 @@ describe("Instinct Trading", () => {
         await program.methods
           .withdraw(RUN_ID)
           .accounts({
+            platform: platformPda,
             run: runPda,
             userParticipation: user1ParticipationPda,
             runVault: runVaultPda,
             userTokenAccount: user1TokenAccount,
+            mint: usdcMint,
+            stake: null,
             user: user1.publicKey,
+            rentTreasury: null,
+            acceptedMint: null,
+            memoProgram: null,
             tokenProgram: TOKEN_PROGRAM_ID,
           })
           .signers([user1])
@@ -450,13 +775,1269 @@ describe("Instinct Trading", () => {
     });
   });
 
-  describe("Admin Functions", () => {
-    it("Pauses the platform", async () => {
-      const tx = await program.methods
-        .pausePlatform()
+  describe("Waitlist", () => {
+    let runId;
+    let member;
+    let queued;
+
+    const joinWaitlist = (user, position, amount) =>
+      program.methods
+        .joinWaitlist(runId, amount)
+        .accounts({
+          platform: platformPda,
+          run: runPdaOf("run", runId),
+          waitlistEntry: findPda(
+            Buffer.from("waitlist"),
+            platformPda.toBuffer(),
+            runId.toArrayLike(Buffer, "le", 8),
+            new anchor.BN(position).toArrayLike(Buffer, "le", 8)
+          ),
+          waitlistMarker: userPdaOf("waitlist_user", runId, user.keypair.publicKey),
+          userParticipation: userPdaOf("participation", runId, user.keypair.publicKey),
+          waitlistVault: runPdaOf("waitlist_vault", runId),
+          userTokenAccount: user.tokenAccount,
+          kycAttestation: null,
+          blacklistEntry: walletPdaOf("blacklist", user.keypair.publicKey),
+          user: user.keypair.publicKey,
+          memoProgram: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user.keypair])
+        .rpc();
+
+    before(async () => {
+      member = await createFundedUser();
+      queued = await createFundedUser();
+
+      // A single-seat run is full after one deposit
+      runId = await createTestRun({ maxParticipants: 1 });
+      await depositInto(runId, member, new anchor.BN(20_000_000));
+
+      await program.methods
+        .createWaitlistVault(runId)
+        .accounts({
+          run: runPdaOf("run", runId),
+          waitlistVault: runPdaOf("waitlist_vault", runId),
+          usdcMint: usdcMint,
+          payer: platformAuthority.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([platformAuthority])
+        .rpc();
+    });
+
+    it("Queues a user for a full run", async () => {
+      const amount = new anchor.BN(20_000_000);
+      await joinWaitlist(queued, 0, amount);
+
+      const entry = await program.account.waitlistEntry.fetch(
+        findPda(
+          Buffer.from("waitlist"),
+          platformPda.toBuffer(),
+          runId.toArrayLike(Buffer, "le", 8),
+          new anchor.BN(0).toArrayLike(Buffer, "le", 8)
+        )
+      );
+      assert.equal(entry.user.toString(), queued.keypair.publicKey.toString());
+      assert.equal(entry.amount.toNumber(), amount.toNumber());
+      assert.equal(entry.position.toNumber(), 0);
+
+      const run = await program.account.run.fetch(runPdaOf("run", runId));
+      assert.equal(run.waitlistTail.toNumber(), 1);
+
+      const waitlistVault = await getAccount(provider.connection, runPdaOf("waitlist_vault", runId));
+      assert.equal(waitlistVault.amount.toString(), amount.toString());
+    });
+
+    it("Fails to queue the same wallet twice", async () => {
+      try {
+        await joinWaitlist(queued, 1, new anchor.BN(20_000_000));
+        assert.fail("Should have thrown error");
+      } catch (err) {
+        assert.ok(err.toString().includes("already in use"));
+      }
+    });
+
+    it("Fails to skip a head that can still be promoted", async () => {
+      try {
+        await program.methods
+          .skipWaitlistHead(runId)
+          .accounts({
+            platform: platformPda,
+            run: runPdaOf("run", runId),
+            waitlistEntry: findPda(
+              Buffer.from("waitlist"),
+              platformPda.toBuffer(),
+              runId.toArrayLike(Buffer, "le", 8),
+              new anchor.BN(0).toArrayLike(Buffer, "le", 8)
+            ),
+            userParticipation: userPdaOf("participation", runId, queued.keypair.publicKey),
+            blacklistEntry: walletPdaOf("blacklist", queued.keypair.publicKey),
+            keeperIncentive: null,
+            cranker: member.keypair.publicKey,
+          })
+          .signers([member.keypair])
+          .rpc();
+        assert.fail("Should have thrown error");
+      } catch (err) {
+        assert.ok(err.toString().includes("WaitlistHeadPromotable"));
+      }
+    });
+  });
+
+  describe("Invites", () => {
+    const inviteCode = Buffer.from("instinct-private-alpha");
+    const codeHash = createHash("sha256").update(inviteCode).digest();
+    let runId;
+    let invitePda;
+    let invited;
+    let outsider;
+
+    before(async () => {
+      invited = await createFundedUser();
+      outsider = await createFundedUser();
+      runId = await createTestRun({ isPrivate: true });
+      invitePda = findPda(
+        Buffer.from("invite"),
+        platformPda.toBuffer(),
+        runId.toArrayLike(Buffer, "le", 8),
+        codeHash
+      );
+
+      await program.methods
+        .createInvite(runId, [...codeHash], 1, invited.keypair.publicKey)
         .accounts({
           platform: platformPda,
+          run: runPdaOf("run", runId),
+          invite: invitePda,
           authority: platformAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([platformAuthority])
+        .rpc();
+    });
+
+    it("Fails a private deposit without an invite", async () => {
+      try {
+        await depositInto(runId, outsider, new anchor.BN(20_000_000));
+        assert.fail("Should have thrown error");
+      } catch (err) {
+        assert.ok(err.toString().includes("InviteRequired"));
+      }
+    });
+
+    it("Fails an invite presented by another wallet", async () => {
+      try {
+        await depositInto(runId, outsider, new anchor.BN(20_000_000), { invite: invitePda, inviteCode });
+        assert.fail("Should have thrown error");
+      } catch (err) {
+        assert.ok(err.toString().includes("InviteNotForWallet"));
+      }
+    });
+
+    it("Admits the invited wallet", async () => {
+      const amount = new anchor.BN(20_000_000);
+      await depositInto(runId, invited, amount, { invite: invitePda, inviteCode });
+
+      const invite = await program.account.invite.fetch(invitePda);
+      assert.equal(invite.remainingUses, 0);
+      assert.equal(invite.wallet.toString(), invited.keypair.publicKey.toString());
+      assert.equal(invite.platform.toString(), platformPda.toString());
+
+      const run = await program.account.run.fetch(runPdaOf("run", runId));
+      assert.equal(run.participantCount, 1);
+      assert.equal(run.totalDeposited.toNumber(), amount.toNumber());
+    });
+  });
+
+  describe("Settlement Disputes", () => {
+    const SETTLEMENT_BOND = new anchor.BN(5_000_000); // 5 USDC
+    const DEPOSIT = new anchor.BN(20_000_000);
+    let runId;
+    let participant;
+    let outsider;
+
+    before(async () => {
+      participant = await createFundedUser();
+      outsider = await createFundedUser();
+      await mintTo(provider.connection, payer.payer, usdcMint, authorityTokenAccount, payer.publicKey, SETTLEMENT_BOND.toNumber());
+
+      await program.methods
+        .setSettlementBond(SETTLEMENT_BOND, new anchor.BN(60 * 60))
+        .accounts(adminAccounts())
+        .signers([platformAuthority])
+        .rpc();
+
+      runId = await createTestRun();
+      await depositInto(runId, participant, DEPOSIT);
+      await startTestRun(runId, [participant]);
+      await settleTestRun(runId, [{ user: participant.keypair.publicKey, shareAmount: DEPOSIT }]);
+    });
+
+    after(async () => {
+      // Later suites settle without a bond
+      await program.methods
+        .setSettlementBond(new anchor.BN(0), new anchor.BN(0))
+        .accounts(adminAccounts())
+        .signers([platformAuthority])
+        .rpc();
+    });
+
+    const disputeAs = (user) =>
+      program.methods
+        .disputeSettlement(runId)
+        .accounts({
+          run: runPdaOf("run", runId),
+          userParticipation: userPdaOf("participation", runId, user.keypair.publicKey),
+          user: user.keypair.publicKey,
+        })
+        .signers([user.keypair])
+        .rpc();
+
+    const releaseBond = () =>
+      program.methods
+        .releaseSettlementBond(runId)
+        .accounts({
+          run: runPdaOf("run", runId),
+          bondVault: runPdaOf("bond_vault", runId),
+          settlerTokenAccount: authorityTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+    it("Holds the settler's bond through the dispute window", async () => {
+      const run = await program.account.run.fetch(runPdaOf("run", runId));
+      assert.equal(run.settlementBond.toNumber(), SETTLEMENT_BOND.toNumber());
+      assert.equal(run.settler.toString(), platformAuthority.publicKey.toString());
+      assert.ok(run.disputeDeadline.toNumber() > 0);
+
+      const bondVault = await getAccount(provider.connection, runPdaOf("bond_vault", runId));
+      assert.equal(bondVault.amount.toString(), SETTLEMENT_BOND.toString());
+
+      try {
+        await releaseBond();
+        assert.fail("Should have thrown error");
+      } catch (err) {
+        assert.ok(err.toString().includes("DisputeWindowOpen"));
+      }
+    });
+
+    it("Fails a dispute from a wallet outside the run", async () => {
+      try {
+        await disputeAs(outsider);
+        assert.fail("Should have thrown error");
+      } catch (err) {
+        assert.ok(err.toString().includes("AccountNotInitialized"));
+      }
+    });
+
+    it("Participant disputes the settlement", async () => {
+      await disputeAs(participant);
+
+      const run = await program.account.run.fetch(runPdaOf("run", runId));
+      assert.equal(run.status.disputed !== undefined, true);
+    });
+
+    it("Fails to dispute the same settlement twice", async () => {
+      try {
+        await disputeAs(participant);
+        assert.fail("Should have thrown error");
+      } catch (err) {
+        assert.ok(err.toString().includes("SettlementDisputed"));
+      }
+    });
+
+    it("Rejects the dispute and returns the bond to the settler", async () => {
+      const balanceBefore = await getAccount(provider.connection, authorityTokenAccount);
+
+      await program.methods
+        .resolveDispute(runId, false, [])
+        .accounts({
+          ...adminAccounts(),
+          run: runPdaOf("run", runId),
+          bondVault: runPdaOf("bond_vault", runId),
+          settlerTokenAccount: authorityTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([platformAuthority])
+        .rpc();
+
+      const run = await program.account.run.fetch(runPdaOf("run", runId));
+      assert.equal(run.status.settled !== undefined, true);
+      assert.equal(run.bondResolved, true);
+
+      const balanceAfter = await getAccount(provider.connection, authorityTokenAccount);
+      assert.equal((balanceAfter.amount - balanceBefore.amount).toString(), SETTLEMENT_BOND.toString());
+    });
+  });
+
+  describe("Emergency Refunds", () => {
+    const DEPOSIT_A = new anchor.BN(20_000_000);
+    const DEPOSIT_B = new anchor.BN(30_000_000);
+    let runId;
+    let depositorA;
+    let depositorB;
+    let outsider;
+
+    const claimRefund = (user) =>
+      program.methods
+        .claimRefund(runId)
+        .accounts(withdrawAccounts(runId, user))
+        .signers([user.keypair])
+        .rpc();
+
+    before(async () => {
+      depositorA = await createFundedUser();
+      depositorB = await createFundedUser();
+      outsider = await createFundedUser();
+      runId = await createTestRun();
+      await depositInto(runId, depositorA, DEPOSIT_A);
+      await depositInto(runId, depositorB, DEPOSIT_B);
+    });
+
+    it("Fails a refund before refunds are enabled", async () => {
+      try {
+        await claimRefund(depositorA);
+        assert.fail("Should have thrown error");
+      } catch (err) {
+        assert.ok(err.toString().includes("RefundsNotEnabled"));
+      }
+    });
+
+    it("Cancels a waiting run into refunds", async () => {
+      await program.methods
+        .enableEmergencyRefunds(runId)
+        .accounts({
+          ...adminAccounts(),
+          run: runPdaOf("run", runId),
+          runVault: runPdaOf("vault", runId),
+        })
+        .signers([platformAuthority])
+        .rpc();
+
+      const run = await program.account.run.fetch(runPdaOf("run", runId));
+      assert.equal(run.status.cancelled !== undefined, true);
+      assert.equal(run.finalBalance.toNumber(), DEPOSIT_A.add(DEPOSIT_B).toNumber());
+    });
+
+    it("Refunds a depositor their deposit", async () => {
+      const balanceBefore = await getAccount(provider.connection, depositorA.tokenAccount);
+      await claimRefund(depositorA);
+
+      const balanceAfter = await getAccount(provider.connection, depositorA.tokenAccount);
+      assert.equal((balanceAfter.amount - balanceBefore.amount).toString(), DEPOSIT_A.toString());
+
+      const participation = await program.account.userParticipation.fetch(
+        userPdaOf("participation", runId, depositorA.keypair.publicKey)
+      );
+      assert.equal(participation.withdrawn, true);
+    });
+
+    it("Fails to claim a refund twice", async () => {
+      try {
+        await claimRefund(depositorA);
+        assert.fail("Should have thrown error");
+      } catch (err) {
+        assert.ok(err.toString().includes("AlreadyWithdrawn"));
+      }
+    });
+
+    it("Fails a refund for a wallet that never deposited", async () => {
+      try {
+        await claimRefund(outsider);
+        assert.fail("Should have thrown error");
+      } catch (err) {
+        assert.ok(err.toString().includes("AccountNotInitialized"));
+      }
+    });
+  });
+
+  describe("Share Tokens", () => {
+    const DEPOSIT = new anchor.BN(20_000_000);
+    let runId;
+    let shareMint;
+    let holder;
+    let holderShareAccount;
+
+    const initShareMint = (id) =>
+      program.methods
+        .initShareMint(id, true)
+        .accounts({
+          platform: platformPda,
+          run: runPdaOf("run", id),
+          shareMint: runPdaOf("share_mint", id),
+          authority: platformAuthority.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([platformAuthority])
+        .rpc();
+
+    const redeemShares = (shares) =>
+      program.methods
+        .redeemShares(runId, shares)
+        .accounts({
+          platform: platformPda,
+          run: runPdaOf("run", runId),
+          runVault: runPdaOf("vault", runId),
+          shareMint,
+          userShareAccount: holderShareAccount,
+          userTokenAccount: holder.tokenAccount,
+          blacklistEntry: walletPdaOf("blacklist", holder.keypair.publicKey),
+          user: holder.keypair.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([holder.keypair])
+        .rpc();
+
+    before(async () => {
+      holder = await createFundedUser();
+      runId = await createTestRun();
+      shareMint = runPdaOf("share_mint", runId);
+      holderShareAccount = getAssociatedTokenAddressSync(shareMint, holder.keypair.publicKey);
+
+      // Open-ended, so shares redeem while the run is still waiting
+      await initShareMint(runId);
+    });
+
+    it("Mints shares 1:1 into an empty vault", async () => {
+      await program.methods
+        .depositShares(runId, DEPOSIT)
+        .accounts({
+          platform: platformPda,
+          run: runPdaOf("run", runId),
+          runVault: runPdaOf("vault", runId),
+          shareMint,
+          userTokenAccount: holder.tokenAccount,
+          userShareAccount: holderShareAccount,
+          stake: null,
+          kycAttestation: null,
+          blacklistEntry: walletPdaOf("blacklist", holder.keypair.publicKey),
+          user: holder.keypair.publicKey,
+          memoProgram: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([holder.keypair])
+        .rpc();
+
+      const shares = await getAccount(provider.connection, holderShareAccount);
+      assert.equal(shares.amount.toString(), DEPOSIT.toString());
+
+      const run = await program.account.run.fetch(runPdaOf("run", runId));
+      assert.equal(run.shareMint.toString(), shareMint.toString());
+      assert.equal(run.totalDeposited.toNumber(), DEPOSIT.toNumber());
+    });
+
+    it("Fails to redeem more shares than were minted", async () => {
+      try {
+        await redeemShares(DEPOSIT.addn(1));
+        assert.fail("Should have thrown error");
+      } catch (err) {
+        assert.ok(err.toString().includes("InsufficientShares"));
+      }
+    });
+
+    it("Redeems shares at NAV while the open-ended run waits", async () => {
+      const redeemed = new anchor.BN(5_000_000);
+      const balanceBefore = await getAccount(provider.connection, holder.tokenAccount);
+      await redeemShares(redeemed);
+
+      const balanceAfter = await getAccount(provider.connection, holder.tokenAccount);
+      assert.equal((balanceAfter.amount - balanceBefore.amount).toString(), redeemed.toString());
+
+      const shares = await getAccount(provider.connection, holderShareAccount);
+      assert.equal(shares.amount.toString(), DEPOSIT.sub(redeemed).toString());
+    });
+
+    it("Fails to issue shares for a run that already holds deposits", async () => {
+      const depositor = await createFundedUser();
+      const participationRunId = await createTestRun();
+      await depositInto(participationRunId, depositor, DEPOSIT);
+
+      try {
+        await initShareMint(participationRunId);
+        assert.fail("Should have thrown error");
+      } catch (err) {
+        assert.ok(err.toString().includes("RunHasDeposits"));
+      }
+    });
+  });
+
+  describe("Zero-Sum Bonus", () => {
+    const DEPOSIT = new anchor.BN(20_000_000);
+    const PROFIT = new anchor.BN(10_000_000);
+    let runId;
+    let rightVoter;
+    let wrongVoter;
+
+    const updateVoteStats = (user, correctVotes, totalVotes) =>
+      program.methods
+        .updateVoteStats(runId, user.keypair.publicKey, correctVotes, totalVotes)
+        .accounts({
+          platform: platformPda,
+          run: runPdaOf("run", runId),
+          userParticipation: userPdaOf("participation", runId, user.keypair.publicKey),
+          authority: platformAuthority.publicKey,
+        })
+        .signers([platformAuthority])
+        .rpc();
+
+    const withdrawAs = (user) =>
+      program.methods
+        .withdraw(runId)
+        .accounts(withdrawAccounts(runId, user))
+        .signers([user.keypair])
+        .rpc();
+
+    before(async () => {
+      rightVoter = await createFundedUser();
+      wrongVoter = await createFundedUser();
+      runId = await createTestRun({ bonusMode: { zeroSum: {} }, bonusBpsPerCorrectVote: 1000 });
+      await depositInto(runId, rightVoter, DEPOSIT);
+      await depositInto(runId, wrongVoter, DEPOSIT);
+      await startTestRun(runId, [rightVoter, wrongVoter]);
+    });
+
+    it("Fails vote stats with more correct votes than votes cast", async () => {
+      try {
+        await updateVoteStats(rightVoter, 3, 2);
+        assert.fail("Should have thrown error");
+      } catch (err) {
+        assert.ok(err.toString().includes("InvalidVoteStats"));
+      }
+    });
+
+    it("Balances bonus and penalty weights", async () => {
+      await updateVoteStats(rightVoter, 2, 2);
+      await updateVoteStats(wrongVoter, 0, 2);
+
+      const run = await program.account.run.fetch(runPdaOf("run", runId));
+      assert.ok(run.voteBonusWeight.gtn(0));
+      assert.equal(run.voteBonusWeight.toString(), run.votePenaltyWeight.toString());
+    });
+
+    it("Funds the correct voter's bonus from the incorrect voter's profit", async () => {
+      // Trading profit lands in the vault before settlement
+      await mintTo(provider.connection, payer.payer, usdcMint, runPdaOf("vault", runId), payer.publicKey, PROFIT.toNumber());
+      await settleTestRun(runId, [
+        { user: rightVoter.keypair.publicKey, shareAmount: DEPOSIT },
+        { user: wrongVoter.keypair.publicKey, shareAmount: DEPOSIT },
+      ]);
+      const run = await program.account.run.fetch(runPdaOf("run", runId));
+
+      const rightBefore = await getAccount(provider.connection, rightVoter.tokenAccount);
+      await withdrawAs(rightVoter);
+      const rightAfter = await getAccount(provider.connection, rightVoter.tokenAccount);
+
+      const wrongBefore = await getAccount(provider.connection, wrongVoter.tokenAccount);
+      await withdrawAs(wrongVoter);
+      const wrongAfter = await getAccount(provider.connection, wrongVoter.tokenAccount);
+
+      // Equal deposits: the 20% bonus and 20% cut on each 4.25 USDC profit share cancel out
+      const rightPaid = rightAfter.amount - rightBefore.amount;
+      const wrongPaid = wrongAfter.amount - wrongBefore.amount;
+      assert.equal(rightPaid.toString(), "25100000");
+      assert.equal(wrongPaid.toString(), "23400000");
+      assert.equal((rightPaid + wrongPaid).toString(), run.finalBalance.toString());
+    });
+  });
+
+  describe("Vote Rounds", () => {
+    const proposalHash = createHash("sha256").update("long SOL-PERP 2x").digest();
+    let runId;
+    let voterA;
+    let voterB;
+    let voterC;
+
+    const roundPdaOf = (prefix, round) =>
+      findPda(Buffer.from(prefix), platformPda.toBuffer(), runId.toArrayLike(Buffer, "le", 8), Buffer.from([round]));
+
+    const postAndOpenRound = async (round) => {
+      await program.methods
+        .postTradeProposal(runId, round, [...proposalHash], "https://instinct.fi/proposals/" + round)
+        .accounts({
+          platform: platformPda,
+          run: runPdaOf("run", runId),
+          tradeProposal: roundPdaOf("proposal", round),
+          authority: platformAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([platformAuthority])
+        .rpc();
+
+      await program.methods
+        .openVoteRound(runId, round, 2, new anchor.BN(Math.floor(Date.now() / 1000) + 10 * 60), new anchor.BN(0))
+        .accounts({
+          platform: platformPda,
+          run: runPdaOf("run", runId),
+          voteRound: roundPdaOf("vote_round", round),
+          tradeProposal: roundPdaOf("proposal", round),
+          rewardsVault: runPdaOf("round_rewards", runId),
+          mint: usdcMint,
+          authorityTokenAccount: authorityTokenAccount,
+          authority: platformAuthority.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([platformAuthority])
+        .rpc();
+    };
+
+    const castVote = (user, option, hash) => {
+      const voteRound = roundPdaOf("vote_round", 0);
+      return program.methods
+        .castVote(runId, 0, option, [...hash])
+        .accounts({
+          platform: platformPda,
+          run: runPdaOf("run", runId),
+          voteRound,
+          userParticipation: userPdaOf("participation", runId, user.keypair.publicKey),
+          voteReceipt: findPda(Buffer.from("vote"), voteRound.toBuffer(), user.keypair.publicKey.toBuffer()),
+          blacklistEntry: walletPdaOf("blacklist", user.keypair.publicKey),
+          user: user.keypair.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user.keypair])
+        .rpc();
+    };
+
+    before(async () => {
+      voterA = await createFundedUser();
+      voterB = await createFundedUser();
+      voterC = await createFundedUser();
+      runId = await createTestRun();
+      await depositInto(runId, voterA, new anchor.BN(30_000_000));
+      await depositInto(runId, voterB, new anchor.BN(20_000_000));
+      await depositInto(runId, voterC, new anchor.BN(10_000_000));
+      await startTestRun(runId, [voterA, voterB, voterC]);
+      await postAndOpenRound(0);
+    });
+
+    it("Fails a vote committed to a different proposal", async () => {
+      const otherHash = createHash("sha256").update("short SOL-PERP 2x").digest();
+      try {
+        await castVote(voterA, 1, otherHash);
+        assert.fail("Should have thrown error");
+      } catch (err) {
+        assert.ok(err.toString().includes("ProposalMismatch"));
+      }
+    });
+
+    it("Tallies votes by snapshotted weight", async () => {
+      await castVote(voterA, 1, proposalHash);
+      await castVote(voterB, 0, proposalHash);
+
+      const round = await program.account.voteRound.fetch(roundPdaOf("vote_round", 0));
+      assert.equal(round.optionWeights[1].toNumber(), 30_000_000);
+      assert.equal(round.optionWeights[0].toNumber(), 20_000_000);
+      assert.equal(round.totalWeight.toNumber(), 50_000_000);
+      assert.equal(Buffer.from(round.proposalHash).toString("hex"), proposalHash.toString("hex"));
+    });
+
+    it("Fails to vote twice in a round", async () => {
+      try {
+        await castVote(voterB, 1, proposalHash);
+        assert.fail("Should have thrown error");
+      } catch (err) {
+        assert.ok(err.toString().includes("already in use"));
+      }
+    });
+
+    it("Fails to vote while the run is paused", async () => {
+      const { authority, ...pauseAccounts } = adminAccounts();
+      await program.methods
+        .pauseRun(runId)
+        .accounts({ ...pauseAccounts, run: runPdaOf("run", runId), pauser: authority })
+        .signers([platformAuthority])
+        .rpc();
+
+      try {
+        await castVote(voterC, 0, proposalHash);
+        assert.fail("Should have thrown error");
+      } catch (err) {
+        assert.ok(err.toString().includes("RunPaused"));
+      } finally {
+        await program.methods
+          .resumeRun(runId)
+          .accounts({ ...adminAccounts(), run: runPdaOf("run", runId) })
+          .signers([platformAuthority])
+          .rpc();
+      }
+    });
+
+    it("Fails to open a round while another is open", async () => {
+      try {
+        await postAndOpenRound(1);
+        assert.fail("Should have thrown error");
+      } catch (err) {
+        assert.ok(err.toString().includes("VotingOpen"));
+      }
+    });
+  });
+
+  describe("Risk Limits", () => {
+    const FEATURE_TRADING_CPI = new anchor.BN(1 << 3);
+    const proposalHash = createHash("sha256").update("long BTC-PERP 2x").digest();
+    let runId;
+
+    const setTradingCpi = (enabled) =>
+      program.methods
+        .setFeatureFlag(FEATURE_TRADING_CPI, enabled)
+        .accounts(adminAccounts())
+        .signers([platformAuthority])
+        .rpc();
+
+    const roundPda = (prefix) =>
+      findPda(Buffer.from(prefix), platformPda.toBuffer(), runId.toArrayLike(Buffer, "le", 8), Buffer.from([0]));
+
+    const recordTrade = (option, marketIndex, size) =>
+      program.methods
+        .recordTrade(runId, 0, option, marketIndex, size)
+        .accounts({
+          platform: platformPda,
+          run: runPdaOf("run", runId),
+          riskConfig: runPdaOf("risk_config", runId),
+          voteRound: roundPda("vote_round"),
+          authority: platformAuthority.publicKey,
+        })
+        .signers([platformAuthority])
+        .rpc();
+
+    // Round 0 closes a couple of seconds after opening with the trader's vote for option 1
+    before(async () => {
+      await setTradingCpi(true);
+      const trader = await createFundedUser();
+      runId = await createTestRun();
+      await depositInto(runId, trader, new anchor.BN(50_000_000));
+      await startTestRun(runId, [trader]);
+
+      await program.methods
+        .postTradeProposal(runId, 0, [...proposalHash], "https://instinct.fi/proposals/0")
+        .accounts({
+          platform: platformPda,
+          run: runPdaOf("run", runId),
+          tradeProposal: roundPda("proposal"),
+          authority: platformAuthority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([platformAuthority])
+        .rpc();
+      await program.methods
+        .openVoteRound(runId, 0, 2, new anchor.BN(Math.floor(Date.now() / 1000) + 2), new anchor.BN(0))
+        .accounts({
+          platform: platformPda,
+          run: runPdaOf("run", runId),
+          voteRound: roundPda("vote_round"),
+          tradeProposal: roundPda("proposal"),
+          rewardsVault: runPdaOf("round_rewards", runId),
+          mint: usdcMint,
+          authorityTokenAccount: authorityTokenAccount,
+          authority: platformAuthority.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([platformAuthority])
+        .rpc();
+      await program.methods
+        .castVote(runId, 0, 1, [...proposalHash])
+        .accounts({
+          platform: platformPda,
+          run: runPdaOf("run", runId),
+          voteRound: roundPda("vote_round"),
+          userParticipation: userPdaOf("participation", runId, trader.keypair.publicKey),
+          voteReceipt: findPda(Buffer.from("vote"), roundPda("vote_round").toBuffer(), trader.keypair.publicKey.toBuffer()),
+          blacklistEntry: walletPdaOf("blacklist", trader.keypair.publicKey),
+          user: trader.keypair.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([trader.keypair])
+        .rpc();
+
+      await new Promise((resolve) => setTimeout(resolve, 5000));
+      await program.methods
+        .finalizeRoundDecision(runId, 0)
+        .accounts({
+          platform: platformPda,
+          voteRound: roundPda("vote_round"),
+          keeperIncentive: null,
+          caller: platformAuthority.publicKey,
+        })
+        .signers([platformAuthority])
+        .rpc();
+    });
+
+    it("Fails a trade against the community decision", async () => {
+      try {
+        await recordTrade(0, 1, new anchor.BN(100_000_000));
+        assert.fail("Should have thrown error");
+      } catch (err) {
+        assert.ok(err.toString().includes("DecisionMismatch"));
+      }
+    });
+
+    it("Records a trade within the run's limits", async () => {
+      await recordTrade(1, 1, new anchor.BN(100_000_000));
+
+      const riskConfig = await program.account.riskConfig.fetch(runPdaOf("risk_config", runId));
+      assert.equal(riskConfig.tradesToday, 1);
+    });
+
+    it("Fails a trade on a market the run does not allow", async () => {
+      try {
+        await recordTrade(1, 5, new anchor.BN(10_000_000));
+        assert.fail("Should have thrown error");
+      } catch (err) {
+        assert.ok(err.toString().includes("MarketNotAllowed"));
+      }
+    });
+
+    it("Fails a trade above the run's max leverage", async () => {
+      try {
+        // 3x leverage on 50 USDC of deposits allows at most 150 USDC
+        await recordTrade(1, 0, new anchor.BN(200_000_000));
+        assert.fail("Should have thrown error");
+      } catch (err) {
+        assert.ok(err.toString().includes("LeverageTooHigh"));
+      }
+    });
+
+    // Leaves the platform with trading CPIs off, as it starts
+    it("Fails to trade with trading CPIs disabled", async () => {
+      await setTradingCpi(false);
+      try {
+        await recordTrade(1, 1, new anchor.BN(10_000_000));
+        assert.fail("Should have thrown error");
+      } catch (err) {
+        assert.ok(err.toString().includes("FeatureDisabled"));
+      }
+    });
+  });
+
+  describe("Staged Settlement", () => {
+    const DEPOSIT = new anchor.BN(20_000_000);
+    let runId;
+    let participants;
+
+    const stageAccounts = () => ({
+      platform: platformPda,
+      run: runPdaOf("run", runId),
+      shareTable: runPdaOf("share_table", runId),
+      authority: platformAuthority.publicKey,
+    });
+
+    const recordShares = (users) =>
+      program.methods
+        .recordSharesBatch(
+          runId,
+          users.map((user) => ({ user: user.keypair.publicKey, shareAmount: DEPOSIT }))
+        )
+        .accounts(stageAccounts())
+        .signers([platformAuthority])
+        .rpc();
+
+    const completeSettlement = async () =>
+      program.methods
+        .completeSettlement(runId, await vaultBalanceOf(runId), null)
+        .accounts(settleAccounts(runId))
+        .signers([platformAuthority])
+        .rpc();
+
+    before(async () => {
+      participants = [await createFundedUser(), await createFundedUser(), await createFundedUser()];
+      runId = await createTestRun();
+      for (const user of participants) {
+        await depositInto(runId, user, DEPOSIT);
+      }
+      await startTestRun(runId, participants);
+      await prepareShareTable(runId);
+    });
+
+    it("Begins a staged settlement", async () => {
+      await program.methods
+        .beginSettlement(runId)
+        .accounts(stageAccounts())
+        .signers([platformAuthority])
+        .rpc();
+
+      const run = await program.account.run.fetch(runPdaOf("run", runId));
+      assert.equal(run.status.settlementPending !== undefined, true);
+      assert.equal(run.sharesRecorded, 0);
+    });
+
+    it("Fails to complete before every share is recorded", async () => {
+      await recordShares(participants.slice(0, 2));
+
+      try {
+        await completeSettlement();
+        assert.fail("Should have thrown error");
+      } catch (err) {
+        assert.ok(err.toString().includes("InvalidSharesCount"));
+      }
+    });
+
+    it("Fails to record more shares than participants", async () => {
+      try {
+        await recordShares(participants.slice(1));
+        assert.fail("Should have thrown error");
+      } catch (err) {
+        assert.ok(err.toString().includes("InvalidSharesCount"));
+      }
+    });
+
+    it("Completes once the last batch is recorded", async () => {
+      await recordShares(participants.slice(2));
+      await completeSettlement();
+
+      const run = await program.account.run.fetch(runPdaOf("run", runId));
+      assert.equal(run.status.settled !== undefined, true);
+      assert.equal(run.sharesRecorded, participants.length);
+      assert.equal(run.finalBalance.toNumber(), DEPOSIT.muln(participants.length).toNumber());
+
+      const shareTable = await program.account.shareTable.fetch(runPdaOf("share_table", runId));
+      assert.equal(shareTable.len, participants.length);
+    });
+  });
+
+  describe("Squads", () => {
+    let runId;
+    let leader;
+    let memberA;
+    let memberB;
+    let squadPda;
+    let squadVault;
+
+    const joinSquad = (member, amount) =>
+      program.methods
+        .joinSquad(runId, leader.keypair.publicKey, amount)
+        .accounts({
+          platform: platformPda,
+          run: runPdaOf("run", runId),
+          squad: squadPda,
+          squadMember: findPda(Buffer.from("squad_member"), squadPda.toBuffer(), member.keypair.publicKey.toBuffer()),
+          squadVault,
+          memberTokenAccount: member.tokenAccount,
+          kycAttestation: null,
+          blacklistEntry: walletPdaOf("blacklist", member.keypair.publicKey),
+          member: member.keypair.publicKey,
+          memoProgram: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([member.keypair])
+        .rpc();
+
+    before(async () => {
+      leader = await createFundedUser();
+      memberA = await createFundedUser();
+      memberB = await createFundedUser();
+      runId = await createTestRun();
+      squadPda = userPdaOf("squad", runId, leader.keypair.publicKey);
+      squadVault = getAssociatedTokenAddressSync(usdcMint, squadPda, true);
+
+      await program.methods
+        .createSquad(runId)
+        .accounts({
+          platform: platformPda,
+          run: runPdaOf("run", runId),
+          squad: squadPda,
+          squadVault,
+          mint: usdcMint,
+          leader: leader.keypair.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([leader.keypair])
+        .rpc();
+    });
+
+    it("Pools member contributions in the squad vault", async () => {
+      await joinSquad(memberA, new anchor.BN(15_000_000));
+      await joinSquad(memberB, new anchor.BN(10_000_000));
+
+      const squad = await program.account.squad.fetch(squadPda);
+      assert.equal(squad.memberCount, 2);
+      assert.equal(squad.totalContributed.toNumber(), 25_000_000);
+
+      const vault = await getAccount(provider.connection, squadVault);
+      assert.equal(vault.amount.toString(), "25000000");
+    });
+
+    it("Fails a contribution that takes the pool past the run's max deposit", async () => {
+      try {
+        await joinSquad(memberB, MAX_DEPOSIT);
+        assert.fail("Should have thrown error");
+      } catch (err) {
+        assert.ok(err.toString().includes("DepositTooHigh"));
+      }
+    });
+
+    it("Commits the pool as one participation", async () => {
+      const squadParticipation = userPdaOf("participation", runId, squadPda);
+      await program.methods
+        .commitSquad(runId)
+        .accounts({
+          platform: platformPda,
+          run: runPdaOf("run", runId),
+          squad: squadPda,
+          userParticipation: squadParticipation,
+          participantPage: findPda(
+            Buffer.from("participant_page"),
+            platformPda.toBuffer(),
+            runId.toArrayLike(Buffer, "le", 8),
+            new anchor.BN(0).toArrayLike(Buffer, "le", 4)
+          ),
+          squadVault,
+          runVault: runPdaOf("vault", runId),
+          stake: null,
+          kycAttestation: null,
+          blacklistEntry: walletPdaOf("blacklist", leader.keypair.publicKey),
+          leader: leader.keypair.publicKey,
+          memoProgram: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([leader.keypair])
+        .rpc();
+
+      const participation = await program.account.userParticipation.fetch(squadParticipation);
+      assert.equal(participation.user.toString(), squadPda.toString());
+      assert.equal(participation.depositAmount.toNumber(), 25_000_000);
+
+      const run = await program.account.run.fetch(runPdaOf("run", runId));
+      assert.equal(run.participantCount, 1);
+      assert.equal((await program.account.squad.fetch(squadPda)).committed, true);
+    });
+
+    it("Fails to join a committed squad", async () => {
+      try {
+        await joinSquad(memberA, new anchor.BN(1_000_000));
+        assert.fail("Should have thrown error");
+      } catch (err) {
+        assert.ok(err.toString().includes("SquadCommitted"));
+      }
+    });
+  });
+
+  describe("Tips", () => {
+    const DEPOSIT = new anchor.BN(20_000_000);
+    const TIP = new anchor.BN(5_000_000);
+    let runId;
+    let tipper;
+    let recipient;
+
+    const tip = (amount) =>
+      program.methods
+        .tip(runId, recipient.keypair.publicKey, amount)
+        .accounts({
+          platform: platformPda,
+          run: runPdaOf("run", runId),
+          senderParticipation: userPdaOf("participation", runId, tipper.keypair.publicKey),
+          recipientParticipation: userPdaOf("participation", runId, recipient.keypair.publicKey),
+          runVault: runPdaOf("vault", runId),
+          blacklistEntry: walletPdaOf("blacklist", tipper.keypair.publicKey),
+          tipper: tipper.keypair.publicKey,
+        })
+        .signers([tipper.keypair])
+        .rpc();
+
+    const withdrawAs = (user) =>
+      program.methods
+        .withdraw(runId)
+        .accounts(withdrawAccounts(runId, user))
+        .signers([user.keypair])
+        .rpc();
+
+    before(async () => {
+      tipper = await createFundedUser();
+      recipient = await createFundedUser();
+      runId = await createTestRun();
+      await depositInto(runId, tipper, DEPOSIT);
+      await depositInto(runId, recipient, DEPOSIT);
+      await startTestRun(runId, [tipper, recipient]);
+    });
+
+    it("Fails to tip before the run settles", async () => {
+      try {
+        await tip(TIP);
+        assert.fail("Should have thrown error");
+      } catch (err) {
+        assert.ok(err.toString().includes("RunNotSettled"));
+      }
+    });
+
+    it("Fails to tip more than the settled share", async () => {
+      await settleTestRun(runId, [
+        { user: tipper.keypair.publicKey, shareAmount: DEPOSIT },
+        { user: recipient.keypair.publicKey, shareAmount: DEPOSIT },
+      ]);
+
+      try {
+        await tip(DEPOSIT.addn(1));
+        assert.fail("Should have thrown error");
+      } catch (err) {
+        assert.ok(err.toString().includes("TipExceedsShare"));
+      }
+    });
+
+    it("Moves part of the tipper's share to the recipient", async () => {
+      await tip(TIP);
+
+      const sent = await program.account.userParticipation.fetch(
+        userPdaOf("participation", runId, tipper.keypair.publicKey)
+      );
+      const received = await program.account.userParticipation.fetch(
+        userPdaOf("participation", runId, recipient.keypair.publicKey)
+      );
+      assert.equal(sent.tipsSent.toNumber(), TIP.toNumber());
+      assert.equal(received.tipsReceived.toNumber(), TIP.toNumber());
+
+      const recipientBefore = await getAccount(provider.connection, recipient.tokenAccount);
+      await withdrawAs(recipient);
+      const recipientAfter = await getAccount(provider.connection, recipient.tokenAccount);
+      assert.equal((recipientAfter.amount - recipientBefore.amount).toString(), DEPOSIT.add(TIP).toString());
+
+      const tipperBefore = await getAccount(provider.connection, tipper.tokenAccount);
+      await withdrawAs(tipper);
+      const tipperAfter = await getAccount(provider.connection, tipper.tokenAccount);
+      assert.equal((tipperAfter.amount - tipperBefore.amount).toString(), DEPOSIT.sub(TIP).toString());
+    });
+  });
+
+  // A committee can only be removed through the timelock, so this suite runs after every other settlement
+  describe("Settlement Committee", () => {
+    const DEPOSIT = new anchor.BN(20_000_000);
+    let runId;
+    let participant;
+    let approverA;
+    let approverB;
+    let outsider;
+    let participantShares;
+    let sharesHash;
+
+    const committeePda = () => findPda(Buffer.from("settlement_committee"), platformPda.toBuffer());
+    const approvalPda = () =>
+      findPda(
+        Buffer.from("settlement_approval"),
+        platformPda.toBuffer(),
+        runId.toArrayLike(Buffer, "le", 8),
+        DEPOSIT.toArrayLike(Buffer, "le", 8),
+        sharesHash
+      );
+
+    const setCommittee = (approvers, threshold) =>
+      program.methods
+        .setSettlementCommittee(approvers, threshold)
+        .accounts({
+          ...adminAccounts(),
+          settlementCommittee: committeePda(),
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([platformAuthority])
+        .rpc();
+
+    const approveAs = (approver) =>
+      program.methods
+        .approveSettlement(runId, DEPOSIT, [...sharesHash])
+        .accounts({
+          platform: platformPda,
+          run: runPdaOf("run", runId),
+          settlementCommittee: committeePda(),
+          settlementApproval: approvalPda(),
+          approver: approver.keypair.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([approver.keypair])
+        .rpc();
+
+    const settle = () =>
+      program.methods
+        .settleRun(runId, DEPOSIT, participantShares, null)
+        .accounts(settleAccounts(runId, {
+          settlementCommittee: committeePda(),
+          settlementApproval: approvalPda(),
+        }))
+        .signers([platformAuthority])
+        .rpc();
+
+    before(async () => {
+      participant = await createFundedUser();
+      approverA = await createFundedUser();
+      approverB = await createFundedUser();
+      outsider = await createFundedUser();
+
+      runId = await createTestRun();
+      await depositInto(runId, participant, DEPOSIT);
+      await startTestRun(runId, [participant]);
+      await prepareShareTable(runId);
+
+      // Committee members approve the SHA-256 of the Borsh-encoded share list
+      participantShares = [{ user: participant.keypair.publicKey, shareAmount: DEPOSIT }];
+      const encoded = Buffer.alloc(4 + 40 * participantShares.length);
+      encoded.writeUInt32LE(participantShares.length, 0);
+      participantShares.forEach(({ user, shareAmount }, i) => {
+        user.toBuffer().copy(encoded, 4 + 40 * i);
+        shareAmount.toArrayLike(Buffer, "le", 8).copy(encoded, 4 + 40 * i + 32);
+      });
+      sharesHash = createHash("sha256").update(encoded).digest();
+
+      await setCommittee([approverA.keypair.publicKey, approverB.keypair.publicKey], 2);
+    });
+
+    it("Fails an approval from a wallet outside the committee", async () => {
+      try {
+        await approveAs(outsider);
+        assert.fail("Should have thrown error");
+      } catch (err) {
+        assert.ok(err.toString().includes("NotSettlementApprover"));
+      }
+    });
+
+    it("Fails to settle below the approval threshold", async () => {
+      await approveAs(approverA);
+
+      try {
+        await settle();
+        assert.fail("Should have thrown error");
+      } catch (err) {
+        assert.ok(err.toString().includes("SettlementNotApproved"));
+      }
+    });
+
+    it("Settles once the threshold has approved", async () => {
+      await approveAs(approverB);
+
+      const approval = await program.account.settlementApproval.fetch(approvalPda());
+      assert.equal(approval.approvals, 0b11);
+
+      await settle();
+
+      const run = await program.account.run.fetch(runPdaOf("run", runId));
+      assert.equal(run.status.settled !== undefined, true);
+      assert.equal(Buffer.from(run.sharesHash).toString("hex"), sharesHash.toString("hex"));
+    });
+
+    it("Fails to replace a committee in force outside the timelock", async () => {
+      try {
+        await setCommittee([outsider.keypair.publicKey], 1);
+        assert.fail("Should have thrown error");
+      } catch (err) {
+        assert.ok(err.toString().includes("SettlementCommitteeTimelocked"));
+      }
+    });
+  });
+
+  describe("Admin Functions", () => {
+    it("Pauses the platform", async () => {
+      const tx = await program.methods
+        .pausePlatform()
+        .accounts({
+          platform: platformPda,
+          pauser: platformAuthority.publicKey,
         })
         .signers([platformAuthority])
         .rpc();